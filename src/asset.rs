@@ -0,0 +1,170 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use base64::Engine;
+use maplit::hashmap;
+use sha2::{Digest, Sha384};
+use toml_edit::{value, Table};
+
+use crate::error::{Diagnostic, Error, Result};
+use crate::html::HTMLView;
+use crate::layout::common::SITE_TABLE;
+use crate::tailwind::generate_css;
+
+/// output 안에 vendored 파일을 쓰는 폴더 이름
+pub const ASSETS_DIR: &str = "assets";
+
+/// html을 쓸 때 페이지 깊이에 맞는 상대 경로로 바뀌는 자리
+pub const ASSETS_MARKER: &str = "{assets}";
+
+/// [site] table의 key, 파일 이름마다 integrity 값이 들어있다.
+pub const ASSETS_KEY: &str = "assets";
+
+/// vendored 폴더에 둘 파일과 받을 수 있는 곳
+pub struct Vendor {
+    pub file: &'static str,
+    pub url: &'static str,
+}
+
+pub const HIGHLIGHT_LIGHT: Vendor = Vendor {
+    file: "atom-one-light.min.css",
+    url: "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/styles/atom-one-light.min.css",
+};
+pub const HIGHLIGHT_DARK: Vendor = Vendor {
+    file: "atom-one-dark.min.css",
+    url: "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/styles/atom-one-dark.min.css",
+};
+pub const HIGHLIGHT: Vendor = Vendor {
+    file: "highlight.min.js",
+    url: "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/highlight.min.js",
+};
+pub const HIGHLIGHT_GO: Vendor = Vendor {
+    file: "go.min.js",
+    url: "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/languages/go.min.js",
+};
+pub const TAILWIND: Vendor = Vendor {
+    file: "tailwind.js",
+    url: "https://cdn.tailwindcss.com",
+};
+/// 미리 만들거나 빌드 때 만든 tailwind css, 있으면 tailwind.js 대신 쓴다.
+pub const TAILWIND_CSS: Vendor = Vendor {
+    file: "tailwind.css",
+    url: "",
+};
+pub const FLOWBITE: Vendor = Vendor {
+    file: "flowbite.min.js",
+    url: "https://flowbite.com/docs/flowbite.min.js?v=3.1.2a",
+};
+
+/// ### bundle assets
+/// vendor 폴더의 파일들을 output/assets 에 복사하고 파일 이름마다 integrity 값을 돌려준다.
+///
+/// tailwind.css가 있으면 tailwind.js는 없어도 된다. with_tailwind가 false면 tailwind는 빌드 때 만들므로 복사하지 않는다.
+pub fn bundle_assets(vendor: &Path, output: &Path, with_tailwind: bool) -> Result<Table> {
+    let tailwind = if vendor.join(TAILWIND_CSS.file).exists() { TAILWIND_CSS } else { TAILWIND };
+    let assets_dir = output.join(ASSETS_DIR);
+    fs::create_dir_all(&assets_dir).map_err(|e| Error::io(&assets_dir, e))?;
+
+    let mut vendoreds = vec![HIGHLIGHT_LIGHT, HIGHLIGHT_DARK, HIGHLIGHT, HIGHLIGHT_GO, FLOWBITE];
+    if with_tailwind {
+        vendoreds.push(tailwind);
+    }
+    let mut integrities = Table::new();
+    for vendored in vendoreds {
+        let source = vendor.join(vendored.file);
+        let bytes = fs::read(&source)
+            .map_err(|e| Error::io(&source, format!("missing vendored asset, download it from {} ({})", vendored.url, e)))?;
+        let target = assets_dir.join(vendored.file);
+        fs::write(&target, &bytes).map_err(|e| Error::io(&target, e))?;
+        integrities.insert(vendored.file, value(integrity(&bytes)));
+    }
+    Ok(integrities)
+}
+
+/// sha384-... 꼴의 subresource integrity 값
+pub fn integrity(bytes: &[u8]) -> String {
+    let hash = Sha384::digest(bytes);
+    format!("sha384-{}", base64::engine::general_purpose::STANDARD.encode(hash))
+}
+
+/// [site] table에 assets가 있으면 그 integrity 값들
+pub fn bundled_assets(layout_tables: &HashMap<String, Table>) -> Option<&Table> {
+    layout_tables
+        .get(SITE_TABLE)
+        .and_then(|site| site.get(ASSETS_KEY))
+        .and_then(|assets| assets.as_table())
+}
+
+/// ### head assets
+/// highlight.js, tailwind, flowbite를 부르는 head 태그들
+///
+/// bundle된 assets가 있으면 {assets}/파일 이름과 integrity로, 없으면 CDN으로 부른다.
+/// integrity가 빈 값이면 빌드 때 만드는 파일이라 integrity 없이 부른다.
+pub fn head_assets(is_dark: bool, assets: Option<&Table>) -> Vec<HTMLView> {
+    let highlight_style = if is_dark { HIGHLIGHT_DARK } else { HIGHLIGHT_LIGHT };
+    let tailwind = match assets {
+        Some(assets) if assets.contains_key(TAILWIND_CSS.file) => TAILWIND_CSS,
+        _ => TAILWIND,
+    };
+    let source = |vendored: &Vendor| -> HashMap<String, String> {
+        match assets.and_then(|x| x.get(vendored.file)).and_then(|x| x.as_str()) {
+            Some("") => hashmap! { "src".to_string() => format!("{}/{}", ASSETS_MARKER, vendored.file) },
+            Some(integrity) => hashmap! {
+                "src".to_string() => format!("{}/{}", ASSETS_MARKER, vendored.file),
+                "integrity".to_string() => integrity.to_string(),
+            },
+            None => hashmap! { "src".to_string() => vendored.url.to_string() },
+        }
+    };
+    let stylesheet = |vendored: &Vendor| {
+        let mut attrs = source(vendored);
+        if let Some(src) = attrs.remove("src") {
+            attrs.insert("href".to_string(), src);
+        }
+        attrs.insert("rel".to_string(), "stylesheet".to_string());
+        HTMLView::new("link", attrs, "", vec![])
+    };
+
+    let highlight_all = HTMLView::new("script", hashmap! {}, "hljs.highlightAll();", vec![]);
+    let tailwind = if tailwind.file == TAILWIND_CSS.file {
+        stylesheet(&tailwind)
+    } else {
+        HTMLView::new("script", source(&tailwind), "", vec![])
+    };
+    vec![
+        stylesheet(&highlight_style),
+        HTMLView::new("script", source(&HIGHLIGHT), "", vec![]),
+        HTMLView::new("script", source(&HIGHLIGHT_GO), "", vec![]),
+        highlight_all,
+        tailwind,
+        HTMLView::new("script", source(&FLOWBITE), "", vec![]),
+    ]
+}
+
+/// ### write tailwind
+/// classes만 담은 tailwind css를 output/assets/tailwind.css 에 쓰고, 만들 수 없던 class들을 경고로 돌려준다.
+pub fn write_tailwind(output: &Path, classes: &BTreeSet<String>) -> Result<Vec<Diagnostic>> {
+    let assets_dir = output.join(ASSETS_DIR);
+    fs::create_dir_all(&assets_dir).map_err(|e| Error::io(&assets_dir, e))?;
+    let target = assets_dir.join(TAILWIND_CSS.file);
+    let (css, unsupported) = generate_css(classes);
+    fs::write(&target, css).map_err(|e| Error::io(&target, e))?;
+    Ok(unsupported
+        .iter()
+        .map(|class| Diagnostic::warning(&target, "", format!("unsupported tailwind class \"{}\" is left out", class)))
+        .collect())
+}
+
+/// output 폴더에서 depth 만큼 들어간 html의 {assets}를 "../../assets" 같은 상대 경로로 바꾼다.
+pub fn relative_assets(html: &str, depth: usize) -> String {
+    if !html.contains(ASSETS_MARKER) {
+        return html.to_string();
+    }
+    let prefix = if depth == 0 {
+        ASSETS_DIR.to_string()
+    } else {
+        format!("{}{}", "../".repeat(depth), ASSETS_DIR)
+    };
+    html.replace(ASSETS_MARKER, &prefix)
+}
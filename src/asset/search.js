@@ -0,0 +1,111 @@
+// prema search: [data-prema-search] 상자마다 search.json을 읽어 결과를 보여준다.
+(function () {
+  if (window.premaSearch) {
+    return;
+  }
+  window.premaSearch = true;
+
+  var indexes = {};
+
+  function load(url) {
+    if (!indexes[url]) {
+      indexes[url] = fetch(url).then(function (response) {
+        return response.json();
+      });
+    }
+    return indexes[url];
+  }
+
+  function score(entry, terms) {
+    var title = entry.title.toLowerCase();
+    var headings = entry.headings.join(" ").toLowerCase();
+    var tags = entry.tags.join(" ").toLowerCase();
+    var text = entry.text.toLowerCase();
+    var total = 0;
+    for (var i = 0; i < terms.length; i++) {
+      var term = terms[i];
+      var found = 0;
+      if (title.indexOf(term) >= 0) found += 10;
+      if (headings.indexOf(term) >= 0) found += 5;
+      if (tags.indexOf(term) >= 0) found += 5;
+      if (text.indexOf(term) >= 0) found += 1;
+      if (found === 0) return 0;
+      total += found;
+    }
+    return total;
+  }
+
+  function snippet(text, term) {
+    var at = text.toLowerCase().indexOf(term);
+    if (at < 0) return text.slice(0, 120);
+    var start = Math.max(0, at - 40);
+    return (start > 0 ? "…" : "") + text.slice(start, start + 120);
+  }
+
+  function setup(box) {
+    var input = box.querySelector("input");
+    var list = box.querySelector("ul");
+    var template = box.querySelector("template");
+    var root = box.getAttribute("data-prema-root") || "";
+    var limit = parseInt(box.getAttribute("data-prema-limit") || "10", 10);
+
+    function render(results, terms) {
+      list.innerHTML = "";
+      results.slice(0, limit).forEach(function (entry) {
+        var item = template.content.firstElementChild.cloneNode(true);
+        var link = item.querySelector("a") || item;
+        link.setAttribute("href", root + entry.url);
+        var title = item.querySelector("[data-title]");
+        var text = item.querySelector("[data-snippet]");
+        if (title) title.textContent = entry.title;
+        if (text) text.textContent = snippet(entry.text, terms[0]);
+        list.appendChild(item);
+      });
+      list.classList.toggle("hidden", results.length === 0);
+    }
+
+    input.addEventListener("input", function () {
+      var terms = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+      if (terms.length === 0) {
+        render([], terms);
+        return;
+      }
+      load(box.getAttribute("data-prema-search")).then(function (entries) {
+        var results = entries
+          .map(function (entry) {
+            return { entry: entry, score: score(entry, terms) };
+          })
+          .filter(function (x) {
+            return x.score > 0;
+          })
+          .sort(function (a, b) {
+            return b.score - a.score;
+          })
+          .map(function (x) {
+            return x.entry;
+          });
+        render(results, terms);
+      });
+    });
+    input.addEventListener("keydown", function (event) {
+      if (event.key === "Escape") {
+        list.classList.add("hidden");
+      }
+    });
+    document.addEventListener("click", function (event) {
+      if (!box.contains(event.target)) {
+        list.classList.add("hidden");
+      }
+    });
+  }
+
+  function init() {
+    document.querySelectorAll("[data-prema-search]").forEach(setup);
+  }
+
+  if (document.readyState === "loading") {
+    document.addEventListener("DOMContentLoaded", init);
+  } else {
+    init();
+  }
+})();
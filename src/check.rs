@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use maplit::hashmap;
+use markdown::mdast::Node;
+use toml_edit::{Item, Table};
+
+use crate::common::SlashNormalize;
+use crate::error::{locate_key, Diagnostic, Location, Result};
+use crate::layout::common::read_document;
+use crate::layout::layouts_from_toml;
+use crate::layout::schema::validate_toml;
+use crate::markdown::common::frontmatter_line_count;
+use crate::markdown::metas_table_from_markdown;
+use crate::markdown::parser::get_node_for_markdown;
+use crate::page::{self, Page};
+
+/// nav에서 메뉴가 아닌 예약된 키
+pub(crate) const NAV_RESERVED_KEYS: [&str; 12] = [
+    "key", "shape", "width", "height", "background", "title", "values", "path", "dark", "headers", "custom_class", "align_absolute",
+];
+
+/// ### check site
+/// make page tree
+///
+/// resolve every link, path, image_path, markdown_path, layout and embed reference
+///
+/// return every failure with its source file and key
+pub fn check_site(md_root_path: &Path) -> Result<Vec<Diagnostic>> {
+    let page = page::read_dir_recursive(md_root_path)?;
+    let mut issues = vec![];
+    check_page(&page, md_root_path, hashmap! {}, &mut issues);
+    Ok(issues)
+}
+
+/// Page::inflate_html 과 같은 순서로 layout table을 물려주면서 검사한다.
+fn check_page(page: &Page, site_root: &Path, layout_tables: HashMap<String, Table>, issues: &mut Vec<Diagnostic>) {
+    let mut layout_tables = layout_tables;
+    let layout_path = page.layout_path();
+    match layout_path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => {
+            layout_tables.extend(layouts_from_toml(layout_path).unwrap_or(hashmap! {}));
+            check_toml(layout_path, site_root, &layout_tables, issues);
+        }
+        Some("md") => {
+            check_markdown(layout_path, page.path(), site_root, &layout_tables, issues);
+        }
+        _ => {}
+    }
+
+    // index가 아닌 markdown은 {stem}/index.html 로 만들어진다.
+    for md_path in page.markdowns() {
+        let stem = md_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        check_markdown(md_path, &page.path().join(stem), site_root, &layout_tables, issues);
+    }
+
+    for sub_page in page.pages() {
+        check_page(sub_page, site_root, layout_tables.clone(), issues);
+    }
+}
+
+fn check_toml(index_path: &Path, site_root: &Path, layout_tables: &HashMap<String, Table>, issues: &mut Vec<Diagnostic>) {
+    let source = fs::read_to_string(index_path).unwrap_or_default();
+    let doc = match read_document(index_path) {
+        Ok(doc) => doc,
+        Err(error) => {
+            issues.push(Diagnostic {
+                location: error.location(),
+                ..Diagnostic::error(index_path, "", format!("failed to read toml: {}", error))
+            });
+            return;
+        }
+    };
+
+    issues.extend(validate_toml(index_path, layout_tables));
+
+    let first = issues.len();
+    for (key, item) in doc.as_table() {
+        if let Item::Table(table) = item {
+            check_table(index_path, key, table, site_root, layout_tables, issues);
+        }
+    }
+    for issue in &mut issues[first..] {
+        issue.location = locate_key(&source, &issue.key);
+    }
+}
+
+fn check_table(index_path: &Path, key_path: &str, table: &Table, site_root: &Path, layout_tables: &HashMap<String, Table>, issues: &mut Vec<Diagnostic>) {
+    let folder = index_path.parent().unwrap_or(Path::new("."));
+    let mut push = |key: &str, message: String| {
+        issues.push(Diagnostic::error(index_path, &format!("{}.{}", key_path, key), message));
+    };
+
+    if let Some(path) = str_value(table, "path") {
+        if !link_exists(site_root, folder, path) {
+            push("path", format!("broken link \"{}\"", path));
+        }
+    }
+    if let Some(image_path) = str_value(table, "image_path") {
+        if !is_external(image_path) && !folder.join(image_path).is_file() {
+            push("image_path", format!("missing image \"{}\"", image_path));
+        }
+    }
+    if let Some(markdown_path) = str_value(table, "markdown_path") {
+        if !folder.join(markdown_path).is_file() {
+            push("markdown_path", format!("missing markdown \"{}\"", markdown_path));
+        }
+    }
+    for key in ["layout", "pagination", "group_layout"] {
+        if let Some(layout) = str_value(table, key) {
+            if !layout_tables.contains_key(layout) {
+                push(key, format!("unknown layout \"{}\"", layout));
+            }
+        }
+    }
+
+    let shape = table.get("shape").and_then(|item| item.as_str()).unwrap_or("");
+    if shape == "nav" {
+        // nav의 하위 table은 view가 아니라 dropdown 메뉴다.
+        for (menu, item) in table.iter().filter(|(k, _)| !NAV_RESERVED_KEYS.contains(k)) {
+            let links: Vec<(String, &str)> = match item {
+                Item::Value(value) => value.as_str().map(|s| vec![(menu.to_string(), s)]).unwrap_or_default(),
+                Item::Table(sub_menus) => sub_menus
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (format!("{}.{}", menu, k), s)))
+                    .collect(),
+                _ => vec![],
+            };
+            for (key, link) in links {
+                if !has_placeholder(link) && !link_exists(site_root, site_root, &link.ensure_slashes()) {
+                    push(&key, format!("broken link \"{}\"", link));
+                }
+            }
+        }
+        return;
+    }
+
+    for (key, item) in table.iter() {
+        if let Item::Table(sub_table) = item {
+            check_table(index_path, &format!("{}.{}", key_path, key), sub_table, site_root, layout_tables, issues);
+        }
+    }
+}
+
+/// base_dir은 markdown이 html로 만들어졌을 때의 위치
+fn check_markdown(md_path: &Path, base_dir: &Path, site_root: &Path, layout_tables: &HashMap<String, Table>, issues: &mut Vec<Diagnostic>) {
+    if let Ok(metas) = metas_table_from_markdown(md_path) {
+        for key in ["header", "footer"] {
+            if let Some(layout) = metas.get(key).and_then(|v| v.as_str()) {
+                if !layout_tables.contains_key(layout) {
+                    issues.push(Diagnostic::error(md_path, key, format!("unknown layout \"{}\"", layout)));
+                }
+            }
+        }
+    }
+
+    let line_offset = fs::read_to_string(md_path)
+        .map(|content| frontmatter_line_count(&content))
+        .unwrap_or(0);
+    match get_node_for_markdown(md_path) {
+        Ok(node) => check_markdown_node(&node, md_path, base_dir, site_root, line_offset, issues),
+        Err(error) => issues.push(Diagnostic {
+            location: error.location(),
+            ..Diagnostic::error(md_path, "", format!("failed to parse markdown: {}", error))
+        }),
+    }
+}
+
+fn check_markdown_node(node: &Node, md_path: &Path, base_dir: &Path, site_root: &Path, line_offset: usize, issues: &mut Vec<Diagnostic>) {
+    let target = match node {
+        Node::Link(link) => Some(("link", link.url.as_str())),
+        Node::Image(image) => Some(("image", image.url.as_str())),
+        _ => None,
+    };
+    if let Some((kind, url)) = target {
+        if !link_exists(site_root, base_dir, url) {
+            issues.push(Diagnostic {
+                location: node.position().map(|p| Location { line: p.start.line + line_offset, column: p.start.column }),
+                ..Diagnostic::error(md_path, kind, format!("broken {} \"{}\"", kind, url))
+            });
+        }
+    }
+    for child in node.children().into_iter().flatten() {
+        check_markdown_node(child, md_path, base_dir, site_root, line_offset, issues);
+    }
+}
+
+fn str_value<'a>(table: &'a Table, key: &str) -> Option<&'a str> {
+    table
+        .get(key)
+        .and_then(|item| item.as_str())
+        .filter(|s| !s.is_empty() && !has_placeholder(s))
+}
+
+fn has_placeholder(s: &str) -> bool {
+    s.contains('{')
+}
+
+fn is_external(link: &str) -> bool {
+    link.contains("://")
+        || link.starts_with("//")
+        || link.starts_with("mailto:")
+        || link.starts_with("tel:")
+        || link.starts_with("data:")
+}
+
+/// "/"로 시작하면 site_root 기준, 아니면 base_dir 기준으로 찾는다.
+///
+/// 폴더, 파일, 또는 같은 이름의 markdown 파일이 있으면 존재하는 것으로 본다.
+fn link_exists(site_root: &Path, base_dir: &Path, link: &str) -> bool {
+    if link.is_empty() || link.starts_with('#') || is_external(link) || has_placeholder(link) {
+        return true;
+    }
+    let link = link
+        .split(['#', '?'])
+        .next()
+        .unwrap_or("");
+    let (base, relative) = match link.strip_prefix('/') {
+        Some(rest) => (site_root, rest),
+        None => (base_dir, link),
+    };
+    let relative = relative.trim_end_matches('/');
+    if relative.is_empty() {
+        return base.is_dir();
+    }
+    let target = base.join(relative);
+    target.exists() || (target.extension().is_none() && target.with_extension("md").is_file())
+}
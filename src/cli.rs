@@ -1,6 +1,10 @@
 
 use crate::server;
+use crate::check;
+use crate::migrate;
+use crate::error::{Error, Result};
 use crate::page;
+use crate::site::Site;
 use crate::file;
 use crate::server::run_server;
 
@@ -23,6 +27,10 @@ enum Cli {
     New(NewCommand),
     #[structopt(name = "html", about = "Converting markdown files to html files")]
     Html(HtmlCommand),
+    #[structopt(name = "check", about = "Checking broken links and missing assets")]
+    Check(CheckCommand),
+    #[structopt(name = "migrate", about = "Converting option.toml files to index.toml layouts and frontmatter")]
+    Migrate(MigrateCommand),
 }
 
 #[derive(Debug, StructOpt)]
@@ -36,13 +44,64 @@ struct NewCommand {
 #[derive(Debug, StructOpt)]
 struct HtmlCommand {
     md_path: String,
-    html_path: String,
+    /// output folder, defaults to [build] output of prema.toml or "html"
+    html_path: Option<String>,
+
+    /// config file to use instead of {md_path}/prema.toml
+    #[structopt(long)]
+    config: Option<String>,
+
+    #[structopt(long)]
+    base_url: Option<String>,
+
+    /// build markdowns with publish: false as well
+    #[structopt(long)]
+    drafts: bool,
+
+    /// leave out markdowns with publish: false even if prema.toml sets drafts
+    #[structopt(long, conflicts_with = "drafts")]
+    no_drafts: bool,
 
     #[structopt(long)]
     server: bool,
+
+    /// folder with vendored tailwind, flowbite and highlight.js, used instead of the CDN
+    #[structopt(long)]
+    assets: Option<String>,
+
+    /// generate assets/tailwind.css from the classes the site uses, instead of the Play CDN
+    #[structopt(long)]
+    tailwind: bool,
+
+    /// use the Play CDN even if prema.toml sets tailwind
+    #[structopt(long, conflicts_with = "tailwind")]
+    no_tailwind: bool,
+
+    /// write assets/search.json even when no page uses the search shape
+    #[structopt(long)]
+    search: bool,
+
+    /// write assets/search.json only when a page uses the search shape, even if prema.toml sets search
+    #[structopt(long, conflicts_with = "search")]
+    no_search: bool,
+
+    /// stop on layout errors in index.toml instead of reporting them as warnings
+    #[structopt(long)]
+    strict: bool,
+}
+
+#[derive(Debug, StructOpt)]
+struct CheckCommand {
+    md_path: String,
 }
 
-pub fn run_cli() -> Result<(), String> {
+#[derive(Debug, StructOpt)]
+struct MigrateCommand {
+    md_path: String,
+}
+
+/// 명령줄 인자를 읽어서 new, html, check, migrate 중 하나를 실행한다.
+pub fn run_cli() -> Result<()> {
     // Match on subcommands and handle errors
     let cli = Cli::from_iter_safe(args()).unwrap_or_else(|e| {
         println!("{e}");
@@ -50,6 +109,12 @@ pub fn run_cli() -> Result<(), String> {
         println!("    prema new {{name}}");
         println!("    prema new {{name}} --tags \"ios, android\"");
         println!("    prema html {{md_path}} {{html_path}}");
+        println!("    prema html {{md_path}} --config prema.toml --base-url https://example.com/blog/");
+        println!("    prema html {{md_path}} {{html_path}} --assets {{vendor_path}}");
+        println!("    prema html {{md_path}} {{html_path}} --tailwind");
+        println!("    prema html {{md_path}} --no-drafts --no-search");
+        println!("    prema check {{md_path}}");
+        println!("    prema migrate {{md_path}}");
         std::process::exit(1); // Exit with an error code
     });
 
@@ -64,11 +129,36 @@ pub fn run_cli() -> Result<(), String> {
         },
         Cli::Html(cmd) => {
             let md_root_path = Path::new(cmd.md_path.as_str());
-            let html_root_path = Path::new(cmd.html_path.as_str());
-            generate_html(md_root_path, html_root_path)?;
+            // prema.toml 보다 명령줄의 값이 먼저
+            let mut site = match &cmd.config {
+                Some(config) => Site::new(md_root_path).config(config)?,
+                None => Site::from_config(md_root_path)?,
+            };
+            if let Some(html_path) = &cmd.html_path {
+                site = site.output(html_path);
+            }
+            if let Some(base_url) = &cmd.base_url {
+                site = site.base_url(base_url);
+            }
+            if let Some(drafts) = flag(cmd.drafts, cmd.no_drafts) {
+                site = site.drafts(drafts);
+            }
+            if let Some(assets) = &cmd.assets {
+                site = site.assets(assets);
+            }
+            if let Some(tailwind) = flag(cmd.tailwind, cmd.no_tailwind) {
+                site = site.tailwind(tailwind);
+            }
+            if let Some(search) = flag(cmd.search, cmd.no_search) {
+                site = site.search(search);
+            }
+            if cmd.strict {
+                site = site.strict(true);
+            }
+            build_site(&site)?;
             
             if cmd.server {
-                server::run_server(html_root_path)?;
+                server::run_server(site.output_dir())?;
             }
 
             // let mut input = String::new();
@@ -81,75 +171,113 @@ pub fn run_cli() -> Result<(), String> {
             // println!("입력한 내용: {}", input);
             Ok(())
         }
+        Cli::Check(cmd) => {
+            let md_root_path = Path::new(cmd.md_path.as_str());
+            let issues = check::check_site(md_root_path)?;
+            for issue in &issues {
+                println!("{}", issue);
+            }
+            let errors = issues.iter().filter(|x| x.is_error()).count();
+            let warnings = issues.len() - errors;
+            if issues.is_empty() {
+                println!("no problems found");
+                Ok(())
+            } else if errors == 0 {
+                println!("{} warning(s) found", warnings);
+                Ok(())
+            } else {
+                Err(Error::Diagnostics(issues))
+            }
+        }
+        Cli::Migrate(cmd) => {
+            let md_root_path = Path::new(cmd.md_path.as_str());
+            let migration = migrate::migrate_site(md_root_path)?;
+            for path in &migration.written {
+                println!("written {}", path.display());
+            }
+            for issue in &migration.issues {
+                println!("{}", issue);
+            }
+            println!("{} file(s) written, {} thing(s) not converted", migration.written.len(), migration.issues.len());
+            Ok(())
+        }
+    }
+}
+
+/// --{flag} 이면 true, --no-{flag} 이면 false, 둘 다 없으면 prema.toml의 값을 쓰도록 None
+fn flag(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
     }
 }
 
 /// ### generate html
+/// read prema.toml in md_root_path
 /// make page tree
 /// validate pages and update
 /// make html files
-pub fn generate_html(md_root_path: &Path, html_root_path: &Path) -> Result<(), String> {
-    let mut page = page::read_dir_recursive(md_root_path)?;
-    page.inflate_html(hashmap! {})?;
-    page.make_html_file(html_root_path)?;
-    // run_server(html_root_path)?;
+pub fn generate_html(md_root_path: &Path, html_root_path: &Path) -> Result<()> {
+    build_site(&Site::from_config(md_root_path)?.output(html_root_path))
+}
+
+fn build_site(site: &Site) -> Result<()> {
+    let report = site.build().inspect_err(|error| {
+        if let Error::Diagnostics(diagnostics) = error {
+            for diagnostic in diagnostics {
+                eprintln!("{}", diagnostic);
+            }
+        }
+    })?;
+    for diagnostic in &report.diagnostics {
+        eprintln!("{}", diagnostic);
+    }
     Ok(())
 }
 
 /// ### make new page
 /// make {name} directory
 ///
-/// make {name}.md in directory
-///
-/// make option.toml in directory
-fn make_new_page(path: &Path, name: &str, tags: Vec<String>) -> Result<(), &'static str> {
+/// make {name}.md in directory, with title, created and tags in frontmatter
+fn make_new_page(path: &Path, name: &str, tags: Vec<String>) -> Result<()> {
     let dir_path = path.join(name);
     if dir_path.exists() {
         println!("Directory '{}' already exists, returning.", name);
-        return Err("Directory already exists"); // Return early if the directory exists
+        return Err(Error::site(&dir_path, "directory already exists")); // Return early if the directory exists
     }
 
     fs::create_dir_all(&dir_path)
-        .ok()
-        .ok_or("faild to create directory")?;
+        .map_err(|e| Error::io(&dir_path, e))?;
 
     // Create the .md file in the new directory
     if let Some(last_name) = dir_path.file_name() {
         let last_name = last_name.to_str().unwrap();
 
         let md_path = dir_path.join(format!("{}.md", last_name));
-        let mut md_file = fs::File::create(md_path)
-            .ok()
-            .ok_or("faild to create file")?;
-
-        writeln!(md_file, "# {} Page", last_name)
-            .ok()
-            .ok_or("faild to write to file")?;
-
-        let toml_path = dir_path.join("option.toml");
-        let mut toml_file = fs::File::create(toml_path)
-            .ok()
-            .ok_or("faild to create file")?;
+        let mut md_file = fs::File::create(&md_path)
+            .map_err(|e| Error::io(&md_path, e))?;
 
+        let now = Local::now();
+        let formatted_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
+        writeln!(md_file, "---").map_err(|e| Error::io(&md_path, e))?;
+        writeln!(md_file, "title: \"{}\"", last_name).map_err(|e| Error::io(&md_path, e))?;
+        writeln!(md_file, "created: \"{}\"", formatted_time).map_err(|e| Error::io(&md_path, e))?;
         if !tags.is_empty() {
-            writeln!(toml_file, "[basic]").ok().ok_or("")?;
             let tags_str = tags.iter()
                   .map(|s| format!("\"{}\"", s.trim())) 
                   .collect::<Vec<String>>()
-                .join(",");
-            writeln!(toml_file, "tags = [{}]", tags_str)
-                .ok()
-                .ok_or("")?;
-
-            let now = Local::now();
-            let formatted_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-            writeln!(toml_file, "created = \"{}\"", formatted_time)
-                .ok()
-                .ok_or("")?;
+                .join(", ");
+            writeln!(md_file, "tags: [{}]", tags_str)
+                .map_err(|e| Error::io(&md_path, e))?;
         }
+        writeln!(md_file, "---").map_err(|e| Error::io(&md_path, e))?;
+
+        writeln!(md_file, "# {} Page", last_name)
+            .map_err(|e| Error::io(&md_path, e))?;
 
         Ok(())
     } else {
-        return Err("faild to create file");
+        return Err(Error::site(&dir_path, "failed to get directory name"));
     }
-}
\ No newline at end of file
+}
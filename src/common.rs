@@ -1,5 +1,7 @@
 use std::{fs, path::Path};
 
+use crate::error::{Error, Result};
+
 /// ``` 코드 에 해당하는 부분들이 제대로 나타나지 않기 때문에
 /// 아래 코드로 완성된 코드를 재배치한다.
 pub fn remove_code_indentation(html: String) -> String {
@@ -39,23 +41,23 @@ pub fn remove_code_indentation(html: String) -> String {
     remove_first
 }
 
-pub fn copy_img_files_to_path(src_dir: &Path, dest_dir: &Path) -> Result<(), String> {
+pub fn copy_img_files_to_path(src_dir: &Path, dest_dir: &Path) -> Result<()> {
   // src_dir이 디렉토리인지 확인
   if !src_dir.is_dir() {
-      return Err(format!("Source directory does not exist or is not a directory: {:?}", src_dir));
+      return Err(Error::site(src_dir, "source directory does not exist or is not a directory"));
   }
 
   // dest_dir이 없으면 생성
   if !dest_dir.exists() {
       fs::create_dir_all(dest_dir)
-          .map_err(|e| format!("Failed to create destination directory: {}", e))?;
+          .map_err(|e| Error::io(dest_dir, e))?;
   }
 
   // src_dir 읽기
   for entry_res in fs::read_dir(src_dir)
-      .map_err(|e| format!("Failed to read source directory: {}", e))? {
+      .map_err(|e| Error::io(src_dir, e))? {
 
-      let entry = entry_res.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+      let entry = entry_res.map_err(|e| Error::io(src_dir, e))?;
       let path = entry.path();
 
       // 파일이고 확장자가 jpg, jpeg, png인지 확인 (대소문자 구분 없이)
@@ -64,11 +66,11 @@ pub fn copy_img_files_to_path(src_dir: &Path, dest_dir: &Path) -> Result<(), Str
               let ext_lower = ext.to_lowercase();
               if ext_lower == "jpg" || ext_lower == "jpeg" || ext_lower == "png" || ext_lower == "svg" {
                   let file_name = path.file_name()
-                      .ok_or_else(|| "Failed to get file name".to_string())?;
+                      .ok_or_else(|| Error::site(&path, "failed to get file name"))?;
                   let dest_path = dest_dir.join(file_name);
 
                   fs::copy(&path, &dest_path)
-                      .map_err(|e| format!("Failed to copy {:?} to {:?}: {}", path, dest_path, e))?;
+                      .map_err(|e| Error::io(&dest_path, format!("failed to copy {:?}, {}", path, e)))?;
               }
           }
       }
@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::error::{Diagnostic, Error, Result};
+use crate::head::is_html;
+
+/// 사이트 폴더에 두는 설정 파일 이름
+pub const CONFIG_FILE: &str = "prema.toml";
+
+/// prema.toml의 table과 받을 수 있는 key들, [plugins]는 아무 key나 받는다.
+const CONFIG_KEYS: [(&str, &[&str]); 5] = [
+    ("site", &["title", "description", "base_url", "lang", "image"]),
+    ("build", &["output", "drafts", "assets", "tailwind", "search"]),
+    ("defaults", &["dark", "header", "footer"]),
+    ("head", &["head_extra", "body_end", "extra_css", "extra_js"]),
+    ("plugins", &[]),
+];
+
+/// ### config
+/// 사이트 폴더의 prema.toml
+///
+/// ```toml
+/// [site]
+/// title = "My Blog"
+/// base_url = "https://example.com/blog/"
+/// lang = "en"
+///
+/// [build]
+/// output = "../html"
+/// tailwind = true
+///
+/// [defaults]
+/// dark = false
+/// header = "nav"
+/// footer = "footer"
+///
+/// [head]
+/// body_end = "analytics.html"
+///
+/// [plugins.comments]
+/// repo = "me/blog"
+/// ```
+///
+/// 파일 경로는 prema.toml이 있는 폴더 기준이다.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub base_url: Option<String>,
+    pub lang: Option<String>,
+    pub image: Option<String>,
+    pub output: Option<PathBuf>,
+    pub drafts: Option<bool>,
+    pub assets: Option<PathBuf>,
+    pub tailwind: Option<bool>,
+    pub search: Option<bool>,
+    pub dark: Option<bool>,
+    pub header: Option<String>,
+    pub footer: Option<String>,
+    pub head_extra: Vec<String>,
+    pub body_end: Vec<String>,
+    pub extra_css: Vec<String>,
+    pub extra_js: Vec<String>,
+    /// [plugins], 그대로 [site] table의 plugins로 물려준다.
+    pub plugins: Table,
+    /// 모르는 table이나 key
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Config {
+    /// input 폴더에 prema.toml이 있으면 그 경로
+    pub fn find(input: &Path) -> Option<PathBuf> {
+        Some(input.join(CONFIG_FILE)).filter(|path| path.is_file())
+    }
+
+    /// ### load
+    /// prema.toml을 읽는다. 값의 형식이 틀리면 에러, 모르는 key는 경고로 남긴다.
+    pub fn load(path: &Path) -> Result<Config> {
+        let source = std::fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        let doc = source.parse::<DocumentMut>().map_err(|e| Error::toml(path, &source, &e))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+
+        let mut config = Config::default();
+        for (key, item) in doc.as_table() {
+            let Some(keys) = CONFIG_KEYS.iter().find(|(k, _)| *k == key).map(|(_, keys)| keys) else {
+                config.diagnostics.push(Diagnostic::warning(path, key, format!("unknown table \"{}\" in {}", key, CONFIG_FILE)));
+                continue;
+            };
+            let Some(table) = item.as_table() else {
+                return Err(Error::layout(path, key, format!("[{}] must be a table", key)));
+            };
+            if key == "plugins" {
+                config.plugins = table.clone();
+                continue;
+            }
+            for (name, _) in table.iter() {
+                if !keys.contains(&name) {
+                    let message = format!("unknown key \"{}\", expected one of {}", name, keys.join(", "));
+                    config.diagnostics.push(Diagnostic::warning(path, &format!("{}.{}", key, name), message));
+                }
+            }
+        }
+
+        let text = |table: &str, key: &str| -> Result<Option<String>> {
+            match doc.get(table).and_then(|x| x.get(key)) {
+                None => Ok(None),
+                Some(item) => item
+                    .as_str()
+                    .map(|x| Some(x.to_string()))
+                    .ok_or_else(|| Error::layout(path, &format!("{}.{}", table, key), "expected a string")),
+            }
+        };
+        let flag = |table: &str, key: &str| -> Result<Option<bool>> {
+            match doc.get(table).and_then(|x| x.get(key)) {
+                None => Ok(None),
+                Some(item) => item
+                    .as_bool()
+                    .map(Some)
+                    .ok_or_else(|| Error::layout(path, &format!("{}.{}", table, key), "expected true or false")),
+            }
+        };
+        // 문자열 하나나 문자열 배열, 파일 경로는 prema.toml 폴더 기준으로 바꾼다.
+        let files = |key: &str| -> Result<Vec<String>> {
+            let strings = match doc.get("head").and_then(|x| x.get(key)) {
+                None => vec![],
+                Some(Item::Value(value)) if value.is_str() => vec![value.as_str().unwrap_or_default().to_string()],
+                Some(item) => item
+                    .as_array()
+                    .filter(|arr| arr.iter().all(|x| x.is_str()))
+                    .map(|arr| arr.iter().filter_map(|x| x.as_str()).map(|x| x.to_string()).collect())
+                    .ok_or_else(|| Error::layout(path, &format!("head.{}", key), "expected a string or an array of strings"))?,
+            };
+            Ok(strings.into_iter().map(|x| relative_to(dir, &x)).collect())
+        };
+
+        config.title = text("site", "title")?;
+        config.description = text("site", "description")?;
+        config.base_url = text("site", "base_url")?;
+        config.lang = text("site", "lang")?;
+        config.image = text("site", "image")?;
+        config.output = text("build", "output")?.map(|x| dir.join(x));
+        config.drafts = flag("build", "drafts")?;
+        config.assets = text("build", "assets")?.map(|x| dir.join(x));
+        config.tailwind = flag("build", "tailwind")?;
+        config.search = flag("build", "search")?;
+        config.dark = flag("defaults", "dark")?;
+        config.header = text("defaults", "header")?;
+        config.footer = text("defaults", "footer")?;
+        config.head_extra = files("head_extra")?;
+        config.body_end = files("body_end")?;
+        config.extra_css = files("extra_css")?;
+        config.extra_js = files("extra_js")?;
+        Ok(config)
+    }
+}
+
+/// html이나 주소가 아니면 dir 기준 경로로
+pub(crate) fn relative_to(dir: &Path, value: &str) -> String {
+    if is_html(value) || value.contains("://") || value.starts_with("//") {
+        return value.to_string();
+    }
+    dir.join(value).to_string_lossy().to_string()
+}
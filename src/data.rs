@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{Array, DocumentMut, InlineTable, Table, Value};
+
+use crate::error::{Error, Result};
+use crate::yaml::yaml_to_toml_edit_value;
+
+/// 사이트 폴더 아래 data 파일을 두는 폴더
+pub const DATA_DIR: &str = "data";
+
+/// layout table들과 함께 물려주는 data table 이름, {data.company.name} 처럼 찾는다.
+///
+/// Site가 data 폴더를 한 번 읽어서 [site] 옆에 넣는다.
+pub const DATA_KEY: &str = "data";
+
+/// "data.company.name" 처럼 data로 시작하는 key path의 값, 아니면 None
+pub fn lookup_data<'a>(layout_tables: &'a HashMap<String, Table>, key: &str) -> Option<&'a Value> {
+    let mut keys = key.strip_prefix(DATA_KEY)?.strip_prefix('.')?.split('.');
+    let mut found = layout_tables.get(DATA_KEY)?.get(keys.next()?)?;
+    for k in keys {
+        found = found.get(k)?;
+    }
+    found.as_value()
+}
+
+const DATA_EXTENSIONS: [&str; 5] = ["toml", "yaml", "yml", "json", "csv"];
+
+/// ### load data
+/// data 폴더의 toml, yaml, json, csv 파일을 파일 이름을 key로 하는 inline table로 읽는다.
+///
+/// data/company.toml 의 name은 {data.company.name}, 하위 폴더는 한 단계 안쪽 table이 된다.
+pub fn load_data(dir: &Path) -> Result<InlineTable> {
+    let mut data = InlineTable::new();
+    if !dir.is_dir() {
+        return Ok(data);
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| Error::io(dir, e))?
+        .filter_map(|x| x.ok())
+        .map(|x| x.path())
+        .collect();
+    paths.sort();
+    for path in paths {
+        let stem = path.file_stem().and_then(|x| x.to_str()).unwrap_or("").to_string();
+        if path.is_dir() {
+            data.insert(&stem, Value::InlineTable(load_data(&path)?));
+        } else if let Some(value) = read_data_file(&path)? {
+            data.insert(&stem, value);
+        }
+    }
+    Ok(data)
+}
+
+/// data 파일 하나, csv는 첫 줄을 key로 하는 inline table 배열이 된다. 모르는 확장자는 None
+pub fn read_data_file(path: &Path) -> Result<Option<Value>> {
+    let ext = path.extension().and_then(|x| x.to_str()).unwrap_or("").to_lowercase();
+    if !DATA_EXTENSIONS.contains(&ext.as_str()) {
+        return Ok(None);
+    }
+    let source = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    let value = match ext.as_str() {
+        "toml" => {
+            let doc = source.parse::<DocumentMut>().map_err(|e| Error::toml(path, &source, &e))?;
+            Some(Value::InlineTable(doc.as_table().clone().into_inline_table()))
+        }
+        "yaml" | "yml" => {
+            let yaml: serde_yaml::Value = serde_yaml::from_str(&source).map_err(|e| Error::site(path, format!("invalid yaml, {}", e)))?;
+            yaml_to_toml_edit_value(&yaml)
+        }
+        "json" => {
+            let json: serde_json::Value = serde_json::from_str(&source).map_err(|e| Error::site(path, format!("invalid json, {}", e)))?;
+            json_to_value(&json)
+        }
+        _ => Some(csv_to_value(path, &source)?),
+    };
+    Ok(Some(value.unwrap_or_else(|| Value::InlineTable(InlineTable::new()))))
+}
+
+fn json_to_value(json: &serde_json::Value) -> Option<Value> {
+    match json {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(b) => Some(Value::from(*b)),
+        serde_json::Value::Number(n) => n.as_i64().map(Value::from).or_else(|| n.as_f64().map(Value::from)),
+        serde_json::Value::String(s) => Some(Value::from(s.as_str())),
+        serde_json::Value::Array(array) => Some(Value::Array(array.iter().filter_map(json_to_value).collect())),
+        serde_json::Value::Object(object) => {
+            let mut table = InlineTable::new();
+            for (k, v) in object {
+                if let Some(value) = json_to_value(v) {
+                    table.insert(k, value);
+                }
+            }
+            Some(Value::InlineTable(table))
+        }
+    }
+}
+
+fn csv_to_value(path: &Path, source: &str) -> Result<Value> {
+    let mut reader = csv::Reader::from_reader(source.as_bytes());
+    let headers = reader.headers().map_err(|e| Error::site(path, format!("invalid csv, {}", e)))?.clone();
+    let mut rows = Array::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| Error::site(path, format!("invalid csv, {}", e)))?;
+        let mut row = InlineTable::new();
+        for (key, field) in headers.iter().zip(record.iter()) {
+            row.insert(key.trim(), Value::from(field.trim()));
+        }
+        rows.push(row);
+    }
+    Ok(Value::Array(rows))
+}
+
+/// 사이트 root 바로 아래, index 파일이 없는 data 폴더는 페이지가 아니다.
+pub fn is_data_dir(root: &Path, path: &Path) -> bool {
+    path == root.join(DATA_DIR)
+        && !path.join("index.md").exists()
+        && !path.join("index.toml").exists()
+}
@@ -0,0 +1,249 @@
+use std::fmt::{self, Formatter};
+use std::path::{Path, PathBuf};
+
+use markdown::message::{Message, Place};
+use toml_edit::{ImDocument, Item, Table, TomlError};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// 1부터 시작하는 줄, 칸 위치
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn from_offset(source: &str, offset: usize) -> Location {
+        let before = &source[..offset.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        Location { line, column }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// prema 전체에서 쓰는 에러
+///
+/// 어느 파일의 어느 key, 몇 번째 줄에서 생긴 문제인지 함께 들고 다닌다.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// 파일이나 폴더를 읽고 쓰는 중 실패. std::io::Error 에만 쓴다.
+    Io { path: PathBuf, message: String },
+    /// 폴더 구조, 파일 이름, glob, data 파일 내용처럼 사이트 원본이 잘못된 것
+    Site { path: PathBuf, message: String },
+    /// toml 문법 오류
+    Toml { path: PathBuf, message: String, location: Option<Location> },
+    /// markdown 문법 오류나 frontmatter 문제
+    Markdown { path: PathBuf, message: String, location: Option<Location> },
+    /// index.toml의 layout table 문제. key는 "root.contents.title" 같은 key path
+    Layout { path: PathBuf, key: String, message: String, location: Option<Location> },
+    /// strict 빌드나 check에서 나온 error 단계의 Diagnostic들
+    Diagnostics(Vec<Diagnostic>),
+}
+
+impl Error {
+    pub fn io(path: &Path, error: impl fmt::Display) -> Error {
+        Error::Io { path: path.to_path_buf(), message: error.to_string() }
+    }
+
+    pub fn site(path: &Path, message: impl Into<String>) -> Error {
+        Error::Site { path: path.to_path_buf(), message: message.into() }
+    }
+
+    pub fn toml(path: &Path, source: &str, error: &TomlError) -> Error {
+        Error::Toml {
+            path: path.to_path_buf(),
+            message: error.message().trim().replace('\n', ", "),
+            location: error.span().map(|span| Location::from_offset(source, span.start)),
+        }
+    }
+
+    /// markdown::to_mdast 의 Message를 옮긴다.
+    ///
+    /// line_offset은 파싱 전에 잘라낸 frontmatter의 줄 수
+    pub fn markdown(path: &Path, message: &Message, line_offset: usize) -> Error {
+        let point = message.place.as_deref().map(|place| match place {
+            Place::Point(point) => point,
+            Place::Position(position) => &position.start,
+        });
+        Error::Markdown {
+            path: path.to_path_buf(),
+            message: message.reason.clone(),
+            location: point.map(|p| Location { line: p.line + line_offset, column: p.column }),
+        }
+    }
+
+    /// key path에 해당하는 위치를 index.toml에서 찾아 채운다.
+    pub fn layout(path: &Path, key: &str, message: impl Into<String>) -> Error {
+        let location = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|source| locate_key(&source, key));
+        Error::Layout {
+            path: path.to_path_buf(),
+            key: key.to_string(),
+            message: message.into(),
+            location,
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::Io { path, .. }
+            | Error::Toml { path, .. }
+            | Error::Markdown { path, .. }
+            | Error::Site { path, .. }
+            | Error::Layout { path, .. } => Some(path),
+            Error::Diagnostics(_) => None,
+        }
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::Toml { location, .. }
+            | Error::Markdown { location, .. }
+            | Error::Layout { location, .. } => *location,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(path) = self.path() {
+            write!(f, "{}", path.display())?;
+            if let Some(location) = self.location() {
+                write!(f, ":{}", location)?;
+            }
+            write!(f, ": ")?;
+        }
+        match self {
+            Error::Layout { key, message, .. } => write!(f, "[{}] {}", key, message),
+            Error::Diagnostics(diagnostics) => {
+                let errors = diagnostics.iter().filter(|x| x.is_error()).count();
+                write!(f, "{} error(s), {} warning(s) found", errors, diagnostics.len() - errors)
+            }
+            Error::Io { message, .. }
+            | Error::Site { message, .. }
+            | Error::Toml { message, .. }
+            | Error::Markdown { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for String {
+    fn from(error: Error) -> String {
+        error.to_string()
+    }
+}
+
+/// "root.contents.title" 같은 key path가 toml 문서 어디에 있는지 찾는다.
+///
+/// DocumentMut는 span을 버리기 때문에 ImDocument로 다시 읽는다.
+pub fn locate_key(source: &str, key_path: &str) -> Option<Location> {
+    let doc = ImDocument::parse(source).ok()?;
+    let mut table = doc.as_table();
+    let mut span = None;
+    for key in key_path.split('.') {
+        let (key, item) = table.get_key_value(key)?;
+        span = key.span().or_else(|| item.span()).or(span);
+        match item {
+            Item::Table(sub_table) => table = sub_table,
+            _ => break,
+        }
+    }
+    span.map(|span| Location::from_offset(source, span.start))
+}
+
+/// 이름이 key이고 shape가 같은 table을 찾아 전체 key path를 돌려준다.
+///
+/// view는 자기 key만 알고 있어서, 에러를 만들 때 key path를 되찾는 데 쓴다.
+pub fn find_key_path(source: &str, key: &str, shape: &str) -> Option<String> {
+    fn walk(table: &Table, prefix: &str, key: &str, shape: &str) -> Option<String> {
+        for (k, item) in table.iter() {
+            if let Item::Table(sub_table) = item {
+                let path = if prefix.is_empty() { k.to_string() } else { format!("{}.{}", prefix, k) };
+                let sub_shape = sub_table.get("shape").and_then(|x| x.as_str()).unwrap_or("");
+                if k == key && sub_shape == shape {
+                    return Some(path);
+                }
+                if let Some(found) = walk(sub_table, &path, key, shape) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+    let doc = ImDocument::parse(source).ok()?;
+    walk(doc.as_table(), "", key, shape)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// 빌드를 멈추지는 않지만 사용자에게 알려야 하는 문제
+///
+/// check 명령과 layout schema 검사에서 쓴다.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: PathBuf,
+    pub key: String,
+    pub location: Option<Location>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(file: &Path, key: &str, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            file: file.to_path_buf(),
+            key: key.to_string(),
+            location: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(file: &Path, key: &str, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(file, key, message)
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.file.display())?;
+        if let Some(location) = self.location {
+            write!(f, ":{}", location)?;
+        }
+        if !self.key.is_empty() {
+            write!(f, ": [{}]", self.key)?;
+        }
+        write!(f, " {}", self.message)
+    }
+}
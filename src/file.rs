@@ -1,17 +1,18 @@
+use crate::error::{Error, Result};
 use crate::option::load_option;
 use crate::page::Page;
 use std::fs;
 use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use glob::glob;
+use glob::{glob_with, MatchOptions, Pattern, PatternError};
 
-pub fn read_first_non_empty_line(path: &Path) -> Result<String, &'static str> {
-    let file = fs::File::open(path).ok().ok_or("failed to open")?;
+pub fn read_first_non_empty_line(path: &Path) -> Result<String> {
+    let file = fs::File::open(path).map_err(|e| Error::io(path, e))?;
     let reader = BufReader::new(file);
 
     for line_result in reader.lines() {
-        let line = line_result.ok().ok_or("failed to read line")?;
+        let line = line_result.map_err(|e| Error::io(path, e))?;
         let trimmed = line.trim();
 
         if trimmed.starts_with('#') {
@@ -23,8 +24,8 @@ pub fn read_first_non_empty_line(path: &Path) -> Result<String, &'static str> {
     Ok("".to_string()) // 공백이 아닌 줄이 없음
 }
 
-pub fn find_all_ext_files(path: &Path, ext: fn(&str) -> bool) -> Result<Vec<PathBuf>, String> {
-    let paths = fs::read_dir(path).ok().ok_or("failed to read dir")?;
+pub fn find_all_ext_files(path: &Path, ext: fn(&str) -> bool) -> Result<Vec<PathBuf>> {
+    let paths = fs::read_dir(path).map_err(|e| Error::io(path, e))?;
     let mut ext_paths: Vec<PathBuf> = vec![];
     for path in paths.filter_map(|x| x.ok()).map(|x| x.path()) {
         match path.extension() {
@@ -40,7 +41,7 @@ pub fn find_all_ext_files(path: &Path, ext: fn(&str) -> bool) -> Result<Vec<Path
 }
 
 /// 해당 디렉토리에 있는 모든 이미지 패스를 찾아내 추가한다.
-pub fn find_images(path: &Path) -> Result<Vec<PathBuf>, String> {
+pub fn find_images(path: &Path) -> Result<Vec<PathBuf>> {
     let image_paths = find_all_ext_files(path, |ext| {
         ext == "png" || ext == "jpeg" || ext == "jpg" || ext == "svg"
     })?;
@@ -61,35 +62,59 @@ pub fn get_file_timestamps(path: &Path) -> io::Result<(Option<SystemTime>, Optio
     Ok((created, modified))
 }
 
-pub fn find_files(pattern: &str, base_path: &Path) -> Vec<PathBuf> {
-    println!("find_files pattern: {}", pattern);
-    let mut results = Vec::new();
-
-    // base_path + pattern (예: /tmp/*.md)
-    let full_pattern = base_path.join(pattern).to_string_lossy().to_string();
+/// ### find files
+/// base_path 기준 glob 패턴들에 맞는 파일들, "**"는 하위 폴더까지 찾는다.
+///
+/// "!"로 시작하는 패턴에 맞는 파일은 뺀다. 경로 순으로 정렬하고 겹치는 파일은 하나만 남긴다.
+/// 잘못된 패턴이나 읽을 수 없는 경로는 에러가 된다.
+pub fn find_files(patterns: &[String], base_path: &Path) -> Result<Vec<PathBuf>> {
+    let options = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+    let (excludes, includes): (Vec<&String>, Vec<&String>) = patterns.iter().partition(|x| x.starts_with('!'));
+    let excludes = excludes
+        .iter()
+        .map(|x| glob_pattern(&x[1..], base_path))
+        .collect::<Result<Vec<Pattern>>>()?;
 
-    for entry in glob(&full_pattern).expect("Invalid glob pattern") {
-        match entry {
-            Ok(path) => {
-                if !path.is_dir() {
-                    results.push(path)
-                }
-            },
-            Err(e) => eprintln!("Glob error: {:?}", e),
+    let mut results = Vec::new();
+    for pattern in includes {
+        glob_pattern(pattern, base_path)?;
+        // base_path에 [ 같은 글자가 있어도 패턴으로 읽히지 않게 escape 한다.
+        let full_pattern = PathBuf::from(Pattern::escape(&base_path.to_string_lossy())).join(pattern);
+        let entries = glob_with(&full_pattern.to_string_lossy(), options).map_err(|e| glob_error(pattern, base_path, e))?;
+        for entry in entries {
+            match entry {
+                Ok(path) => {
+                    let relative = path.strip_prefix(base_path).unwrap_or(&path);
+                    if !path.is_dir() && !excludes.iter().any(|x| x.matches_path_with(relative, options)) {
+                        results.push(path)
+                    }
+                },
+                // 읽을 수 없는 폴더 같은 것
+                Err(e) => return Err(Error::io(e.path(), e.error())),
+            }
         }
     }
+    results.sort();
+    results.dedup();
+    Ok(results)
+}
+
+fn glob_pattern(pattern: &str, base_path: &Path) -> Result<Pattern> {
+    Pattern::new(pattern).map_err(|e| glob_error(pattern, base_path, e))
+}
 
-    results
+fn glob_error(pattern: &str, base_path: &Path, error: PatternError) -> Error {
+    Error::site(base_path, format!("invalid glob pattern \"{}\", {}", pattern, error))
 }
 
-pub fn has_stem_dir(path: &Path) -> Result<bool, String> {
+pub fn has_stem_dir(path: &Path) -> Result<bool> {
     // 1️⃣ 확장자가 md인지 확인
     if path.extension().and_then(|e| e.to_str()) != Some("md") {
-        return Err("only md file is available".to_string());
+        return Err(Error::site(path, "only md file is available"));
     }
 
-    let stem = path.file_stem().and_then(|s| s.to_str()).ok_or("failed to get file stem")?;
-    let parent = path.parent().ok_or("failed to get parent")?;
+    let stem = path.file_stem().and_then(|s| s.to_str()).ok_or_else(|| Error::site(path, "failed to get file stem"))?;
+    let parent = path.parent().ok_or_else(|| Error::site(path, "failed to get parent"))?;
     let stem_dir: PathBuf = parent.join(stem);
     
     Ok(stem_dir.is_dir())
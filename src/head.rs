@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use maplit::hashmap;
+use toml_edit::{Item, Table, TableLike};
+
+use crate::asset::{ASSETS_DIR, ASSETS_MARKER};
+use crate::error::{Diagnostic, Error, Result};
+use crate::html::HTMLView;
+use crate::layout::common::SITE_TABLE;
+
+/// html을 쓸 때 output 폴더에서의 페이지 경로로 바뀌는 자리, "posts/a/" 처럼
+pub const PAGE_PATH_MARKER: &str = "{page_path}";
+
+/// ### head meta
+/// 페이지의 title, description, Open Graph, canonical, lang
+///
+/// 페이지(frontmatter나 index.toml의 [root])의 값이 먼저이고, 없으면 [site]의 값을 쓴다.
+///
+/// ```yaml
+/// ---
+/// title: Hello
+/// description: first post
+/// image: /images/cover.png
+/// lang: ko
+/// ---
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HeadMeta {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub lang: Option<String>,
+    pub canonical: Option<String>,
+    /// [site]의 title, "페이지 | 사이트" 꼴의 title과 og:site_name에 쓴다.
+    pub site_name: Option<String>,
+    pub base_url: Option<String>,
+    /// head 끝에 그대로 넣는 html, [site]의 것이 먼저
+    pub head_extra: Vec<String>,
+    /// body 끝에 그대로 넣는 html
+    pub body_end: Vec<String>,
+    /// head에 link로 부르는 css 주소들
+    pub extra_css: Vec<String>,
+    /// body 끝에 script로 부르는 js 주소들
+    pub extra_js: Vec<String>,
+}
+
+impl HeadMeta {
+    /// page는 frontmatter나 [root], dir은 그 파일이 있는 폴더로 head_extra 같은 파일 경로의 기준
+    pub fn new(page: &dyn TableLike, dir: &Path, layout_tables: &HashMap<String, Table>) -> HeadMeta {
+        let site = layout_tables.get(SITE_TABLE);
+        let page_value = |key: &str| string(page.get(key).and_then(|x| x.as_str()));
+        let site_value = |key: &str| string(site.and_then(|x| x.get(key)).and_then(|x| x.as_str()));
+        // [site]의 값은 Site가 이미 파일을 읽고 복사해 두었다.
+        let site_strings = |key: &str| site.and_then(|x| x.get(key)).map(strings).unwrap_or_default();
+        let page_strings = |key: &str| page.get(key).map(strings).unwrap_or_default();
+        let snippets = |key: &str| {
+            let mut snippets = site_strings(key);
+            // 읽을 수 없는 파일은 Page가 경고로 남기고 여기서는 뺀다.
+            snippets.extend(page_strings(key).iter().filter_map(|x| read_snippet(x, dir).ok()));
+            snippets
+        };
+        let urls = |key: &str| {
+            let mut urls = site_strings(key);
+            urls.extend(page_strings(key).iter().map(|x| extra_url(x)));
+            urls
+        };
+        HeadMeta {
+            title: page_value("title"),
+            description: page_value("description").or_else(|| site_value("description")),
+            image: page_value("image").or_else(|| site_value("image")),
+            lang: page_value("lang").or_else(|| site_value("lang")),
+            canonical: page_value("canonical"),
+            site_name: site_value("title"),
+            base_url: site_value("base_url"),
+            head_extra: snippets("head_extra"),
+            body_end: snippets("body_end"),
+            extra_css: urls("extra_css"),
+            extra_js: urls("extra_js"),
+        }
+    }
+
+    /// "페이지 | 사이트", 둘 중 하나만 있으면 그것
+    pub fn full_title(&self) -> Option<String> {
+        match (&self.title, &self.site_name) {
+            (Some(title), Some(site)) if title != site => Some(format!("{} | {}", title, site)),
+            (Some(title), _) => Some(title.clone()),
+            (None, site) => site.clone(),
+        }
+    }
+
+    /// base_url이 있을 때만 정해지는 페이지 주소, 경로는 html을 쓸 때 채운다.
+    fn url(&self) -> Option<String> {
+        if let Some(canonical) = &self.canonical {
+            return Some(self.absolute(canonical));
+        }
+        self.base_url
+            .as_ref()
+            .map(|base_url| format!("{}/{}", base_url.trim_end_matches('/'), PAGE_PATH_MARKER))
+    }
+
+    /// "/a.png"는 base_url 뒤에, "a.png"는 페이지 주소 뒤에 붙인다.
+    fn absolute(&self, path: &str) -> String {
+        if path.contains("://") {
+            return path.to_string();
+        }
+        match &self.base_url {
+            Some(base_url) if path.starts_with('/') => format!("{}{}", base_url.trim_end_matches('/'), path),
+            Some(base_url) => format!("{}/{}{}", base_url.trim_end_matches('/'), PAGE_PATH_MARKER, path),
+            None => path.to_string(),
+        }
+    }
+
+    /// ### head views
+    /// title, meta description, og:*, twitter:*, canonical 태그들
+    pub fn views(&self) -> Vec<HTMLView> {
+        let mut views = vec![];
+        let meta = |kind: &str, name: &str, content: &str| {
+            HTMLView::new("meta", hashmap! {
+                kind.to_string() => name.to_string(),
+                "content".to_string() => escape(content),
+            }, "", vec![])
+        };
+        let title = self.full_title();
+        if let Some(title) = &title {
+            views.push(HTMLView::new("title", hashmap! {}, &escape(title), vec![]));
+        }
+        if let Some(description) = &self.description {
+            views.push(meta("name", "description", description));
+        }
+        let url = self.url();
+        if let Some(url) = &url {
+            views.push(HTMLView::new("link", hashmap! {
+                "rel".to_string() => "canonical".to_string(),
+                "href".to_string() => escape(url),
+            }, "", vec![]));
+        }
+
+        let image = self.image.as_ref().map(|x| self.absolute(x));
+        if let Some(title) = self.title.as_ref().or(title.as_ref()) {
+            views.push(meta("property", "og:title", title));
+            views.push(meta("name", "twitter:title", title));
+        }
+        if let Some(description) = &self.description {
+            views.push(meta("property", "og:description", description));
+            views.push(meta("name", "twitter:description", description));
+        }
+        if let Some(image) = &image {
+            views.push(meta("property", "og:image", image));
+            views.push(meta("name", "twitter:image", image));
+        }
+        if let Some(url) = &url {
+            views.push(meta("property", "og:url", url));
+        }
+        if let Some(site_name) = &self.site_name {
+            views.push(meta("property", "og:site_name", site_name));
+        }
+        if !views.is_empty() {
+            views.push(meta("property", "og:type", "website"));
+            let card = if image.is_some() { "summary_large_image" } else { "summary" };
+            views.push(meta("name", "twitter:card", card));
+        }
+        views
+    }
+
+    /// head 끝에 둘 extra_css와 head_extra, tailwind 뒤라서 덮어쓸 수 있다.
+    pub fn extra_views(&self) -> Vec<HTMLView> {
+        let mut views: Vec<HTMLView> = self
+            .extra_css
+            .iter()
+            .map(|href| HTMLView::new("link", hashmap! {
+                "rel".to_string() => "stylesheet".to_string(),
+                "href".to_string() => href.clone(),
+            }, "", vec![]))
+            .collect();
+        views.extend(self.head_extra.iter().map(|x| HTMLView::raw(x)));
+        views
+    }
+
+    /// body 끝에 둘 extra_js와 body_end
+    pub fn body_end_views(&self) -> Vec<HTMLView> {
+        let mut views: Vec<HTMLView> = self
+            .extra_js
+            .iter()
+            .map(|src| HTMLView::new("script", hashmap! { "src".to_string() => src.clone() }, "", vec![]))
+            .collect();
+        views.extend(self.body_end.iter().map(|x| HTMLView::raw(x)));
+        views
+    }
+}
+
+/// extra_css, extra_js 중 output/assets 에 복사할 파일들, 페이지 파일이 있는 폴더 기준
+pub fn extra_files(page: &dyn TableLike) -> Vec<String> {
+    ["extra_css", "extra_js"]
+        .iter()
+        .filter_map(|key| page.get(key))
+        .flat_map(strings)
+        .filter(|x| !is_url(x))
+        .collect()
+}
+
+/// ### copy extra file
+/// 파일을 output/assets 에 복사하고 html에서 부를 주소를 돌려준다. 주소면 그대로 돌려준다.
+pub fn copy_extra_file(path: &str, dir: &Path, output: &Path) -> Result<String> {
+    if is_url(path) {
+        return Ok(path.to_string());
+    }
+    let source = dir.join(path);
+    let name = source.file_name().ok_or_else(|| Error::site(&source, "invalid file name"))?;
+    let assets_dir = output.join(ASSETS_DIR);
+    fs::create_dir_all(&assets_dir).map_err(|e| Error::io(&assets_dir, e))?;
+    fs::copy(&source, assets_dir.join(name)).map_err(|e| Error::io(&source, e))?;
+    Ok(extra_url(path))
+}
+
+/// head_extra, body_end 값이 html이면 그대로, 아니면 dir 기준 파일의 내용
+pub fn read_snippet(snippet: &str, dir: &Path) -> Result<String> {
+    if is_html(snippet) {
+        return Ok(snippet.to_string());
+    }
+    let path = dir.join(snippet.trim());
+    fs::read_to_string(&path).map_err(|e| Error::io(&path, e))
+}
+
+/// `<`로 시작하면 html, 아니면 파일 경로로 본다.
+pub fn is_html(snippet: &str) -> bool {
+    snippet.trim_start().starts_with('<')
+}
+
+/// head_extra, body_end 중 읽을 수 없는 파일을 경고로, file은 page가 있는 index.toml이나 markdown
+pub fn snippet_diagnostics(page: &dyn TableLike, dir: &Path, file: &Path, key_prefix: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for key in ["head_extra", "body_end"] {
+        for snippet in page.get(key).map(strings).unwrap_or_default() {
+            if let Err(e) = read_snippet(&snippet, dir) {
+                diagnostics.push(Diagnostic::warning(file, &format!("{}{}", key_prefix, key), format!("cannot read snippet {}", e)));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// "css/site.css" -> "{assets}/site.css", 주소는 그대로
+fn extra_url(path: &str) -> String {
+    if is_url(path) {
+        return path.to_string();
+    }
+    let name = Path::new(path).file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+    format!("{}/{}", ASSETS_MARKER, name)
+}
+
+fn is_url(path: &str) -> bool {
+    path.contains("://") || path.starts_with("//")
+}
+
+/// 문자열 하나나 문자열 배열
+fn strings(item: &Item) -> Vec<String> {
+    match item.as_array() {
+        Some(array) => array.iter().filter_map(|x| x.as_str()).map(|x| x.to_string()).collect(),
+        None => item.as_str().map(|x| vec![x.to_string()]).unwrap_or_default(),
+    }
+}
+
+/// output 폴더에서 relative 경로에 쓰는 html의 {page_path}를 "posts/a/" 같은 경로로 바꾼다.
+pub fn page_path(html: &str, relative: &str) -> String {
+    if !html.contains(PAGE_PATH_MARKER) {
+        return html.to_string();
+    }
+    let relative = relative.trim_matches('/');
+    let path = if relative.is_empty() { String::new() } else { format!("{}/", relative) };
+    html.replace(PAGE_PATH_MARKER, &path)
+}
+
+fn string(value: Option<&str>) -> Option<String> {
+    value.map(|x| x.trim().to_string()).filter(|x| !x.is_empty())
+}
+
+/// 속성 값과 title 안에서 쓸 수 있게
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
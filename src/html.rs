@@ -3,6 +3,10 @@ use std::collections::HashMap;
 use std::fmt::Formatter;
 use maplit::hashmap;
 use crate::option::MDOption;
+use crate::theme::Theme;
+use crate::asset::{bundled_assets, head_assets};
+use crate::head::HeadMeta;
+use toml_edit::Table;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
@@ -59,9 +63,19 @@ impl HTMLView {
         HTMLView { tag: tag.to_string(), attrs, value: value.to_string(), views }
     }
 
+    /// head_extra 처럼 그대로 쓰는 html
+    pub fn raw(html: &str) -> HTMLView {
+        HTMLView::new("", hashmap! {}, html, vec![])
+    }
+
     pub fn html(&self) -> String {
-        let attr_string = self
-            .attrs
+        if self.tag.is_empty() {
+            return format!("{}\n", self.value.trim());
+        }
+        // 같은 view는 항상 같은 html이 되도록 속성 이름 순서로
+        let mut attrs: Vec<(&String, &String)> = self.attrs.iter().collect();
+        attrs.sort();
+        let attr_string = attrs
             .iter()
             .map(|(k, v)| format!(r#"{}="{}""#, k, v))
             .collect::<Vec<_>>()
@@ -100,20 +114,32 @@ impl HTMLView {
         }
     }
 
-    pub fn wrap_body(&self, is_dark: bool) -> HTMLView {
+    /// body의 배경과 글꼴은 theme의 surface, font_body
+    ///
+    /// layout_tables의 [theme]과 [site]의 bundle된 assets를 쓴다. head는 title, description 같은 페이지 정보
+    pub fn wrap_body(&self, is_dark: bool, layout_tables: &HashMap<String, Table>, head: &HeadMeta) -> HTMLView {
+        let theme = Theme::from_layout_tables(layout_tables);
         let self_view = self.clone();
+        let mut body_class = format!("mx-auto w-screen h-screen {}", theme.color_class("surface", "bg", "bg-white dark:bg-slate-900"));
+        if let Some(font) = theme.font_class("font_body") {
+            body_class = format!("{} {}", body_class, font);
+        }
         let body_view = HTMLView {
             tag: "body".to_string(),
             attrs: hashmap! {
-                "class".to_string() => filter_attrs("mx-auto bg-white w-screen h-screen dark:bg-slate-900", is_dark),
+                "class".to_string() => filter_attrs(&body_class, is_dark),
             },
             value: "".to_string(),
-            views: vec![self_view]
+            views: [vec![self_view], head.body_end_views()].concat()
         };
-        let head_view = metas(is_dark);
+        let head_view = metas(is_dark, bundled_assets(layout_tables), head);
+        let mut html_attrs = hashmap! {};
+        if let Some(lang) = &head.lang {
+            html_attrs.insert("lang".to_string(), lang.clone());
+        }
         let html_view = HTMLView {
             tag: "html".to_string(),
-            attrs: hashmap! {},
+            attrs: html_attrs,
             value: "".to_string(),
             views: vec![head_view, body_view]
         };
@@ -187,7 +213,10 @@ impl HTMLView {
     }
 }
 
-pub fn metas(is_dark: bool) -> HTMLView {
+/// head의 charset, viewport, 페이지 정보와 highlight.js, tailwind, flowbite
+///
+/// assets는 bundle된 파일들의 integrity 값, 없으면 CDN을 쓴다.
+pub fn metas(is_dark: bool, assets: Option<&Table>, head: &HeadMeta) -> HTMLView {
     let charset = HTMLView::new("meta", hashmap! {
         "charset".to_string() => "UTF-8".to_string(),
     }, "", vec![]);
@@ -195,24 +224,11 @@ pub fn metas(is_dark: bool) -> HTMLView {
         "name".to_string() => "viewport".to_string(),
         "content".to_string() => "width=device-width, initial-scale=1.0".to_string()
     }, "", vec![]);
-    let highlight_dark = HTMLView::new("link", hashmap! {
-        "rel".to_string() => "stylesheet".to_string(),
-        "href".to_string() => format!("https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/styles/atom-one-{}.min.css", if (is_dark) { "dark" } else { "light" }),
-    }, "", vec![]);
-    let highlight = HTMLView::new("script", hashmap! {
-        "src".to_string() => "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/highlight.min.js".to_string(),
-    }, "", vec![]);
-    let highlight_go = HTMLView::new("script", hashmap! {
-        "src".to_string() => "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/languages/go.min.js".to_string(),
-    }, "", vec![]);
-    let highlight_all = HTMLView::new("script", hashmap! {}, "hljs.highlightAll();", vec![]);
-    let tailwind = HTMLView::new("script", hashmap! {
-        "src".to_string() => "https://cdn.tailwindcss.com".to_string(),
-    }, "", vec![]);
-    let flowbite = HTMLView::new("script", hashmap! {
-        "src".to_string() => "https://flowbite.com/docs/flowbite.min.js?v=3.1.2a".to_string(),
-    }, "", vec![]);
-    HTMLView::new("head", hashmap! {}, "", vec![charset, viewport, highlight_dark, highlight, highlight_go, highlight_all, tailwind, flowbite])
+    let mut views = vec![charset, viewport];
+    views.extend(head.views());
+    views.extend(head_assets(is_dark, assets));
+    views.extend(head.extra_views());
+    HTMLView::new("head", hashmap! {}, "", views)
 }
 
 /// 사용가능한 HTML Tag
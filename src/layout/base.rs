@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{InlineTable, Table};
+
+use super::common::{item_to_bool, item_to_string};
+use super::responsive::responsive_classes;
+use super::style::style_classes;
+use super::view::TOMLView;
+
+/// ### view common
+/// 모든 shape가 같이 쓰는 key들
+///
+/// value 합치기, dark 물려받기, 크기, 위치, style, id, aria-* 를 한 곳에서 읽는다.
+pub struct ViewCommon {
+    pub index_path: PathBuf,
+    pub key: String,
+    /// 상위 view의 key들을 앞에 붙인 "root.posts" 같은 key path
+    pub key_path: String,
+    pub width: String,
+    pub height: String,
+    pub background: String,
+    pub path: String,
+    pub align_absolute: String,
+    pub custom_class: String,
+    /// html id, 없으면 key
+    pub id: String,
+    /// aria-label = "..." 처럼 그대로 html 속성이 되는 값들
+    pub aria: Vec<(String, String)>,
+    /// margin, padding, border, shadow, opacity, z_index, 글자 간격 같은 style class
+    pub styles: Vec<String>,
+    /// breakpoint별 width, height와 hidden class
+    pub responsive: Vec<String>,
+    /// 상위 view의 value에 이 table의 value를 덮어쓴 값
+    pub value: Option<InlineTable>,
+    pub dark: bool,
+}
+
+impl ViewCommon {
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>) -> ViewCommon {
+        let mut merged = value.cloned().unwrap_or_default();
+        if let Some(table_value) = table.get("value").and_then(|v| v.as_inline_table()) {
+            for (k, v) in table_value.iter() {
+                merged.insert(k, v.clone());
+            }
+        }
+        let value = if merged.is_empty() { None } else { Some(&merged) };
+
+        let default_dark = super_view.map(|x| x.dark()).unwrap_or(false);
+        let key_path = match super_view {
+            Some(super_view) => format!("{}.{}", super_view.key_path(), key),
+            None => key.to_string(),
+        };
+
+        ViewCommon {
+            index_path: index_path.to_path_buf(),
+            key: key.to_string(),
+            key_path,
+            width: item_to_string(table, "width", "wrap", value),
+            height: item_to_string(table, "height", "wrap", value),
+            background: item_to_string(table, "background", "transparent", value),
+            path: item_to_string(table, "path", "", value),
+            align_absolute: item_to_string(table, "align_absolute", "", value),
+            custom_class: item_to_string(table, "custom_class", "", value),
+            id: item_to_string(table, "id", key, value),
+            aria: aria_attrs(table, value),
+            styles: style_classes(table, value),
+            responsive: responsive_classes(table, value),
+            dark: item_to_bool(table, "dark", default_dark, value),
+            value: value.cloned(),
+        }
+    }
+
+    /// 문자열 width, height의 class, wrap이면 없다.
+    pub fn size_classes(&self) -> Vec<String> {
+        let mut classes = vec![];
+        for (size, prefix) in [(&self.width, "w"), (&self.height, "h")] {
+            if size.starts_with(&format!("{}-", prefix)) {
+                classes.push(size.clone());
+            } else if size != "wrap" {
+                classes.push(format!("{}-[{}]", prefix, size));
+            }
+        }
+        classes
+    }
+
+    /// ### class
+    /// shape마다 다른 class 뒤에 공통 class를 붙인다.
+    ///
+    /// 크기, align_absolute, styles, responsive, custom_class 순서라서 custom_class가 가장 마지막이다.
+    pub fn class(&self, mut class_parts: Vec<String>) -> String {
+        class_parts.extend(self.size_classes());
+        if !self.align_absolute.is_empty() {
+            let mut align_class = self.align_absolute.clone();
+            if !align_class.chars().any(|c| c.is_numeric()) {
+                align_class = format!("{}-0", align_class);
+            }
+            class_parts.push(format!("absolute {}", align_class));
+        }
+        class_parts.extend(self.styles.clone());
+        class_parts.extend(self.responsive.clone());
+        if !self.custom_class.is_empty() {
+            class_parts.push(self.custom_class.clone());
+        }
+        class_parts.retain(|x| !x.is_empty());
+        class_parts.join(" ")
+    }
+
+    /// background 뒤에 shape마다 다른 style을 붙인다.
+    pub fn style(&self, style_parts: Vec<String>) -> String {
+        let mut parts = vec![format!("background:{}", self.background)];
+        parts.extend(style_parts);
+        parts.join("; ") + ";" // 끝에 세미콜론
+    }
+
+    /// id, class, aria-* 속성
+    pub fn attrs(&self, class: String) -> HashMap<String, String> {
+        let mut attrs: HashMap<String, String> = self.aria.iter().cloned().collect();
+        attrs.insert("id".to_string(), self.id.clone());
+        attrs.insert("class".to_string(), class);
+        attrs
+    }
+}
+
+/// aria-label = "메뉴" -> ("aria-label", "메뉴")
+fn aria_attrs(table: &Table, value: Option<&InlineTable>) -> Vec<(String, String)> {
+    table
+        .iter()
+        .filter(|(k, item)| k.starts_with("aria-") && item.is_value())
+        .map(|(k, item)| match item.as_str() {
+            Some(_) => (k.to_string(), item_to_string(table, k, "", value)),
+            None => (k.to_string(), item.as_value().map(|v| v.to_string().trim().to_string()).unwrap_or_default()),
+        })
+        .collect()
+}
+
+/// ### common accessors
+/// TOMLView의 index_path, as_any, key, width, height, background, path, dark, value, views를 만든다.
+///
+/// view에 common: ViewCommon, views: Vec<Box<dyn TOMLView>> 가 있어야 한다.
+macro_rules! common_accessors {
+    () => {
+        fn index_path(&self) -> PathBuf {
+            self.common.index_path.clone()
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn key(&self) -> String {
+            self.common.key.clone()
+        }
+        fn key_path(&self) -> String {
+            self.common.key_path.clone()
+        }
+        fn width(&self) -> String {
+            self.common.width.clone()
+        }
+        fn height(&self) -> String {
+            self.common.height.clone()
+        }
+        fn background(&self) -> String {
+            self.common.background.clone()
+        }
+        fn path(&self) -> String {
+            self.common.path.clone()
+        }
+        fn dark(&self) -> bool {
+            self.common.dark
+        }
+        fn value(&self) -> Option<InlineTable> {
+            self.common.value.clone()
+        }
+        fn views(&self) -> &Vec<Box<dyn TOMLView>> {
+            &self.views
+        }
+    };
+}
+pub(crate) use common_accessors;
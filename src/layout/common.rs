@@ -1,11 +1,15 @@
 use std::{
-    collections::{HashMap, HashSet}, fs::File, io::Read, path::{Path, PathBuf}
+    collections::{HashMap, HashSet}, fs::File, io::Read, path::{Path, PathBuf}, sync::LazyLock
 };
 use maplit::hashmap;
-use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value};
-use super::view::{BoxView, ColumnView, NavView, RowView, TOMLView, TextView};
-use crate::{html::HTMLView, layout::view::{EmbedView, GridView, ImageView, ListColumnView, ListRowView, MarkdownListColumnView, MarkdownListRowView, MarkdownView}};
 use regex::Regex;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value};
+use super::component::{component_to_tomlview, is_component};
+use super::placeholder::fill_placeholders;
+use super::query::is_shown;
+use super::registry::shape_constructor;
+use super::view::TOMLView;
+use crate::{data::{lookup_data, DATA_KEY}, error::{find_key_path, Error, Result}, html::HTMLView, theme::{Theme, THEME_TABLE}};
 
 pub fn item_to_string(table: &Table, key: &str, default: &str, value: Option<&InlineTable>) -> String {
     let raw = table
@@ -13,21 +17,21 @@ pub fn item_to_string(table: &Table, key: &str, default: &str, value: Option<&In
         .and_then(|item| item.as_str())
         .unwrap_or(default);
 
-    if let Some(inline) = value {
-        let re = Regex::new(r"\{([^}]+)\}").unwrap();
-        return re
-            .replace_all(raw, |caps: &regex::Captures| {
-                let inner_key = &caps[1]; // {key} 안쪽
-                inline
-                    .get(inner_key)
-                    .and_then(|v| v.as_str())
-                    .unwrap_or(caps.get(0).unwrap().as_str()) // 없으면 그대로 둠
-                    .to_string()
-            })
-            .to_string();
-    }
+    // value가 없어도 {{ }} 를 풀고 빈 placeholder를 지운다.
+    fill_placeholders(raw, value.unwrap_or(&InlineTable::new()))
+}
 
-    raw.to_string()
+/// {theme.primary} 처럼 . 으로 안쪽 inline table의 값을 찾는다.
+pub fn lookup_value<'a>(table: &'a InlineTable, key: &str) -> Option<&'a Value> {
+    if let Some(found) = table.get(key) {
+        return Some(found);
+    }
+    let mut keys = key.split('.');
+    let mut found = table.get(keys.next()?)?;
+    for k in keys {
+        found = found.as_inline_table()?.get(k)?;
+    }
+    Some(found)
 }
 
 pub fn item_to_strings(table: &Table, key: &str) -> Vec<String> {
@@ -42,6 +46,15 @@ pub fn item_to_strings(table: &Table, key: &str) -> Vec<String> {
         .unwrap_or_else(Vec::new)
 }
 
+/// 정수, 또는 "{count}" 처럼 value에서 온 숫자 문자열
+pub fn item_to_integer(table: &Table, key: &str, default: i64, value: Option<&InlineTable>) -> i64 {
+    match table.get(key) {
+        Some(item) if item.is_integer() => item.as_integer().unwrap_or(default),
+        Some(_) => item_to_string(table, key, "", value).trim().parse().unwrap_or(default),
+        None => default,
+    }
+}
+
 pub fn item_to_bool(table: &Table, key: &str, default: bool, value: Option<&InlineTable>) -> bool {
     let raw = table
         .get(key)
@@ -71,28 +84,19 @@ pub fn get_value_in_inlinetable(key: &str, table: Option<InlineTable>) -> Option
     None
 }
 
-pub fn layout_to_tomlview(view: &dyn TOMLView, layout: String, layout_tables: HashMap<String, Table>, value: Option<&InlineTable>) -> Result<Box<dyn TOMLView>, String> {
+pub fn layout_to_tomlview(view: &dyn TOMLView, layout: String, layout_tables: &HashMap<String, Table>, value: Option<&InlineTable>) -> Result<Box<dyn TOMLView>> {
     if let Some(layout_view) = layout_tables.iter()
-        .filter_map(|(key, x)| table_to_tomlview(view.index_path().as_path(), key, x, value, Some(view), layout_tables.clone()).ok())
+        .filter_map(|(key, x)| table_to_tomlview(view.index_path().as_path(), key, x, value, Some(view), layout_tables).ok())
         .filter(|x| x.key() == layout)
         .next() {
             // println!("layout_to_tomlview shape: {:?} {:?}", layout_view.key(), layout_view.shape());
             return Ok(layout_view)
         }
-    Err("Faild to load view in layout".to_string())
+    Err(Error::layout(view.index_path().as_path(), &view.key(), format!("Faild to load view in layout \"{}\"", layout)))
 }
 
-pub fn get_layout_tables_except_key(index_path: &Path, except_key: &str) -> Result<HashMap<String, Table>, String> {
-    let mut file = File::open(index_path).ok().ok_or(format!("Failed to open the file in layout_tables in {:?}", index_path))?;
-
-    let mut config_str = String::new();
-    file.read_to_string(&mut config_str)
-        .ok()
-        .ok_or("Failed to read the file")?;
-
-    let doc = config_str
-        .parse::<DocumentMut>()
-        .map_err(|err| format!("{}", err))?;
+pub fn get_layout_tables_except_key(index_path: &Path, except_key: &str) -> Result<HashMap<String, Table>> {
+    let doc = read_document(index_path)?;
 
     let mut layout_tables: HashMap<String, Table> = hashmap! {};
     for (k, v) in doc.as_table() {
@@ -105,106 +109,155 @@ pub fn get_layout_tables_except_key(index_path: &Path, except_key: &str) -> Resu
     Ok(layout_tables)
 }
 
-pub fn get_tomlview_for_key(index_path: &Path, find_key: &str, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>) -> Result<Box<dyn TOMLView>, String> {
-    let mut file = File::open(index_path).ok().ok_or(format!("Failed to open the file in tomlview {:?}", index_path))?;
+/// index.toml을 읽어서 DocumentMut로 만든다.
+pub fn read_document(index_path: &Path) -> Result<DocumentMut> {
+    let mut file = File::open(index_path).map_err(|e| Error::io(index_path, e))?;
 
     let mut config_str = String::new();
     file.read_to_string(&mut config_str)
-        .ok()
-        .ok_or("Failed to read the file")?;
+        .map_err(|e| Error::io(index_path, e))?;
 
-    let doc = config_str
+    config_str
         .parse::<DocumentMut>()
-        .map_err(|err| format!("{}", err))?;
+        .map_err(|err| Error::toml(index_path, &config_str, &err))
+}
+
+/// layout table들과 함께 물려주는 빌드 옵션 table 이름
+///
+/// Site가 drafts, base_url 같은 값을 담아 넣는다.
+pub const SITE_TABLE: &str = "site";
+
+/// table의 문자열에 나온 data key path, "data.company.name" 처럼
+static DATA_PATH_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&format!(r"\b{}(\.[\w\-]+)+", DATA_KEY)).unwrap());
+
+/// [theme]과 table이 쓰는 data 값을 value에 넣어서 {theme.primary}, {data.company.name} 으로 쓸 수 있게 한다.
+///
+/// data는 전부가 아니라 table의 문자열에 나온 key path의 값만 넣는다. value에 이미 같은 key가 있으면 그대로 둔다.
+fn value_with_site(value: Option<&InlineTable>, table: &Table, layout_tables: &HashMap<String, Table>) -> Option<InlineTable> {
+    let theme = Theme::from_layout_tables(layout_tables);
+    let mut additions = vec![];
+    if !theme.is_empty() && !value.is_some_and(|x| x.contains_key(THEME_TABLE)) {
+        additions.push((THEME_TABLE.to_string(), Value::InlineTable(theme.to_inline_table())));
+    }
+    if layout_tables.contains_key(DATA_KEY) {
+        let mut texts = vec![];
+        for (_, item) in table.iter() {
+            if let Some(found) = item.as_value() {
+                collect_texts(found, &mut texts);
+            }
+        }
+        for path in texts.iter().flat_map(|text| DATA_PATH_RE.find_iter(text)) {
+            let path = path.as_str();
+            let exists = value.is_some_and(|x| lookup_value(x, path).is_some()) || additions.iter().any(|(x, _)| x == path);
+            if let Some(found) = lookup_data(layout_tables, path).filter(|_| !exists) {
+                additions.push((path.to_string(), found.clone()));
+            }
+        }
+    }
+    if additions.is_empty() {
+        return value.cloned();
+    }
+    let mut value = value.cloned().unwrap_or_default();
+    for (path, addition) in additions {
+        insert_path(&mut value, &path, addition);
+    }
+    Some(value)
+}
+
+/// 값 안의 문자열, 배열과 inline table 안쪽까지
+fn collect_texts<'a>(value: &'a Value, texts: &mut Vec<&'a str>) {
+    match value {
+        Value::String(text) => texts.push(text.value()),
+        Value::Array(array) => array.iter().for_each(|x| collect_texts(x, texts)),
+        Value::InlineTable(table) => table.iter().for_each(|(_, x)| collect_texts(x, texts)),
+        _ => {}
+    }
+}
+
+/// "data.company.name" 자리에 addition을 넣는다. 가는 길의 inline table은 없으면 만든다.
+fn insert_path(value: &mut InlineTable, path: &str, addition: Value) {
+    let mut keys: Vec<&str> = path.split('.').collect();
+    let Some(last) = keys.pop() else {
+        return;
+    };
+    let mut table = value;
+    for key in keys {
+        let Some(inner) = table.entry(key).or_insert(Value::InlineTable(InlineTable::new())).as_inline_table_mut() else {
+            return;
+        };
+        table = inner;
+    }
+    table.insert(last, addition);
+}
+
+/// [site] table의 drafts가 true면 publish: false 인 markdown도 포함한다.
+pub fn include_drafts(layout_tables: &HashMap<String, Table>) -> bool {
+    layout_tables
+        .get(SITE_TABLE)
+        .and_then(|table| table.get("drafts"))
+        .and_then(|item| item.as_bool())
+        .unwrap_or(false)
+}
+
+pub fn get_tomlview_for_key(index_path: &Path, find_key: &str, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>) -> Result<Box<dyn TOMLView>> {
+    let doc = read_document(index_path)?;
 
     for (k, v) in doc.as_table() {
         if let Item::Table(table) = v {
-            println!("key! {} {:?}", k, index_path);
             if k == find_key {
                 let value = value.or_else(|| {
                     table.get("value").and_then(|item| item.as_inline_table())
                 });
 
-                let view = table_to_tomlview(index_path, k, table, value, super_view, layout_tables);
-                match view {
-                    Ok(view) => {
-                        return Ok(view);
-                    }
-                    Err(message) => {
-                        println!("message {}", message);
-                    }
-                }
-                break;
+                return table_to_tomlview(index_path, k, table, value, super_view, layout_tables);
             }
         }
     }
-    Err("".to_string())
+    Err(Error::layout(index_path, find_key, format!("no [{}] table", find_key)))
 }
 
-pub fn table_to_tomlview(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>) -> Result<Box<dyn TOMLView>, String> {
+pub fn table_to_tomlview(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>) -> Result<Box<dyn TOMLView>> {
+    // 최상위 view는 dark가 없으면 [site]의 dark를 쓴다.
+    let site_dark = layout_tables.get(SITE_TABLE).and_then(|site| site.get("dark")).and_then(|x| x.as_bool());
+    let dark_table;
+    let table = match (super_view, table.contains_key("dark"), site_dark) {
+        (None, false, Some(dark)) => {
+            let mut with_dark = table.clone();
+            with_dark.insert("dark", toml_edit::value(dark));
+            dark_table = with_dark;
+            &dark_table
+        }
+        _ => table,
+    };
+    let value = value_with_site(value, table, layout_tables);
+    let value = value.as_ref();
+    // 부르는 쪽은 Err인 하위 view를 건너뛴다.
+    if !is_shown(table, value) {
+        return Err(Error::Layout {
+            path: index_path.to_path_buf(),
+            key: key.to_string(),
+            message: "hidden by if or unless".to_string(),
+            location: None,
+        });
+    }
     let shape = item_to_string(table, "shape", "", value);
     // println!("table_to_tomlview {:?} {:?}", key, shape);
-    match shape.as_str() {
-        "nav" => {
-            let nav_view = NavView::new(index_path, key, table, value, super_view, layout_tables);
-            Ok(Box::new(nav_view))
-        }
-        "box" => {
-            let box_view = BoxView::new(index_path, key, table, value, super_view, layout_tables);
-            Ok(Box::new(box_view))
-        }
-        "column" => {
-            let column_view = ColumnView::new(index_path, key, table, value, super_view, layout_tables, false);
-            Ok(Box::new(column_view))
-        }
-        "row" => {
-            let row_view = RowView::new(index_path, key, table, value, super_view, layout_tables, false);
-            Ok(Box::new(row_view))
-        }
-        "scroll_column" => {
-            let column_view = ColumnView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(column_view))
-        }
-        "scroll_row" => {
-            let row_view = RowView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(row_view))
-        }
-        "text" => {
-            let text_view = TextView::new(index_path, key, table, value, super_view, layout_tables);
-            Ok(Box::new(text_view))
-        }
-        "image" => {
-            let image_view = ImageView::new(index_path, key, table, value, super_view, layout_tables);
-            Ok(Box::new(image_view))
-        }
-        "list_column" => {
-            let list_column_view = ListColumnView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(list_column_view))
-        }
-        "list_row" => {
-            let list_row_view = ListRowView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(list_row_view))
-        }
-        "mdlist_column" => {
-            let list_column_view = MarkdownListColumnView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(list_column_view))
-        }
-        "mdlist_row" => {
-            let list_row_view = MarkdownListRowView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(list_row_view))
-        }
-        "markdown" => {
-            let markdown_view = MarkdownView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(markdown_view))
-        }
-        "grid" => {
-            let grid_view = GridView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(grid_view))
-        }
-        "embed" => {
-            let embed_view = EmbedView::new(index_path, key, table, value, super_view, layout_tables, true);
-            Ok(Box::new(embed_view))
-        }
-        _ => Err("".to_string()),
+    if let Some(constructor) = shape_constructor(&shape) {
+        return constructor(index_path, key, table, value, super_view, layout_tables);
     }
+    if let Some(component) = layout_tables.get(&shape).filter(|x| is_component(x)).cloned() {
+        return component_to_tomlview(index_path, key, table, value, super_view, layout_tables, &component);
+    }
+
+    // view는 자기 key만 알고 있으므로 파일에서 전체 key path를 되찾는다.
+    let key_path = std::fs::read_to_string(index_path)
+        .ok()
+        .and_then(|source| find_key_path(&source, key, shape.as_str()))
+        .unwrap_or(key.to_string());
+    let message = if shape.is_empty() {
+        "missing shape".to_string()
+    } else {
+        format!("unknown shape \"{}\"", shape)
+    };
+    Err(Error::layout(index_path, &key_path, message))
 }
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use toml_edit::{InlineTable, Item, Table, Value};
+
+use super::common::{item_to_string, table_to_tomlview};
+use super::view::TOMLView;
+use crate::error::{find_key_path, Error, Result};
+
+/// component 인자의 타입
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamType {
+    String,
+    Integer,
+    Float,
+    Bool,
+}
+
+impl ParamType {
+    fn parse(name: &str) -> Option<ParamType> {
+        match name {
+            "string" => Some(ParamType::String),
+            "integer" => Some(ParamType::Integer),
+            "float" => Some(ParamType::Float),
+            "bool" => Some(ParamType::Bool),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ParamType::String => "string",
+            ParamType::Integer => "integer",
+            ParamType::Float => "float",
+            ParamType::Bool => "bool",
+        }
+    }
+}
+
+/// params = { title = "string", count = { type = "integer", default = 3 } }
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub kind: ParamType,
+    pub default: Option<Value>,
+}
+
+/// params가 있는 최상위 table은 shape 이름처럼 쓸 수 있는 component다.
+pub fn is_component(table: &Table) -> bool {
+    table.contains_key("params")
+}
+
+pub fn component_params(component: &Table) -> std::result::Result<Vec<Param>, String> {
+    let params = match component.get("params").and_then(|item| item.as_table_like()) {
+        Some(params) => params,
+        None => return Err("params must be a table like { title = \"string\" }".to_string()),
+    };
+
+    let mut result = vec![];
+    for (name, item) in params.iter() {
+        let (kind, default) = match item {
+            Item::Value(Value::String(kind)) => (kind.value().as_str(), None),
+            Item::Value(Value::InlineTable(spec)) => (
+                spec.get("type").and_then(|v| v.as_str()).unwrap_or(""),
+                spec.get("default").cloned(),
+            ),
+            _ => ("", None),
+        };
+        let kind = ParamType::parse(kind)
+            .ok_or_else(|| format!("param \"{}\" has unknown type, expected string, integer, float or bool", name))?;
+        result.push(Param { name: name.to_string(), kind, default });
+    }
+    Ok(result)
+}
+
+/// 인자 값을 타입에 맞는지 확인하고 {name} 자리에 들어갈 문자열로 바꾼다.
+///
+/// 값이 없으면 default를 쓰고, default도 없으면 에러
+pub fn param_value(param: &Param, value: Option<&Value>) -> std::result::Result<String, String> {
+    let value = match value.or(param.default.as_ref()) {
+        Some(value) => value,
+        None => return Err(format!("missing param \"{}\"", param.name)),
+    };
+    let text = value.as_str().map(|s| s.trim());
+    let converted = match param.kind {
+        ParamType::String => text.map(|s| s.to_string()),
+        ParamType::Integer => value
+            .as_integer()
+            .or_else(|| text.and_then(|s| s.parse().ok()))
+            .map(|x| x.to_string()),
+        ParamType::Float => value
+            .as_float()
+            .or_else(|| value.as_integer().map(|x| x as f64))
+            .or_else(|| text.and_then(|s| s.parse().ok()))
+            .map(|x| x.to_string()),
+        ParamType::Bool => value
+            .as_bool()
+            .or_else(|| text.and_then(|s| s.parse().ok()))
+            .map(|x| x.to_string()),
+    };
+    converted.ok_or_else(|| format!("param \"{}\" expects {}, found {}", param.name, param.kind.name(), value.to_string().trim()))
+}
+
+/// ### component to tomlview
+/// shape = "{component}" 인 table을 component의 layout으로 바꿔 만든다.
+///
+/// params에 해당하는 key는 value로 넘기고, 나머지 key는 component의 root key를 덮어쓴다.
+pub fn component_to_tomlview(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, component: &Table) -> Result<Box<dyn TOMLView>> {
+    let name = item_to_string(table, "shape", "", value);
+    let name = name.as_str();
+    let key_path = || {
+        std::fs::read_to_string(index_path)
+            .ok()
+            .and_then(|source| find_key_path(&source, key, name))
+            .unwrap_or(key.to_string())
+    };
+    let params = component_params(component)
+        .map_err(|message| Error::layout(index_path, name, message))?;
+    if component.get("shape").and_then(|x| x.as_str()) == Some(name) {
+        return Err(Error::layout(index_path, name, format!("component \"{}\" cannot use itself as shape", name)));
+    }
+
+    let mut merged = value.cloned().unwrap_or_default();
+    for param in &params {
+        let raw = match table.get(&param.name).and_then(|item| item.as_value()) {
+            Some(Value::String(_)) => Some(Value::from(item_to_string(table, &param.name, "", value))),
+            Some(raw) => Some(raw.clone()),
+            None => None,
+        };
+        let text = param_value(param, raw.as_ref())
+            .map_err(|message| Error::layout(index_path, &key_path(), format!("{} for component \"{}\"", message, name)))?;
+        merged.insert(&param.name, Value::from(text));
+    }
+
+    let mut component_table = component.clone();
+    component_table.remove("params");
+    for (k, item) in table.iter() {
+        if k != "shape" && !params.iter().any(|param| param.name == k) {
+            component_table.insert(k, item.clone());
+        }
+    }
+    table_to_tomlview(index_path, key, &component_table, Some(&merged), super_view, layout_tables)
+}
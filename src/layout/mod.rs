@@ -1,21 +1,57 @@
 use std::{collections::HashMap, path::Path};
-use common::{get_layout_tables_except_key, get_tomlview_for_key, table_to_tomlview};
+use common::{get_layout_tables_except_key, get_tomlview_for_key, read_document, table_to_tomlview};
 use toml_edit::{value, Table};
 
+use crate::error::Result;
+use crate::head::HeadMeta;
+use pagination::{paged_html, paged_lists};
+
 mod padding;
+mod base;
 mod view;
 pub mod common;
 mod svg;
 mod nav;
+pub mod pagination;
+pub mod placeholder;
+pub mod query;
+mod search;
+pub mod schema;
+pub mod registry;
+pub mod component;
+pub mod responsive;
+pub mod style;
 
-pub fn toml_to_html(layout_path: &Path, layout_tables: HashMap<String, Table>) -> Result<String, String> {
-    let view = get_tomlview_for_key(layout_path, "root", None, None, layout_tables)?;
-    let html_view = view.htmlview(None).wrap_body(view.dark());
-    let html = html_view.html();
+pub use view::TOMLView;
+
+/// ### toml to html
+/// index.toml의 root layout부터 html 문서를 만든다.
+///
+/// layout_tables는 상위 폴더에서 물려받은 layout들
+pub fn toml_to_html(layout_path: &Path, layout_tables: HashMap<String, Table>) -> Result<String> {
+    let (html, _) = toml_to_html_and_lists(layout_path, &layout_tables)?;
     return Ok(html);
 }
 
-pub fn layouts_from_toml(index_path: &Path) -> Result<HashMap<String, Table>, String> {
+/// ### toml to paged html
+/// toml_to_html과 같고, page_size로 나뉜 목록의 2쪽부터를 쓸 폴더와 함께 돌려준다.
+pub fn toml_to_paged_html(layout_path: &Path, layout_tables: &HashMap<String, Table>) -> Result<(String, Vec<(String, String)>)> {
+    paged_html(layout_tables, |layout_tables| toml_to_html_and_lists(layout_path, layout_tables))
+}
+
+fn toml_to_html_and_lists(layout_path: &Path, layout_tables: &HashMap<String, Table>) -> Result<(String, Vec<(String, i64)>)> {
+    let view = get_tomlview_for_key(layout_path, "root", None, None, layout_tables)?;
+    let root = read_document(layout_path)?
+        .get("root")
+        .and_then(|x| x.as_table())
+        .cloned()
+        .unwrap_or_default();
+    let head = HeadMeta::new(&root, layout_path.parent().unwrap_or(Path::new(".")), layout_tables);
+    let html_view = view.htmlview(None).wrap_body(view.dark(), layout_tables, &head);
+    Ok((html_view.html(), paged_lists(view.as_ref())))
+}
+
+pub fn layouts_from_toml(index_path: &Path) -> Result<HashMap<String, Table>> {
     let tables = get_layout_tables_except_key(index_path, "root")?;
     Ok(tables)
 }
\ No newline at end of file
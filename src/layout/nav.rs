@@ -5,6 +5,7 @@ use toml_edit::{Item, Table};
 use crate::html::{filter_attrs, HTMLView};
 use super::svg::{svg_dropdown, svg_menu};
 use crate::common::SlashNormalize;
+use crate::theme::Theme;
 
 
 pub fn make_nav_sub_menus(title: String, sub_menus: &Table, is_dark: bool) -> HTMLView {
@@ -35,7 +36,13 @@ pub fn make_nav_sub_menus(title: String, sub_menus: &Table, is_dark: bool) -> HT
   HTMLView::new("li", hashmap! {}, "", vec![dropdown_navbar_link, dropdown_navbar])
 }
 
-pub fn make_nav(title: String, menus: Vec<String>, sub_menus: HashMap<String, Item>, is_dark: bool) -> HTMLView {
+/// 글자색은 theme의 text, 마우스를 올렸을 때는 primary, 배경은 surface
+pub fn make_nav(title: String, menus: Vec<String>, sub_menus: HashMap<String, Item>, is_dark: bool, theme: &Theme) -> HTMLView {
+  let link_class = format!(
+      "block py-2 px-3 {} rounded-sm hover:bg-gray-100 md:hover:bg-transparent md:border-0 {} md:p-0 dark:hover:bg-gray-700 dark:hover:text-white md:dark:hover:bg-transparent",
+      theme.color_class("text", "text", "text-gray-900 dark:text-white"),
+      theme.color_class("primary", "md:hover:text", "md:hover:text-blue-700 md:dark:hover:text-blue-500"),
+  );
   let nav_menus = menus
       .iter()
       .map(|menu| {
@@ -43,7 +50,7 @@ pub fn make_nav(title: String, menus: Vec<String>, sub_menus: HashMap<String, It
               if let Item::Value(value) = sub {
                   HTMLView::new("a", hashmap! {
                       "href".to_string() => value.as_str().unwrap_or("").ensure_slashes(),
-                      "class".to_string() => filter_attrs("block py-2 px-3 text-gray-900 rounded-sm hover:bg-gray-100 md:hover:bg-transparent md:border-0 md:hover:text-blue-700 md:p-0 dark:text-white md:dark:hover:text-blue-500 dark:hover:bg-gray-700 dark:hover:text-white md:dark:hover:bg-transparent", is_dark),
+                      "class".to_string() => filter_attrs(&link_class, is_dark),
                       "aria-current".to_string() => "page".to_string()
                   }, menu, vec![])
                       .wrap_tag("li", hashmap! {})
@@ -56,7 +63,7 @@ pub fn make_nav(title: String, menus: Vec<String>, sub_menus: HashMap<String, It
           } else {
               HTMLView::new("a", hashmap! {
                   "href".to_string() => "#".to_string(),
-                  "class".to_string() => filter_attrs("block py-2 px-3 text-gray-900 rounded-sm hover:bg-gray-100 md:hover:bg-transparent md:border-0 md:hover:text-blue-700 md:p-0 dark:text-white md:dark:hover:text-blue-500 dark:hover:bg-gray-700 dark:hover:text-white md:dark:hover:bg-transparent", is_dark),
+                  "class".to_string() => filter_attrs(&link_class, is_dark),
                   "aria-current".to_string() => "page".to_string()
               }, menu, vec![]).wrap_tag("li", hashmap! {})
           }
@@ -83,7 +90,7 @@ pub fn make_nav(title: String, menus: Vec<String>, sub_menus: HashMap<String, It
   }, "", vec![svg_menu(), right_collapse_span]);
 
   let left_text = HTMLView::new("span", hashmap! {
-      "class".to_string() => filter_attrs("self-center text-2xl font-semibold whitespace-nowrap dark:text-white", is_dark),
+      "class".to_string() => filter_attrs(&format!("self-center text-2xl font-semibold whitespace-nowrap {}", theme.color_class("text", "text", "dark:text-white")), is_dark),
   }, title.as_str(), vec![]);
   let left = HTMLView::new("a", hashmap! {
       "class".to_string() => filter_attrs("flex items-center space-x-3 rtl:space-x-reverse", is_dark), 
@@ -91,5 +98,5 @@ pub fn make_nav(title: String, menus: Vec<String>, sub_menus: HashMap<String, It
   }, "", vec![left_text]);
 
   let div = HTMLView::new("div", hashmap! {"class".to_string() => filter_attrs("flex flex-wrap items-center justify-between p-4", is_dark) }, "", vec![left, right_collapse, right]);
-  HTMLView::new("nav", hashmap! {"class".to_string() => filter_attrs("bg-white border-gray-200 dark:bg-gray-900 dark:border-gray-700", is_dark) }, "", vec![div])
+  HTMLView::new("nav", hashmap! {"class".to_string() => filter_attrs(&format!("{} {}", theme.color_class("surface", "bg", "bg-white dark:bg-gray-900"), theme.color_class("border", "border", "border-gray-200 dark:border-gray-700")), is_dark) }, "", vec![div])
 }
\ No newline at end of file
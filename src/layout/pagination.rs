@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use toml_edit::{value, InlineTable, Table};
+
+use crate::error::Result;
+
+use super::common::SITE_TABLE;
+use super::view::TOMLView;
+
+/// [site] table에 들어가는 목록 key path마다 지금 그리는 쪽 번호, 1부터
+pub const PAGES_KEY: &str = "pages";
+
+/// [site] table에 들어가는 page/N/ 을 갖는 목록의 key path, 없으면 나뉜 목록 모두가 page/N/ 을 쓴다.
+pub const PAGE_OWNER_KEY: &str = "page_owner";
+
+/// [site] table에 들어가는 지금 쓰는 html의 폴더, "page/2/" 처럼. 1쪽은 없다.
+pub const PAGE_DIR_KEY: &str = "page_dir";
+
+/// ### pagination
+/// page_size로 나뉜 목록의 지금 쪽과 전체 쪽 수
+///
+/// pagination layout에 {page}, {total_pages}, {prev_url}, {next_url}로 넘긴다.
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    /// 나뉜 목록의 "root.posts" 같은 key path
+    pub key: String,
+    pub page: i64,
+    pub total_pages: i64,
+    /// 이 목록의 2쪽부터가 놓이는 폴더, "page/" 나 "page/tags/"
+    pub base: String,
+    /// 지금 쓰는 html이 페이지 폴더에서 몇 단계 아래인지
+    pub depth: usize,
+}
+
+impl Pagination {
+    /// 지금 쪽에서 page 쪽으로 가는 상대 주소, 1쪽은 폴더 자신, 2쪽부터 base 아래 N/
+    pub fn url(&self, page: i64) -> String {
+        let up = "../".repeat(self.depth);
+        match (self.page, page) {
+            (1, 1) if up.is_empty() => "./".to_string(),
+            (1, 1) => up,
+            (1, _) => format!("{}{}{}/", up, self.base, page),
+            (_, 1) => up,
+            (_, _) => format!("../{}/", page),
+        }
+    }
+
+    /// pagination layout의 value, 앞이나 뒤 쪽이 없으면 주소는 빈 문자열
+    pub fn values(&self) -> InlineTable {
+        let mut values = InlineTable::new();
+        values.insert("page", self.page.to_string().into());
+        values.insert("total_pages", self.total_pages.to_string().into());
+        let prev_url = if self.page > 1 { self.url(self.page - 1) } else { String::new() };
+        let next_url = if self.page < self.total_pages { self.url(self.page + 1) } else { String::new() };
+        values.insert("prev_url", prev_url.into());
+        values.insert("next_url", next_url.into());
+        values
+    }
+}
+
+/// key 목록의 지금 그리는 쪽 번호, 없으면 1
+pub fn current_page(layout_tables: &HashMap<String, Table>, key: &str) -> i64 {
+    layout_tables
+        .get(SITE_TABLE)
+        .and_then(|table| table.get(PAGES_KEY))
+        .and_then(|item| item.get(key))
+        .and_then(|item| item.as_integer())
+        .unwrap_or(1)
+}
+
+/// 지금 쓰는 html이 페이지 폴더에서 몇 단계 아래인지, 1쪽은 0
+pub fn page_depth(layout_tables: &HashMap<String, Table>) -> usize {
+    layout_tables
+        .get(SITE_TABLE)
+        .and_then(|table| table.get(PAGE_DIR_KEY))
+        .and_then(|item| item.as_str())
+        .map(|dir| dir.split('/').filter(|x| !x.is_empty()).count())
+        .unwrap_or(0)
+}
+
+/// key 목록의 2쪽부터가 놓이는 폴더, page/N/ 을 갖는 목록이 아니면 page/{key}/N/
+///
+/// key path의 root는 빼고 나머지는 폴더로 나눈다. "root.side.tags"는 page/side/tags/N/
+pub fn page_base(layout_tables: &HashMap<String, Table>, key: &str) -> String {
+    let owner = layout_tables
+        .get(SITE_TABLE)
+        .and_then(|table| table.get(PAGE_OWNER_KEY))
+        .and_then(|item| item.as_str());
+    match owner {
+        Some(owner) if owner != key => {
+            let dir = key.strip_prefix("root.").unwrap_or(key).replace('.', "/");
+            format!("page/{}/", dir)
+        }
+        _ => "page/".to_string(),
+    }
+}
+
+/// ### paginate
+/// page_size가 0보다 크면 key 목록의 지금 쪽의 것만 남긴다.
+pub fn paginate<T>(items: Vec<T>, page_size: i64, key: &str, layout_tables: &HashMap<String, Table>) -> (Vec<T>, Option<Pagination>) {
+    if page_size <= 0 {
+        return (items, None);
+    }
+    let total_pages = (items.len().div_ceil(page_size as usize) as i64).max(1);
+    let page = current_page(layout_tables, key).clamp(1, total_pages);
+    let items = items
+        .into_iter()
+        .skip(((page - 1) * page_size) as usize)
+        .take(page_size as usize)
+        .collect();
+    let pagination = Pagination {
+        key: key.to_string(),
+        page,
+        total_pages,
+        base: page_base(layout_tables, key),
+        depth: page_depth(layout_tables),
+    };
+    (items, Some(pagination))
+}
+
+/// view와 하위 view들에 있는 나뉜 목록들의 key path와 전체 쪽 수, 위에서부터 차례대로
+///
+/// 목록 안의 layout마다 같은 목록이 또 있으면 가장 큰 전체 쪽 수를 쓴다.
+pub fn paged_lists(view: &dyn TOMLView) -> Vec<(String, i64)> {
+    fn collect(view: &dyn TOMLView, lists: &mut Vec<(String, i64)>) {
+        if let Some(pagination) = view.pagination() {
+            match lists.iter_mut().find(|(key, _)| *key == pagination.key) {
+                Some(list) => list.1 = list.1.max(pagination.total_pages),
+                None => lists.push((pagination.key, pagination.total_pages)),
+            }
+        }
+        for sub_view in view.views() {
+            collect(sub_view.as_ref(), lists);
+        }
+    }
+    let mut lists = vec![];
+    collect(view, &mut lists);
+    lists
+}
+
+/// owner 목록이 page/N/ 을 갖게 하는 layout_tables
+pub fn with_owner(layout_tables: &HashMap<String, Table>, owner: &str) -> HashMap<String, Table> {
+    let mut layout_tables = layout_tables.clone();
+    layout_tables
+        .entry(SITE_TABLE.to_string())
+        .or_default()
+        .insert(PAGE_OWNER_KEY, value(owner));
+    layout_tables
+}
+
+/// key 목록의 page 쪽을 그릴 때 쓰는 layout_tables, 다른 목록은 1쪽이다.
+pub fn with_page(layout_tables: &HashMap<String, Table>, key: &str, page: i64) -> HashMap<String, Table> {
+    let dir = format!("{}{}/", page_base(layout_tables, key), page);
+    let mut pages = InlineTable::new();
+    pages.insert(key, page.into());
+    let mut layout_tables = layout_tables.clone();
+    let site = layout_tables.entry(SITE_TABLE.to_string()).or_default();
+    site.insert(PAGES_KEY, value(pages));
+    site.insert(PAGE_DIR_KEY, value(dir));
+    layout_tables
+}
+
+/// with_page로 그린 html을 쓸 폴더, "page/2" 처럼
+pub fn page_dir(layout_tables: &HashMap<String, Table>) -> String {
+    layout_tables
+        .get(SITE_TABLE)
+        .and_then(|table| table.get(PAGE_DIR_KEY))
+        .and_then(|item| item.as_str())
+        .unwrap_or("")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// ### paged html
+/// render로 1쪽 html을 그리고, 나뉜 목록마다 2쪽부터를 ("page/2", html) 처럼 쓸 폴더와 함께 그린다.
+///
+/// render는 layout_tables로 그린 html과 그 html의 root view가 가진 나뉜 목록들을 돌려준다.
+/// 나뉜 목록이 여럿이면 처음 것만 page/N/ 을 갖고, 나머지는 page/{key}/N/ 에 쓴다.
+pub fn paged_html<F>(layout_tables: &HashMap<String, Table>, render: F) -> Result<(String, Vec<(String, String)>)>
+where
+    F: Fn(&HashMap<String, Table>) -> Result<(String, Vec<(String, i64)>)>,
+{
+    let (mut html, lists) = render(layout_tables)?;
+    if lists.is_empty() {
+        return Ok((html, vec![]));
+    }
+    let owner_tables;
+    let layout_tables = if lists.len() > 1 {
+        owner_tables = with_owner(layout_tables, &lists[0].0);
+        html = render(&owner_tables)?.0;
+        &owner_tables
+    } else {
+        layout_tables
+    };
+    let mut pages = vec![];
+    for (key, total) in &lists {
+        for page in 2..=*total {
+            let page_tables = with_page(layout_tables, key, page);
+            pages.push((page_dir(&page_tables), render(&page_tables)?.0));
+        }
+    }
+    Ok((html, pages))
+}
@@ -0,0 +1,177 @@
+use std::fmt::Write;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use toml_edit::{InlineTable, Value};
+
+use super::common::lookup_value;
+use super::query::parse_date;
+
+/// {key | 필터} 에 쓸 수 있는 필터
+pub const PLACEHOLDER_FILTERS: [&str; 5] = ["date", "upper", "lower", "truncate", "default"];
+
+/// date 필터에 형식을 주지 않았을 때
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// {key}, 글자 그대로의 {{, }} 를 찾는다.
+static PLACEHOLDER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{|\}\}|\{([^{}]+)\}").unwrap());
+
+/// placeholder의 key, "company.name" 처럼 .으로 이어진다.
+static KEY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[\w\-]+(\.[\w\-]+)*$").unwrap());
+
+/// placeholder의 필터 이름
+static FILTER_NAME_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\w+$").unwrap());
+
+/// ### fill placeholders
+/// raw 안의 {key}, {key | 필터:"인자" | 필터} 를 value의 값으로 바꾼다.
+///
+/// 값이 없으면 default 필터의 값, 그것도 없으면 빈 문자열이 된다.
+/// `{"a": 1}` 처럼 key로 읽을 수 없는 것은 그대로 둔다.
+/// {{, }} 는 placeholder가 있는 문자열에서만 {, } 가 되고, 없으면 raw를 그대로 돌려준다.
+pub fn fill_placeholders(raw: &str, value: &InlineTable) -> String {
+    if !has_placeholders(raw) {
+        return raw.to_string();
+    }
+    PLACEHOLDER_RE
+        .replace_all(raw, |caps: &regex::Captures| match &caps[0] {
+            "{{" => "{".to_string(),
+            "}}" => "}".to_string(),
+            whole => match parse_placeholder(&caps[1]) {
+                Some(placeholder) => placeholder.resolve(value).unwrap_or_default(),
+                None => whole.to_string(),
+            },
+        })
+        .to_string()
+}
+
+/// raw에 key로 읽히는 {key} 가 하나라도 있는지
+fn has_placeholders(raw: &str) -> bool {
+    raw.contains('{')
+        && PLACEHOLDER_RE
+            .captures_iter(raw)
+            .any(|caps| caps.get(1).is_some_and(|inner| parse_placeholder(inner.as_str()).is_some()))
+}
+
+/// {} 안쪽, key와 차례로 거칠 필터들
+struct Placeholder {
+    key: String,
+    filters: Vec<(String, Option<String>)>,
+}
+
+impl Placeholder {
+    /// 값이 없으면 None
+    fn resolve(&self, value: &InlineTable) -> Option<String> {
+        let mut found = lookup_value(value, &self.key).map(value_to_text);
+        for (name, arg) in &self.filters {
+            found = apply_filter(name, arg.as_deref(), found);
+        }
+        found
+    }
+}
+
+/// `key` 또는 `key | 필터:"인자" | 필터` 꼴일 때만 placeholder로 읽는다.
+fn parse_placeholder(inner: &str) -> Option<Placeholder> {
+    let mut parts = split_filters(inner)?.into_iter();
+    let key = parts.next()?.trim().to_string();
+    if !KEY_RE.is_match(&key) {
+        return None;
+    }
+    let filters = parts
+        .map(|part| {
+            let (name, arg) = parse_filter(&part);
+            FILTER_NAME_RE.is_match(name).then(|| (name.to_string(), arg))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(Placeholder { key, filters })
+}
+
+/// 문자열은 그대로, 숫자와 bool은 글자로, 배열은 ", "로 이어서
+pub fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.value().to_string(),
+        Value::Array(array) => array.iter().map(value_to_text).collect::<Vec<String>>().join(", "),
+        value => value.to_string().trim().to_string(),
+    }
+}
+
+/// placeholder의 필터 이름이 모두 있는 것인지
+pub fn check_placeholders(raw: &str) -> Result<(), String> {
+    for caps in PLACEHOLDER_RE.captures_iter(raw) {
+        let Some(placeholder) = caps.get(1).and_then(|inner| parse_placeholder(inner.as_str())) else {
+            continue;
+        };
+        for (name, _) in &placeholder.filters {
+            if !PLACEHOLDER_FILTERS.contains(&name.as_str()) {
+                return Err(format!("unknown filter \"{}\" in {}, expected one of {}", name, &caps[0], PLACEHOLDER_FILTERS.join(", ")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 따옴표 밖의 | 로 나눈다. 따옴표가 닫히지 않으면 None
+fn split_filters(inner: &str) -> Option<Vec<String>> {
+    let mut parts = vec![String::new()];
+    let mut quote: Option<char> = None;
+    for c in inner.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('|', None) => {
+                parts.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(part) = parts.last_mut() {
+            part.push(c);
+        }
+    }
+    quote.is_none().then_some(parts)
+}
+
+/// `truncate:120` -> ("truncate", Some("120")), `date:"%Y"` -> ("date", Some("%Y"))
+fn parse_filter(part: &str) -> (&str, Option<String>) {
+    let part = part.trim();
+    match part.split_once(':') {
+        Some((name, arg)) => {
+            let arg = arg.trim();
+            let arg = ['"', '\'']
+                .iter()
+                .find_map(|quote| arg.strip_prefix(*quote).and_then(|x| x.strip_suffix(*quote)))
+                .unwrap_or(arg);
+            (name.trim(), Some(arg.to_string()))
+        }
+        None => (part, None),
+    }
+}
+
+/// 모르는 필터나 읽을 수 없는 값은 그대로 넘긴다.
+fn apply_filter(name: &str, arg: Option<&str>, found: Option<String>) -> Option<String> {
+    if name == "default" {
+        return match found {
+            Some(text) if !text.is_empty() => Some(text),
+            _ => Some(arg.unwrap_or("").to_string()),
+        };
+    }
+    let text = found?;
+    Some(match name {
+        "upper" => text.to_uppercase(),
+        "lower" => text.to_lowercase(),
+        "truncate" => match arg.and_then(|x| x.parse::<usize>().ok()) {
+            Some(length) if text.chars().count() > length => {
+                format!("{}…", text.chars().take(length).collect::<String>().trim_end())
+            }
+            _ => text,
+        },
+        "date" => {
+            let mut formatted = String::new();
+            // 잘못된 형식 문자는 format이 에러를 내므로 그때는 원래 값
+            match parse_date(&text) {
+                Some(date) if write!(formatted, "{}", date.format(arg.unwrap_or(DEFAULT_DATE_FORMAT))).is_ok() => formatted,
+                _ => text,
+            }
+        }
+        _ => text,
+    })
+}
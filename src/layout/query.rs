@@ -0,0 +1,331 @@
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use dateparser::parse_with;
+use regex::Regex;
+use toml_edit::{InlineTable, Table, Value};
+
+use super::common::{item_to_integer, item_to_string, lookup_value};
+
+/// ### sortable
+/// order_by와 filter가 비교하는 frontmatter 값
+///
+/// 숫자, 날짜, 문자열 순으로 알아보고, 종류가 다르면 없음 < 숫자 < 날짜 < 문자열 이다.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sortable {
+    Missing,
+    Number(f64),
+    Date(DateTime<Utc>),
+    Text(String),
+}
+
+impl Sortable {
+    pub fn from_value(value: Option<&Value>) -> Sortable {
+        match value {
+            None => Sortable::Missing,
+            Some(Value::Integer(x)) => Sortable::Number(*x.value() as f64),
+            Some(Value::Float(x)) => Sortable::Number(*x.value()),
+            Some(Value::Boolean(x)) => Sortable::Text(x.value().to_string()),
+            Some(Value::String(x)) => Sortable::from_text(x.value()),
+            Some(value) => Sortable::Text(value.to_string().trim().to_string()),
+        }
+    }
+
+    pub fn from_text(text: &str) -> Sortable {
+        let text = text.trim();
+        if let Ok(number) = text.parse::<f64>() {
+            return Sortable::Number(number);
+        }
+        match parse_date(text) {
+            Some(date) => Sortable::Date(date),
+            None => Sortable::Text(text.to_string()),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Sortable::Missing => 0,
+            Sortable::Number(_) => 1,
+            Sortable::Date(_) => 2,
+            Sortable::Text(_) => 3,
+        }
+    }
+
+    pub fn compare(&self, other: &Sortable) -> Ordering {
+        match (self, other) {
+            (Sortable::Number(a), Sortable::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+            (Sortable::Date(a), Sortable::Date(b)) => a.cmp(b),
+            (Sortable::Text(a), Sortable::Text(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+/// ### parse date
+/// "2025-10-01", "Oct 1, 2025" 같은 날짜, dateparser가 모르는 "25-10-01" 처럼 두 자리 연도도 읽는다.
+pub fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    // dateparser는 "3" 같은 짧은 값도 날짜로 읽으므로 숫자가 섞인 것만
+    if !text.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // 시간이 없는 날짜는 지금 시각이 아니라 0시로 읽어야 같은 날끼리 같다.
+    if let Ok(date) = parse_with(text, &Utc, NaiveTime::MIN) {
+        return Some(date);
+    }
+    ["%y-%m-%d", "%y.%m.%d", "%y/%m/%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+/// 문자열 하나 또는 문자열 배열
+pub fn item_to_keys(table: &Table, key: &str, value: Option<&InlineTable>) -> Vec<String> {
+    let keys = match table.get(key).and_then(|item| item.as_array()) {
+        Some(array) => array.iter().filter_map(|x| x.as_str()).map(|x| x.to_string()).collect(),
+        None => vec![item_to_string(table, key, "", value)],
+    };
+    keys.into_iter().map(|x| x.trim().to_string()).filter(|x| !x.is_empty()).collect()
+}
+
+/// ### sort metas
+/// order_by 순서대로, 앞의 key가 같으면 다음 key로 비교한다. "created_desc" 처럼 _desc가 붙으면 거꾸로
+pub fn sort_metas(metas: Vec<InlineTable>, order_by: &[String]) -> Vec<InlineTable> {
+    if order_by.is_empty() {
+        return metas;
+    }
+    let orders: Vec<(&str, bool)> = order_by
+        .iter()
+        .map(|key| match key.strip_suffix("_desc") {
+            Some(key) => (key, true),
+            None => (key.as_str(), false),
+        })
+        .collect();
+    let mut keyed: Vec<(Vec<Sortable>, InlineTable)> = metas
+        .into_iter()
+        .map(|metas| {
+            let keys = orders.iter().map(|(key, _)| Sortable::from_value(lookup_value(&metas, key))).collect();
+            (keys, metas)
+        })
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| {
+        orders
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .map(|((_, desc), (a, b))| if *desc { b.compare(a) } else { a.compare(b) })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    keyed.into_iter().map(|(_, metas)| metas).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Contains,
+    NotContains,
+}
+
+/// ### filter
+/// `tags contains "rust"`, `publish == true`, `created >= "2024-01-01"` 같은 frontmatter 조건
+#[derive(Debug, Clone)]
+pub struct Filter {
+    key: String,
+    operator: Operator,
+    literal: String,
+}
+
+/// "key 연산자 값"
+static FILTER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*([\w.\-]+)\s+(==|!=|>=|<=|>|<|!contains|contains)\s+(.+?)\s*$"#).unwrap()
+});
+
+impl Filter {
+    /// ### parse
+    /// "key 연산자 값", 연산자는 ==, !=, >, >=, <, <=, contains, !contains
+    pub fn parse(expression: &str) -> Result<Filter, String> {
+        let caps = FILTER_RE.captures(expression).ok_or_else(|| {
+            format!("invalid filter \"{}\", expected \"key operator value\" like tags contains \"rust\"", expression)
+        })?;
+        let operator = match &caps[2] {
+            "==" => Operator::Equal,
+            "!=" => Operator::NotEqual,
+            ">" => Operator::Greater,
+            ">=" => Operator::GreaterEqual,
+            "<" => Operator::Less,
+            "<=" => Operator::LessEqual,
+            "contains" => Operator::Contains,
+            _ => Operator::NotContains,
+        };
+        let literal = caps[3].to_string();
+        let literal = ['"', '\'']
+            .iter()
+            .find_map(|quote| literal.strip_prefix(*quote).and_then(|x| x.strip_suffix(*quote)))
+            .map(|x| x.to_string())
+            .unwrap_or(literal);
+        Ok(Filter { key: caps[1].to_string(), operator, literal })
+    }
+
+    pub fn matches(&self, metas: &InlineTable) -> bool {
+        let value = lookup_value(metas, &self.key);
+        match self.operator {
+            Operator::Contains => contains(value, &self.literal),
+            Operator::NotContains => !contains(value, &self.literal),
+            Operator::NotEqual => self.compare(value) != Some(Ordering::Equal),
+            operator => match self.compare(value) {
+                None => false,
+                Some(ordering) => match operator {
+                    Operator::Equal => ordering.is_eq(),
+                    Operator::Greater => ordering.is_gt(),
+                    Operator::GreaterEqual => ordering.is_ge(),
+                    Operator::Less => ordering.is_lt(),
+                    _ => ordering.is_le(),
+                },
+            },
+        }
+    }
+
+    /// 값이 없거나 종류가 달라 비교할 수 없으면 None
+    fn compare(&self, value: Option<&Value>) -> Option<Ordering> {
+        let value = Sortable::from_value(value);
+        let literal = Sortable::from_text(&self.literal);
+        (value.rank() == literal.rank() && value != Sortable::Missing).then(|| value.compare(&literal))
+    }
+}
+
+/// 배열이면 같은 값이 있는지, 문자열이면 그 글자가 들어있는지
+fn contains(value: Option<&Value>, literal: &str) -> bool {
+    match value {
+        Some(Value::Array(array)) => array.iter().any(|x| match x.as_str() {
+            Some(text) => text.trim() == literal,
+            None => x.to_string().trim() == literal,
+        }),
+        Some(Value::String(text)) => text.value().contains(literal),
+        _ => false,
+    }
+}
+
+/// ### query metas
+/// filter로 거르고 order_by 순서로 모아 offset, limit 만큼 자른다.
+pub fn query_metas(metas: Vec<InlineTable>, table: &Table, value: Option<&InlineTable>) -> Vec<InlineTable> {
+    // 잘못된 filter는 validate_toml이 알려주므로 여기서는 건너뛴다.
+    let filters: Vec<Filter> = item_to_keys(table, "filter", value)
+        .iter()
+        .filter_map(|x| Filter::parse(x).ok())
+        .collect();
+    let metas: Vec<InlineTable> = metas
+        .into_iter()
+        .filter(|metas| filters.iter().all(|filter| filter.matches(metas)))
+        .collect();
+
+    let offset = item_to_integer(table, "offset", 0, value).max(0) as usize;
+    let limit = item_to_integer(table, "limit", 0, value);
+    let metas = sort_metas(metas, &item_to_keys(table, "order_by", value));
+    let metas = group_metas(metas, &item_to_string(table, "group_by", "", value)).into_iter().skip(offset);
+    if limit > 0 {
+        metas.take(limit as usize).collect()
+    } else {
+        metas.collect()
+    }
+}
+
+/// ### condition
+/// if, unless 값 하나. "image" 처럼 key만 쓰면 그 값이 있고 비어 있지 않은지, 아니면 filter 식
+pub fn check_condition(expression: &str) -> Result<(), String> {
+    if is_key(expression) {
+        return Ok(());
+    }
+    Filter::parse(expression).map(|_| ())
+}
+
+/// 조건에 key만 쓴 것, "image" 나 "author.name"
+static KEY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*[\w.\-]+\s*$").unwrap());
+
+fn is_key(expression: &str) -> bool {
+    KEY_RE.is_match(expression)
+}
+
+/// 값이 있고 "", false, 0, 빈 배열이 아니면 참
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None => false,
+        Some(Value::String(text)) => !text.value().trim().is_empty() && !text.value().trim().eq_ignore_ascii_case("false"),
+        Some(Value::Boolean(x)) => *x.value(),
+        Some(Value::Integer(x)) => *x.value() != 0,
+        Some(Value::Float(x)) => *x.value() != 0.0,
+        Some(Value::Array(array)) => !array.is_empty(),
+        Some(Value::InlineTable(table)) => !table.is_empty(),
+        Some(Value::Datetime(_)) => true,
+    }
+}
+
+fn is_true(expression: &str, value: &InlineTable) -> bool {
+    if is_key(expression) {
+        return is_truthy(lookup_value(value, expression.trim()));
+    }
+    Filter::parse(expression).is_ok_and(|filter| filter.matches(value))
+}
+
+/// ### is shown
+/// if가 참이고 unless가 거짓일 때만 그린다. 상위 value에 table의 value를 덮어쓴 값으로 본다.
+pub fn is_shown(table: &Table, value: Option<&InlineTable>) -> bool {
+    let condition = |key: &str| table.get(key).and_then(|item| item.as_str());
+    let (when, unless) = (condition("if"), condition("unless"));
+    if when.is_none() && unless.is_none() {
+        return true;
+    }
+    let mut merged = value.cloned().unwrap_or_default();
+    if let Some(table_value) = table.get("value").and_then(|item| item.as_inline_table()) {
+        for (k, v) in table_value.iter() {
+            merged.insert(k, v.clone());
+        }
+    }
+    when.is_none_or(|x| is_true(x, &merged)) && !unless.is_some_and(|x| is_true(x, &merged))
+}
+
+/// ### group metas
+/// 같은 묶음을 붙여 놓는다. 묶음은 처음 나온 차례대로, 묶음 안은 order_by 순서 그대로
+///
+/// order_by가 group_by와 다른 key여도 묶음의 머리가 한 번만 나온다.
+pub fn group_metas(metas: Vec<InlineTable>, group_by: &str) -> Vec<InlineTable> {
+    if group_by.is_empty() {
+        return metas;
+    }
+    let mut groups: Vec<(String, Vec<InlineTable>)> = vec![];
+    for metas in metas {
+        let name = group_name(&metas, group_by);
+        match groups.iter_mut().find(|(x, _)| *x == name) {
+            Some((_, items)) => items.push(metas),
+            None => groups.push((name, vec![metas])),
+        }
+    }
+    groups.into_iter().flat_map(|(_, items)| items).collect()
+}
+
+/// ### group name
+/// "created:year" -> "2024", "created:month" -> "2024-03", "created:day" -> "2024-03-01", 그냥 key면 그 값
+pub fn group_name(metas: &InlineTable, group_by: &str) -> String {
+    let (key, unit) = group_by.split_once(':').unwrap_or((group_by, ""));
+    let value = lookup_value(metas, key.trim());
+    let format = match unit.trim() {
+        "year" => "%Y",
+        "month" => "%Y-%m",
+        "day" => "%Y-%m-%d",
+        _ => "",
+    };
+    match Sortable::from_value(value) {
+        Sortable::Date(date) if !format.is_empty() => date.format(format).to_string(),
+        Sortable::Missing => String::new(),
+        _ => match value {
+            Some(Value::String(text)) => text.value().trim().to_string(),
+            Some(value) => value.to_string().trim().to_string(),
+            None => String::new(),
+        },
+    }
+}
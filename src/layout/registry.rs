@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use toml_edit::{InlineTable, Table};
+
+use super::view::{BoxView, ColumnView, EmbedView, GridView, ImageView, ListColumnView, ListRowView, MarkdownListColumnView, MarkdownListRowView, MarkdownView, NavView, RowView, SearchView, TOMLView, TextView};
+use crate::error::Result;
+
+/// shape 이름으로 view를 만드는 함수
+///
+/// 인자는 table_to_tomlview와 같다. (index_path, key, table, value, super_view, layout_tables)
+pub type ShapeConstructor = fn(&Path, &str, &Table, Option<&InlineTable>, Option<&dyn TOMLView>, &HashMap<String, Table>) -> Result<Box<dyn TOMLView>>;
+
+static SHAPES: OnceLock<RwLock<HashMap<String, ShapeConstructor>>> = OnceLock::new();
+
+fn shapes() -> &'static RwLock<HashMap<String, ShapeConstructor>> {
+    SHAPES.get_or_init(|| RwLock::new(builtin_shapes()))
+}
+
+/// ### register shape
+/// index.toml에서 shape = "{name}" 으로 쓸 수 있는 view를 등록한다.
+///
+/// 기본 shape와 이름이 같으면 기본 shape를 대신한다.
+pub fn register_shape(name: &str, constructor: ShapeConstructor) {
+    shapes()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(name.to_string(), constructor);
+}
+
+pub fn shape_constructor(name: &str) -> Option<ShapeConstructor> {
+    shapes()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .copied()
+}
+
+pub fn is_registered(name: &str) -> bool {
+    shape_constructor(name).is_some()
+}
+
+fn builtin_shapes() -> HashMap<String, ShapeConstructor> {
+    let shapes: [(&str, ShapeConstructor); 17] = [
+        ("nav", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(NavView::new(index_path, key, table, value, super_view, layout_tables)))
+        }),
+        ("search", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(SearchView::new(index_path, key, table, value, super_view, layout_tables)))
+        }),
+        ("box", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(BoxView::new(index_path, key, table, value, super_view, layout_tables)))
+        }),
+        ("column", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(ColumnView::new(index_path, key, table, value, super_view, layout_tables, false)))
+        }),
+        ("row", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(RowView::new(index_path, key, table, value, super_view, layout_tables, false)))
+        }),
+        ("scroll_column", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(ColumnView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("scroll_row", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(RowView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("text", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(TextView::new(index_path, key, table, value, super_view, layout_tables)))
+        }),
+        ("image", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(ImageView::new(index_path, key, table, value, super_view, layout_tables)))
+        }),
+        ("list_column", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(ListColumnView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("list_row", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(ListRowView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("mdlist_column", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(MarkdownListColumnView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("mdlist_row", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(MarkdownListRowView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("markdown", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(MarkdownView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("grid", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(GridView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("mdgrid", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(GridView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+        ("embed", |index_path, key, table, value, super_view, layout_tables| {
+            Ok(Box::new(EmbedView::new(index_path, key, table, value, super_view, layout_tables, true)))
+        }),
+    ];
+    shapes
+        .into_iter()
+        .map(|(name, constructor)| (name.to_string(), constructor))
+        .collect()
+}
@@ -0,0 +1,169 @@
+use toml_edit::{InlineTable, Item, Table, Value};
+
+use super::common::item_to_string;
+
+/// tailwind breakpoint, 작은 화면부터
+pub const BREAKPOINTS: [&str; 5] = ["sm", "md", "lg", "xl", "2xl"];
+
+/// breakpoint 없이 모든 화면에 적용되는 값의 key
+pub const BASE_BREAKPOINT: &str = "base";
+
+/// ### breakpoint classes
+/// 3 또는 { sm = 1, md = 2, lg = 4 } 를 class 앞부분과 합쳐 tailwind class로 바꾼다.
+///
+/// "grid-cols", { base = 1, lg = 4 } -> ["grid-cols-1", "lg:grid-cols-4"]
+///
+/// breakpoint 없는 class는 base가 있을 때만 만든다. { md = 2 } 는 md보다 작은 화면에 class가 없다.
+pub fn breakpoint_classes(table: &Table, key: &str, class: &str, value: Option<&InlineTable>) -> Vec<String> {
+    breakpoint_classes_with(table, key, value, |raw| class_with_value(class, raw))
+}
+
+/// breakpoint_classes와 같고, 값 하나를 class로 바꾸는 방법을 정할 수 있다.
+pub fn breakpoint_classes_with(table: &Table, key: &str, value: Option<&InlineTable>, to_class: impl Fn(&str) -> String) -> Vec<String> {
+    match table.get(key) {
+        Some(Item::Value(Value::String(_))) => {
+            let raw = item_to_string(table, key, "", value);
+            vec![to_class(&raw)]
+        }
+        Some(Item::Value(Value::Integer(n))) => vec![to_class(&n.value().to_string())],
+        Some(Item::Value(Value::Float(x))) => vec![to_class(&x.value().to_string())],
+        Some(item) => match item.as_table_like() {
+            Some(breakpoints) => {
+                let text = |item: &Item| match item.as_value() {
+                    Some(Value::Integer(n)) => Some(n.value().to_string()),
+                    Some(Value::Float(x)) => Some(x.value().to_string()),
+                    Some(Value::String(s)) => Some(s.value().to_string()),
+                    _ => None,
+                };
+                let mut classes = vec![];
+                if let Some(base) = breakpoints.get(BASE_BREAKPOINT).and_then(text) {
+                    classes.push(to_class(&base));
+                }
+                for bp in BREAKPOINTS {
+                    if let Some(raw) = breakpoints.get(bp).and_then(text) {
+                        classes.push(format!("{}:{}", bp, to_class(&raw)));
+                    }
+                }
+                classes
+            }
+            None => vec![],
+        },
+        None => vec![],
+    }
+}
+
+/// value의 key로 breakpoint classes를 만든다. values나 frontmatter 항목에 쓴다.
+pub fn breakpoint_classes_in_value(value: &InlineTable, key: &str, class: &str) -> Vec<String> {
+    breakpoint_classes(&value.clone().into_table(), key, class, None)
+}
+
+/// ### responsive classes
+/// width, height가 breakpoint table일 때의 class와 hidden의 class
+///
+/// width = { base = "100%", md = "50%" } -> ["w-[100%]", "md:w-[50%]"]
+///
+/// 문자열 width, height는 지금처럼 각 view가 처리한다.
+pub fn responsive_classes(table: &Table, value: Option<&InlineTable>) -> Vec<String> {
+    let mut classes = size_classes(table, "width", "w");
+    classes.extend(size_classes(table, "height", "h"));
+    classes.extend(hidden_classes(table, value));
+    classes
+}
+
+fn size_classes(table: &Table, key: &str, prefix: &str) -> Vec<String> {
+    let breakpoints = match table.get(key).and_then(|item| item.as_table_like()) {
+        Some(breakpoints) => breakpoints,
+        None => return vec![],
+    };
+    let size_class = |raw: &str, is_base: bool| {
+        if raw.starts_with(&format!("{}-", prefix)) {
+            Some(raw.to_string())
+        } else if raw == "wrap" {
+            // 기본값 wrap은 class가 없고, 중간에 wrap으로 돌아갈 때는 auto
+            (!is_base).then(|| format!("{}-auto", prefix))
+        } else {
+            Some(format!("{}-[{}]", prefix, raw))
+        }
+    };
+    let mut classes = vec![];
+    if let Some(base) = breakpoints.get(BASE_BREAKPOINT).and_then(|x| x.as_str()) {
+        classes.extend(size_class(base, true));
+    }
+    for bp in BREAKPOINTS {
+        if let Some(raw) = breakpoints.get(bp).and_then(|x| x.as_str()) {
+            classes.extend(size_class(raw, false).map(|class| format!("{}:{}", bp, class)));
+        }
+    }
+    classes
+}
+
+/// hidden = true 는 항상 숨기고, hidden = ["sm"] 은 그 breakpoint부터 다음 breakpoint 전까지 숨긴다.
+///
+/// base는 sm보다 작은 화면
+fn hidden_classes(table: &Table, value: Option<&InlineTable>) -> Vec<String> {
+    let names: Vec<String> = match table.get("hidden").and_then(|item| item.as_value()) {
+        Some(Value::Boolean(hidden)) => return if *hidden.value() { vec!["hidden".to_string()] } else { vec![] },
+        Some(Value::String(_)) => vec![item_to_string(table, "hidden", "", value)],
+        Some(Value::Array(names)) => names.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect(),
+        _ => vec![],
+    };
+    names
+        .iter()
+        .filter_map(|name| {
+            let name = name.trim();
+            if name == BASE_BREAKPOINT {
+                return Some(format!("max-{}:hidden", BREAKPOINTS[0]));
+            }
+            let index = BREAKPOINTS.iter().position(|bp| *bp == name)?;
+            Some(match BREAKPOINTS.get(index + 1) {
+                Some(next) => format!("{}:max-{}:hidden", name, next),
+                None => format!("{}:hidden", name),
+            })
+        })
+        .collect()
+}
+
+/// ### direction classes
+/// direction = "row" 또는 { base = "column", md = "row" } 를 flex 방향 class로 바꾼다.
+///
+/// direction이 없으면 shape의 기본 방향을 쓴다.
+pub fn direction_classes(table: &Table, value: Option<&InlineTable>, default: &str) -> Vec<String> {
+    let direction_class = |raw: &str| match raw.trim() {
+        "row" => "flex-row",
+        "row_reverse" => "flex-row-reverse",
+        "column_reverse" => "flex-col-reverse",
+        _ => "flex-col",
+    };
+    match table.get("direction") {
+        Some(item) if item.is_str() => {
+            vec![direction_class(&item_to_string(table, "direction", default, value)).to_string()]
+        }
+        Some(item) => match item.as_table_like() {
+            Some(breakpoints) => {
+                let base = breakpoints
+                    .get(BASE_BREAKPOINT)
+                    .and_then(|x| x.as_str())
+                    .unwrap_or(default);
+                let mut classes = vec![direction_class(base).to_string()];
+                for bp in BREAKPOINTS {
+                    if let Some(raw) = breakpoints.get(bp).and_then(|x| x.as_str()) {
+                        classes.push(format!("{}:{}", bp, direction_class(raw)));
+                    }
+                }
+                classes
+            }
+            None => vec![direction_class(default).to_string()],
+        },
+        None => vec![direction_class(default).to_string()],
+    }
+}
+
+/// 2 -> grid-cols-2, full -> col-span-full, "200px 1fr" -> grid-cols-[200px_1fr]
+pub fn class_with_value(class: &str, raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.parse::<u32>().is_ok() || ["full", "none", "auto", "subgrid"].contains(&raw) {
+        format!("{}-{}", class, raw)
+    } else {
+        format!("{}-[{}]", class, raw.replace(' ', "_"))
+    }
+}
@@ -0,0 +1,612 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use glob::Pattern;
+use regex::Regex;
+use toml_edit::{Item, Table, Value};
+
+use crate::error::{locate_key, Diagnostic};
+use crate::file::find_files;
+use crate::layout::style::{is_side, BORDER_KEYS, MAX_WIDTHS, SHADOWS, TEXT_TRANSFORMS};
+use crate::layout::common::{read_document, SITE_TABLE};
+use crate::layout::component::{component_params, is_component, param_value};
+use crate::layout::placeholder::check_placeholders;
+use crate::layout::query::{check_condition, Filter};
+use crate::layout::registry::is_registered;
+use crate::layout::responsive::{BASE_BREAKPOINT, BREAKPOINTS};
+use crate::markdown::parser::{MarkdownOverride, MARKDOWN_ELEMENTS, MARKDOWN_TABLE};
+use crate::theme::{is_color_token, FONT_TOKENS, THEME_TABLE};
+
+/// layout이 아닌 최상위 table
+pub const RESERVED_TABLES: [&str; 3] = [SITE_TABLE, THEME_TABLE, MARKDOWN_TABLE];
+
+/// key 값이 가져야 하는 모양
+#[derive(Clone, Copy)]
+pub enum Format {
+    /// "wrap", 길이, 또는 w-, h- 로 시작하는 tailwind class
+    Size,
+    /// px, %, rem 같은 단위가 붙은 길이
+    Length,
+    /// 길이, 또는 4 처럼 tailwind 간격 단계
+    Spacing,
+    /// 0부터 100까지의 정수(%) 또는 0부터 1까지의 실수
+    Opacity,
+    /// 정수 또는 "auto"
+    ZIndex,
+    /// 1.5 같은 배수나 길이
+    LineHeight,
+    /// 길이 또는 prose, lg 같은 tailwind 크기 이름
+    MaxWidth,
+    /// 길이 또는 "full", "none"
+    Radius,
+    /// 값 하나, breakpoint table, 또는 { x = .., top = .. } 처럼 방향별로 나눈 값
+    Sides(&'static Format),
+    /// 길이, 또는 { width = .., color = .., radius = .. }
+    Border,
+    Color,
+    Bool,
+    /// 0 이상의 정수
+    Integer,
+    Text,
+    /// 문자열 배열
+    Strings,
+    /// 문자열 하나 또는 문자열 배열
+    Keys,
+    /// tags contains "rust" 같은 조건 하나 또는 그 배열
+    Filters,
+    /// glob 패턴 하나 또는 그 배열, "!"로 시작하면 빼는 패턴
+    Globs,
+    /// "image" 같은 key 하나 또는 tags contains "rust" 같은 조건
+    Condition,
+    /// inline table 배열
+    Values,
+    /// inline table
+    Table,
+    /// 3 이나 "200px 1fr", 또는 { sm = 1, md = 2 } 처럼 breakpoint별 값
+    Breakpoints,
+    /// 값 하나, 또는 { base = .., md = .. } 처럼 breakpoint별로 나눈 값
+    Responsive(&'static Format),
+    /// true, 또는 숨길 breakpoint 이름 배열
+    Hidden,
+    OneOf(&'static [&'static str]),
+}
+
+/// shape 하나가 받을 수 있는 key들
+pub struct ShapeSchema {
+    pub keys: Vec<(&'static str, Format)>,
+    /// 하위 layout을 가질 수 있는지
+    pub children: bool,
+    /// nav 메뉴처럼 정해지지 않은 key를 받는지
+    pub free_keys: bool,
+}
+
+const COMMON_KEYS: [(&str, Format); 25] = [
+    ("shape", Format::Text),
+    ("if", Format::Condition),
+    ("unless", Format::Condition),
+    ("width", Format::Responsive(&Format::Size)),
+    ("height", Format::Responsive(&Format::Size)),
+    ("hidden", Format::Hidden),
+    ("background", Format::Color),
+    ("path", Format::Text),
+    ("value", Format::Table),
+    ("dark", Format::Bool),
+    ("custom_class", Format::Text),
+    ("align_absolute", Format::Text),
+    ("id", Format::Text),
+    ("margin", Format::Sides(&Format::Spacing)),
+    ("padding", Format::Sides(&Format::Spacing)),
+    ("border", Format::Border),
+    ("shadow", Format::OneOf(&SHADOWS)),
+    ("opacity", Format::Opacity),
+    ("z_index", Format::ZIndex),
+    ("line_height", Format::Responsive(&Format::LineHeight)),
+    ("letter_spacing", Format::Responsive(&Format::Length)),
+    ("max_width", Format::Responsive(&Format::MaxWidth)),
+    ("text_transform", Format::OneOf(&TEXT_TRANSFORMS)),
+    // grid 안에서 차지하는 칸 수
+    ("span", Format::Breakpoints),
+    ("row_span", Format::Breakpoints),
+];
+
+/// [root]만 받는 head key, title, description, image, lang, canonical과 head, body에 더하는 것들
+const ROOT_KEYS: [(&str, Format); 9] = [
+    ("title", Format::Text),
+    ("description", Format::Text),
+    ("image", Format::Text),
+    ("lang", Format::Text),
+    ("canonical", Format::Text),
+    ("head_extra", Format::Text),
+    ("body_end", Format::Text),
+    ("extra_css", Format::Strings),
+    ("extra_js", Format::Strings),
+];
+
+/// flex 방향을 바꿀 수 있는 shape의 key
+const DIRECTION_KEY: (&str, Format) = ("direction", Format::Responsive(&Format::OneOf(&["row", "column", "row_reverse", "column_reverse"])));
+
+const GRID_KEYS: [(&str, Format); 5] = [
+    ("layout", Format::Text),
+    ("columns", Format::Breakpoints),
+    ("gap", Format::Length),
+    ("row_gap", Format::Length),
+    ("column_gap", Format::Length),
+];
+
+const CONTAINER_KEYS: [(&str, Format); 3] = [
+    ("inner_padding", Format::Length),
+    ("align_subs", Format::OneOf(&["start", "center", "end", "baseline", "stretch"])),
+    ("fixed", Format::OneOf(&["top", "bottom", "left", "right"])),
+];
+
+const TEXT_KEYS: [(&str, Format); 8] = [
+    ("size", Format::Length),
+    ("text", Format::Text),
+    ("color", Format::Color),
+    ("family", Format::Text),
+    ("weight", Format::OneOf(&["thin", "extralight", "light", "normal", "medium", "semibold", "bold", "extrabold", "black"])),
+    ("underline", Format::Bool),
+    ("horizontal_align", Format::OneOf(&["left", "center", "right"])),
+    ("vertical_align", Format::OneOf(&["top", "center", "bottom"])),
+];
+
+const IMAGE_KEYS: [(&str, Format); 3] = [
+    ("image_path", Format::Text),
+    ("content_size", Format::OneOf(&["cover", "contain", "fill", "none", "scale-down"])),
+    ("rounded", Format::Length),
+];
+
+/// list_*, mdlist_*, mdgrid가 항목을 고르고 늘어놓는 key들
+const QUERY_KEYS: [(&str, Format); 4] = [
+    ("order_by", Format::Keys),
+    ("filter", Format::Filters),
+    ("limit", Format::Integer),
+    ("offset", Format::Integer),
+];
+
+/// list_*, mdlist_*를 쪽으로 나누는 key들
+const PAGINATION_KEYS: [(&str, Format); 2] = [
+    ("page_size", Format::Integer),
+    ("pagination", Format::Text),
+];
+
+pub fn shape_schema(shape: &str) -> Option<ShapeSchema> {
+    let mut keys: Vec<(&'static str, Format)> = COMMON_KEYS.to_vec();
+    let (children, free_keys) = match shape {
+        "column" | "row" | "scroll_column" | "scroll_row" => {
+            keys.extend(CONTAINER_KEYS);
+            keys.push(DIRECTION_KEY);
+            (true, false)
+        }
+        "box" => {
+            keys.extend(CONTAINER_KEYS);
+            (true, false)
+        }
+        "text" => {
+            keys.extend(TEXT_KEYS);
+            (false, false)
+        }
+        "image" => {
+            keys.extend(IMAGE_KEYS);
+            (false, false)
+        }
+        "nav" => {
+            keys.extend([("title", Format::Text), ("headers", Format::Strings)]);
+            (false, true)
+        }
+        "search" => {
+            keys.extend([("placeholder", Format::Text), ("limit", Format::Integer)]);
+            (false, false)
+        }
+        "list_column" | "list_row" => {
+            keys.extend(CONTAINER_KEYS);
+            keys.push(DIRECTION_KEY);
+            keys.extend([("layout", Format::Text), ("values", Format::Values), ("data", Format::Text)]);
+            keys.extend(QUERY_KEYS);
+            keys.extend(PAGINATION_KEYS);
+            (false, false)
+        }
+        "mdlist_column" | "mdlist_row" => {
+            keys.extend(CONTAINER_KEYS);
+            keys.push(DIRECTION_KEY);
+            keys.extend([("layout", Format::Text), ("files", Format::Globs)]);
+            keys.extend(QUERY_KEYS);
+            keys.extend([("group_by", Format::Text), ("group_layout", Format::Text)]);
+            keys.extend(PAGINATION_KEYS);
+            (false, false)
+        }
+        "markdown" => {
+            keys.extend(CONTAINER_KEYS);
+            keys.push(("markdown_path", Format::Text));
+            (false, false)
+        }
+        "grid" => {
+            keys.extend(CONTAINER_KEYS);
+            keys.extend(GRID_KEYS);
+            keys.extend([("values", Format::Values), ("row_count", Format::Breakpoints)]);
+            (true, false)
+        }
+        "mdgrid" => {
+            keys.extend(CONTAINER_KEYS);
+            keys.extend(GRID_KEYS);
+            keys.push(("files", Format::Globs));
+            keys.extend(QUERY_KEYS);
+            (true, false)
+        }
+        "embed" => {
+            keys.extend(CONTAINER_KEYS);
+            keys.push(("layout", Format::Text));
+            (false, false)
+        }
+        _ => return None,
+    };
+    Some(ShapeSchema { keys, children, free_keys })
+}
+
+/// index.toml의 모든 layout table을 shape별 schema로 검사한다.
+///
+/// layout_tables는 상위 폴더에서 물려받은 layout들로, component를 찾는 데 쓴다.
+pub fn validate_toml(index_path: &Path, layout_tables: &HashMap<String, Table>) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let doc = match read_document(index_path) {
+        Ok(doc) => doc,
+        Err(error) => {
+            diagnostics.push(Diagnostic {
+                location: error.location(),
+                ..Diagnostic::error(index_path, "", error.to_string())
+            });
+            return diagnostics;
+        }
+    };
+
+    let mut layout_tables = layout_tables.clone();
+    for (key, item) in doc.as_table() {
+        if let Item::Table(table) = item {
+            layout_tables.insert(key.to_string(), table.clone());
+        }
+    }
+    for (key, item) in doc.as_table() {
+        if let Item::Table(table) = item {
+            if key == THEME_TABLE {
+                validate_theme(index_path, table, &mut diagnostics);
+            } else if key == MARKDOWN_TABLE {
+                validate_markdown(index_path, table, &layout_tables, &mut diagnostics);
+            } else if !RESERVED_TABLES.contains(&key) {
+                validate_table(index_path, key, table, &layout_tables, &mut diagnostics);
+            }
+        }
+    }
+
+    let source = std::fs::read_to_string(index_path).unwrap_or_default();
+    for diagnostic in &mut diagnostics {
+        diagnostic.location = locate_key(&source, &diagnostic.key);
+    }
+    diagnostics
+}
+
+/// [theme]의 색 token은 색, radius는 길이, 그 밖의 key는 {theme.*} 로만 쓰이므로 검사하지 않는다.
+fn validate_theme(index_path: &Path, table: &Table, diagnostics: &mut Vec<Diagnostic>) {
+    for (key, item) in table.iter() {
+        let format = if is_color_token(key) {
+            Format::Color
+        } else if key == "radius" {
+            Format::Radius
+        } else if FONT_TOKENS.contains(&key) {
+            Format::Text
+        } else {
+            continue;
+        };
+        if let Err(message) = validate_value(format, item) {
+            diagnostics.push(Diagnostic::error(index_path, &format!("{}.{}", THEME_TABLE, key), message));
+        }
+    }
+}
+
+/// [markdown]의 key는 node 종류, 값은 class 문자열이나 있는 layout
+fn validate_markdown(index_path: &Path, table: &Table, layout_tables: &HashMap<String, Table>, diagnostics: &mut Vec<Diagnostic>) {
+    for (key, item) in table.iter() {
+        let key_path = format!("{}.{}", MARKDOWN_TABLE, key);
+        if !MARKDOWN_ELEMENTS.contains(&key) {
+            diagnostics.push(Diagnostic::warning(index_path, &key_path, format!("unknown markdown element \"{}\", expected one of {}", key, MARKDOWN_ELEMENTS.join(", "))));
+            continue;
+        }
+        match MarkdownOverride::from_item(item) {
+            Some(MarkdownOverride::Class(_)) => {}
+            Some(MarkdownOverride::Layout(layout)) => {
+                if !layout_tables.contains_key(&layout) {
+                    diagnostics.push(Diagnostic::error(index_path, &key_path, format!("unknown layout \"{}\"", layout)));
+                }
+            }
+            None => diagnostics.push(Diagnostic::error(index_path, &key_path, "expected a class string or a table like { layout = \"name\" }")),
+        }
+    }
+}
+
+fn validate_table(index_path: &Path, key_path: &str, table: &Table, layout_tables: &HashMap<String, Table>, diagnostics: &mut Vec<Diagnostic>) {
+    let shape = match table.get("shape") {
+        None => {
+            diagnostics.push(Diagnostic::error(index_path, key_path, "missing shape"));
+            return;
+        }
+        Some(item) => match item.as_str() {
+            Some(shape) => shape,
+            None => {
+                diagnostics.push(Diagnostic::error(index_path, &format!("{}.shape", key_path), "shape must be a string"));
+                return;
+            }
+        },
+    };
+    // {shape} 처럼 값으로 채워지는 shape는 미리 알 수 없다.
+    if has_placeholder(shape) {
+        return;
+    }
+
+    if is_component(table) {
+        if let Err(message) = component_params(table) {
+            diagnostics.push(Diagnostic::error(index_path, &format!("{}.params", key_path), message));
+        }
+    }
+
+    let mut params = vec![];
+    let mut schema = match shape_schema(shape) {
+        Some(schema) => schema,
+        None => match layout_tables.get(shape).filter(|x| is_component(x)) {
+            Some(component) => {
+                let root_shape = component.get("shape").and_then(|x| x.as_str()).unwrap_or("");
+                params = component_params(component).unwrap_or_default();
+                match shape_schema(root_shape) {
+                    Some(schema) => schema,
+                    None => return,
+                }
+            }
+            // 등록된 사용자 shape는 schema를 알 수 없다.
+            None if is_registered(shape) => return,
+            None => {
+                diagnostics.push(Diagnostic::error(index_path, key_path, format!("unknown shape \"{}\"", shape)));
+                return;
+            }
+        },
+    };
+
+    if key_path == "root" {
+        schema.keys.extend(ROOT_KEYS);
+    }
+
+    for param in &params {
+        let raw = table.get(&param.name).and_then(|item| item.as_value());
+        if raw.and_then(|x| x.as_str()).is_some_and(has_placeholder) {
+            continue;
+        }
+        if let Err(message) = param_value(param, raw) {
+            let key = if raw.is_some() { format!("{}.{}", key_path, param.name) } else { key_path.to_string() };
+            diagnostics.push(Diagnostic::error(index_path, &key, format!("{} for component \"{}\"", message, shape)));
+        }
+    }
+
+    for (key, item) in table.iter() {
+        if key == "params" || params.iter().any(|param| param.name == key) {
+            continue;
+        }
+        let key_path = format!("{}.{}", key_path, key);
+        if let Item::Table(sub_table) = item {
+            if schema.children {
+                validate_table(index_path, &key_path, sub_table, layout_tables, diagnostics);
+            } else if !schema.free_keys {
+                diagnostics.push(Diagnostic::warning(index_path, &key_path, format!("sub layouts are not supported in {}", shape)));
+            }
+            continue;
+        }
+
+        match schema.keys.iter().find(|(k, _)| *k == key) {
+            Some((_, format)) => {
+                let checked = validate_value(*format, item).and_then(|_| match format {
+                    Format::Globs => check_files(index_path, item),
+                    _ => Ok(()),
+                });
+                if let Err(message) = checked {
+                    diagnostics.push(Diagnostic::error(index_path, &key_path, message));
+                }
+            }
+            None if schema.free_keys => {}
+            // aria-label 처럼 그대로 html 속성이 되는 key
+            None if key.starts_with("aria-") => {}
+            None => {
+                let mut message = format!("unknown key \"{}\" for shape \"{}\"", key, shape);
+                if let Some(similar) = similar_key(key, &schema.keys) {
+                    message.push_str(&format!(", did you mean \"{}\"?", similar));
+                }
+                diagnostics.push(Diagnostic::warning(index_path, &key_path, message));
+            }
+        }
+    }
+}
+
+/// files 패턴으로 찾는 중 읽을 수 없는 폴더가 있는지, {..} 로 채워지는 패턴은 미리 알 수 없다.
+fn check_files(index_path: &Path, item: &Item) -> Result<(), String> {
+    let patterns: Vec<String> = match item.as_array() {
+        Some(arr) => arr.iter().filter_map(|v| v.as_str()).map(|x| x.to_string()).collect(),
+        None => item.as_str().into_iter().map(|x| x.to_string()).collect(),
+    };
+    if patterns.iter().any(|x| has_placeholder(x)) {
+        return Ok(());
+    }
+    let folder = index_path.parent().unwrap_or(index_path);
+    find_files(&patterns, folder).map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn validate_value(format: Format, item: &Item) -> Result<(), String> {
+    let value = match item.as_value() {
+        Some(value) => value,
+        None => return Err("expected a value".to_string()),
+    };
+    if let Some(text) = value.as_str().filter(|x| has_placeholder(x)) {
+        return check_placeholders(text);
+    }
+    if let Format::Sides(inner) = format {
+        if let Some(sides) = value.as_inline_table().filter(|t| t.iter().any(|(k, _)| is_side(k))) {
+            return sides.iter().try_for_each(|(k, v)| {
+                if !is_side(k) {
+                    return Err(format!("unknown side \"{}\", expected one of x, y, top, bottom, left, right", k));
+                }
+                validate_value(Format::Responsive(inner), &Item::Value(v.clone())).map_err(|message| format!("{}: {}", k, message))
+            });
+        }
+        return validate_value(Format::Responsive(inner), item);
+    }
+    if let Format::Border = format {
+        if let Some(border) = value.as_inline_table().filter(|t| t.iter().any(|(k, _)| BORDER_KEYS.contains(&k))) {
+            return border.iter().try_for_each(|(k, v)| {
+                let inner = match k {
+                    "width" => &Format::Length,
+                    "color" => &Format::Color,
+                    "radius" => &Format::Radius,
+                    _ => return Err(format!("unknown border key \"{}\", expected one of {}", k, BORDER_KEYS.join(", "))),
+                };
+                validate_value(Format::Responsive(inner), &Item::Value(v.clone())).map_err(|message| format!("{}: {}", k, message))
+            });
+        }
+        return validate_value(Format::Responsive(&Format::Length), item);
+    }
+    if let Format::Filters = format {
+        let expressions: Vec<&str> = match value.as_array() {
+            Some(arr) => arr.iter().filter_map(|v| v.as_str()).collect(),
+            None => value.as_str().into_iter().collect(),
+        };
+        if expressions.is_empty() {
+            return Err(format!("invalid filter {}, expected a string or an array of strings", value.to_string().trim()));
+        }
+        return expressions.iter().try_for_each(|x| Filter::parse(x).map(|_| ()));
+    }
+    if let Format::Condition = format {
+        return match value.as_str() {
+            Some(expression) => check_condition(expression),
+            None => Err(format!("invalid condition {}, expected a key like \"image\" or an expression like tags contains \"rust\"", value.to_string().trim())),
+        };
+    }
+    if let Format::Globs = format {
+        let patterns: Vec<&str> = match value.as_array() {
+            Some(arr) => arr.iter().filter_map(|v| v.as_str()).collect(),
+            None => value.as_str().into_iter().collect(),
+        };
+        if patterns.is_empty() {
+            return Err(format!("invalid files {}, expected a glob pattern or an array of them", value.to_string().trim()));
+        }
+        return patterns.iter().try_for_each(|x| {
+            Pattern::new(x.trim_start_matches('!'))
+                .map(|_| ())
+                .map_err(|e| format!("invalid glob pattern \"{}\", {}", x, e))
+        });
+    }
+    if let Format::Responsive(inner) = format {
+        return match value.as_inline_table() {
+            Some(breakpoints) => breakpoints.iter().try_for_each(|(k, v)| {
+                if k != BASE_BREAKPOINT && !BREAKPOINTS.contains(&k) {
+                    return Err(format!("unknown breakpoint \"{}\", expected one of {}, {}", k, BASE_BREAKPOINT, BREAKPOINTS.join(", ")));
+                }
+                validate_value(*inner, &Item::Value(v.clone())).map_err(|message| format!("{}: {}", k, message))
+            }),
+            None => validate_value(*inner, item),
+        };
+    }
+    let valid = match format {
+        Format::Size => value.as_str().is_some_and(|s| {
+            s == "wrap" || s.starts_with("w-") || s.starts_with("h-") || is_length(s)
+        }),
+        Format::Length => value.as_str().is_some_and(is_length),
+        Format::Spacing => value.as_integer().is_some_and(|n| n >= 0) || value.as_str().is_some_and(is_length),
+        Format::Opacity => value.as_integer().is_some_and(|n| (0..=100).contains(&n)) || value.as_float().is_some_and(|x| (0.0..=1.0).contains(&x)),
+        Format::ZIndex => value.as_integer().is_some() || value.as_str().is_some_and(|s| s == "auto"),
+        Format::LineHeight => value.as_float().is_some_and(|x| x > 0.0) || value.as_integer().is_some_and(|n| n > 0) || value.as_str().is_some_and(|s| is_length(s) || s.parse::<f64>().is_ok()),
+        Format::MaxWidth => value.as_str().is_some_and(|s| is_length(s) || MAX_WIDTHS.contains(&s)),
+        Format::Radius => value.as_str().is_some_and(|s| is_length(s) || s == "full" || s == "none"),
+        Format::Color => value.as_str().is_some_and(is_color),
+        Format::Bool => value.as_bool().is_some(),
+        Format::Integer => value.as_integer().is_some_and(|x| x >= 0),
+        Format::Text => value.as_str().is_some(),
+        Format::Strings => value.as_array().is_some_and(|arr| arr.iter().all(|v| v.as_str().is_some())),
+        Format::Keys => value.as_str().is_some() || value.as_array().is_some_and(|arr| arr.iter().all(|v| v.as_str().is_some())),
+        Format::Values => value.as_array().is_some_and(|arr| arr.iter().all(|v| matches!(v, Value::InlineTable(_)))),
+        Format::Table => value.as_inline_table().is_some(),
+        Format::OneOf(options) => value.as_str().is_some_and(|s| options.contains(&s)),
+        Format::Breakpoints => match value.as_inline_table() {
+            Some(breakpoints) => breakpoints.iter().all(|(k, v)| {
+                (k == BASE_BREAKPOINT || BREAKPOINTS.contains(&k)) && (v.as_integer().is_some() || v.as_str().is_some())
+            }),
+            None => value.as_integer().is_some() || value.as_str().is_some(),
+        },
+        Format::Hidden => value.as_bool().is_some() || value.as_str().is_some_and(|s| s == BASE_BREAKPOINT || BREAKPOINTS.contains(&s)) || value.as_array().is_some_and(|arr| {
+            arr.iter().all(|v| v.as_str().is_some_and(|s| s == BASE_BREAKPOINT || BREAKPOINTS.contains(&s)))
+        }),
+        Format::Responsive(_) | Format::Sides(_) | Format::Border | Format::Filters | Format::Globs | Format::Condition => true,
+    };
+    if valid {
+        return Ok(());
+    }
+    let value = value.to_string().trim().to_string();
+    Err(match format {
+        Format::Size => format!("invalid size {}, expected \"wrap\", a length like \"100px\" or \"50%\", or a w-/h- class", value),
+        Format::Length => format!("invalid length {}, expected a length like \"10px\"", value),
+        Format::Spacing => format!("invalid spacing {}, expected a length like \"10px\" or a number like 4", value),
+        Format::Opacity => format!("invalid opacity {}, expected 0 to 100 or 0.0 to 1.0", value),
+        Format::ZIndex => format!("invalid z_index {}, expected a number or \"auto\"", value),
+        Format::LineHeight => format!("invalid line height {}, expected a number like 1.5 or a length like \"24px\"", value),
+        Format::MaxWidth => format!("invalid max width {}, expected a length or one of {}", value, MAX_WIDTHS.join(", ")),
+        Format::Radius => format!("invalid radius {}, expected a length, \"full\" or \"none\"", value),
+        Format::Color => format!("invalid color {}, expected a color like \"#fff\"", value),
+        Format::Bool => format!("invalid value {}, expected true or false", value),
+        Format::Integer => format!("invalid value {}, expected a whole number", value),
+        Format::Text => format!("invalid value {}, expected a string", value),
+        Format::Strings => format!("invalid value {}, expected an array of strings", value),
+        Format::Keys => format!("invalid value {}, expected a string or an array of strings", value),
+        Format::Values => format!("invalid value {}, expected an array of inline tables", value),
+        Format::Table => format!("invalid value {}, expected an inline table", value),
+        Format::OneOf(options) => format!("invalid value {}, expected one of {}", value, options.join(", ")),
+        Format::Breakpoints => format!("invalid value {}, expected a number or a table like {{ sm = 1, md = 2, lg = 4 }}", value),
+        Format::Hidden => format!("invalid value {}, expected true or an array of {}, {}", value, BASE_BREAKPOINT, BREAKPOINTS.join(", ")),
+        Format::Responsive(_) | Format::Sides(_) | Format::Border | Format::Filters | Format::Globs | Format::Condition => format!("invalid value {}", value),
+    })
+}
+
+fn has_placeholder(s: &str) -> bool {
+    s.contains('{')
+}
+
+static LENGTH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(-?\d+(\.\d+)?(px|%|rem|em|vw|vh|dvh|svh|lvh|ch|pt)|0|auto|calc\(.+\))$").unwrap()
+});
+
+static COLOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(#([0-9a-fA-F]{3,4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})|(rgb|rgba|hsl|hsla|var)\(.+\)|[a-zA-Z]+)$").unwrap()
+});
+
+pub fn is_length(s: &str) -> bool {
+    LENGTH_RE.is_match(s.trim())
+}
+
+pub fn is_color(s: &str) -> bool {
+    COLOR_RE.is_match(s.trim())
+}
+
+/// 오타로 보이는 key에 대해 가장 비슷한 key를 찾는다.
+fn similar_key(key: &str, keys: &[(&'static str, Format)]) -> Option<&'static str> {
+    keys.iter()
+        .map(|(k, _)| (*k, edit_distance(key, k)))
+        .filter(|(_, distance)| *distance <= 2)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(k, _)| k)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current.push((prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
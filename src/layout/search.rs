@@ -0,0 +1,60 @@
+use maplit::hashmap;
+use crate::asset::ASSETS_MARKER;
+use crate::html::{filter_attrs, HTMLView};
+use crate::search::{SEARCH_INDEX, SEARCH_MARKER, SEARCH_SCRIPT};
+use crate::theme::Theme;
+
+/// ### make search
+/// 검색 상자와 결과 목록, 결과 한 줄은 template으로 두고 search.js가 복사해 채운다.
+///
+/// 색인과 주소는 {assets} 기준이라 페이지 깊이와 상관없이 서버 없이도 찾아간다.
+pub fn make_search(placeholder: &str, limit: i64, is_dark: bool, theme: &Theme) -> Vec<HTMLView> {
+  let input = HTMLView::new("input", hashmap! {
+      "type".to_string() => "search".to_string(),
+      "placeholder".to_string() => placeholder.to_string(),
+      "aria-label".to_string() => placeholder.to_string(),
+      "autocomplete".to_string() => "off".to_string(),
+      "class".to_string() => filter_attrs(&format!(
+          "block w-full p-2 text-sm rounded-lg border focus:outline-none focus:ring-2 {} {} {} {}",
+          theme.color_class("text", "text", "text-gray-900 dark:text-white"),
+          theme.color_class("surface", "bg", "bg-gray-50 dark:bg-gray-700"),
+          theme.color_class("border", "border", "border-gray-300 dark:border-gray-600"),
+          theme.color_class("primary", "focus:ring", "focus:ring-blue-500"),
+      ), is_dark),
+  }, "", vec![]);
+
+  let results = HTMLView::new("ul", hashmap! {
+      "role".to_string() => "listbox".to_string(),
+      "class".to_string() => filter_attrs(&format!(
+          "absolute z-30 hidden w-full mt-1 overflow-y-auto max-h-96 rounded-lg border shadow-lg {} {}",
+          theme.color_class("surface", "bg", "bg-white dark:bg-gray-800"),
+          theme.color_class("border", "border", "border-gray-200 dark:border-gray-700"),
+      ), is_dark),
+  }, "", vec![]);
+
+  let title = HTMLView::new("span", hashmap! {
+      "data-title".to_string() => "".to_string(),
+      "class".to_string() => filter_attrs(&format!("block font-medium {}", theme.color_class("text", "text", "text-gray-900 dark:text-white")), is_dark),
+  }, "", vec![]);
+  let snippet = HTMLView::new("span", hashmap! {
+      "data-snippet".to_string() => "".to_string(),
+      "class".to_string() => filter_attrs(&format!("block text-sm truncate {}", theme.color_class("muted", "text", "text-gray-500 dark:text-gray-400")), is_dark),
+  }, "", vec![]);
+  let item = HTMLView::new("a", hashmap! {
+      "class".to_string() => filter_attrs("block px-4 py-2 hover:bg-gray-100 dark:hover:bg-gray-700", is_dark),
+  }, "", vec![title, snippet])
+      .wrap_tag("li", hashmap! {});
+  let template = HTMLView::new("template", hashmap! {}, "", vec![item]);
+
+  let script = HTMLView::new("script", hashmap! {
+      "src".to_string() => format!("{}/{}", ASSETS_MARKER, SEARCH_SCRIPT),
+  }, "", vec![]);
+
+  let box_view = HTMLView::new("div", hashmap! {
+      SEARCH_MARKER.to_string() => format!("{}/{}", ASSETS_MARKER, SEARCH_INDEX),
+      "data-prema-root".to_string() => format!("{}/../", ASSETS_MARKER),
+      "data-prema-limit".to_string() => limit.to_string(),
+      "class".to_string() => "relative w-full".to_string(),
+  }, "", vec![input, results, template]);
+  vec![box_view, script]
+}
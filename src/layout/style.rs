@@ -0,0 +1,103 @@
+use toml_edit::{InlineTable, Table, TableLike, Value};
+
+use super::common::item_to_string;
+use super::responsive::{breakpoint_classes, breakpoint_classes_with, class_with_value};
+
+/// 그림자 크기, shadow = "md" -> shadow-md
+pub const SHADOWS: [&str; 7] = ["none", "sm", "md", "lg", "xl", "2xl", "inner"];
+
+/// padding, margin을 방향별로 줄 때의 key와 tailwind 접미사
+pub const SIDES: [(&str, &str); 6] = [("x", "x"), ("y", "y"), ("top", "t"), ("bottom", "b"), ("left", "l"), ("right", "r")];
+
+/// border = { width = "1px", color = "#ddd", radius = "8px" }
+pub const BORDER_KEYS: [&str; 3] = ["width", "color", "radius"];
+
+/// max_width에 쓸 수 있는 tailwind 크기 이름
+pub const MAX_WIDTHS: [&str; 15] = ["none", "xs", "sm", "md", "lg", "xl", "2xl", "3xl", "4xl", "5xl", "6xl", "7xl", "full", "prose", "screen"];
+
+pub const TEXT_TRANSFORMS: [&str; 4] = ["uppercase", "lowercase", "capitalize", "none"];
+
+/// ### style classes
+/// padding, margin, border, shadow, opacity, z_index, line_height, letter_spacing, max_width, text_transform를 tailwind class로 바꾼다.
+///
+/// margin = 4 -> m-4, padding = { x = "16px", y = 2 } -> px-[16px] py-2, padding = { base = "8px", md = "16px" } -> p-[8px] md:p-[16px]
+pub fn style_classes(table: &Table, value: Option<&InlineTable>) -> Vec<String> {
+    let mut classes = spacing_classes(table, "margin", "m", value);
+    classes.extend(spacing_classes(table, "padding", "p", value));
+    classes.extend(border_classes(table, value));
+
+    let shadow = item_to_string(table, "shadow", "", value);
+    let shadow = shadow.trim();
+    if !shadow.is_empty() {
+        classes.push(format!("shadow-{}", shadow));
+    }
+    if let Some(opacity) = table.get("opacity").and_then(|x| x.as_value()) {
+        match opacity {
+            Value::Integer(n) => classes.push(format!("opacity-[{}%]", n.value())),
+            Value::Float(x) => classes.push(format!("opacity-[{}]", x.value())),
+            _ => {}
+        }
+    }
+    if let Some(z_index) = table.get("z_index").and_then(|x| x.as_value()) {
+        match z_index {
+            Value::Integer(n) => classes.push(format!("z-[{}]", n.value())),
+            Value::String(_) => classes.push(format!("z-{}", item_to_string(table, "z_index", "", value).trim())),
+            _ => {}
+        }
+    }
+
+    classes.extend(breakpoint_classes_with(table, "line_height", value, |raw| format!("leading-[{}]", raw.trim())));
+    classes.extend(breakpoint_classes_with(table, "letter_spacing", value, |raw| format!("tracking-[{}]", raw.trim())));
+    classes.extend(breakpoint_classes_with(table, "max_width", value, |raw| {
+        let raw = raw.trim();
+        if MAX_WIDTHS.contains(&raw) {
+            format!("max-w-{}", raw)
+        } else {
+            format!("max-w-[{}]", raw)
+        }
+    }));
+    let text_transform = item_to_string(table, "text_transform", "", value);
+    match text_transform.trim() {
+        "" => {}
+        "none" => classes.push("normal-case".to_string()),
+        transform => classes.push(transform.to_string()),
+    }
+    classes
+}
+
+/// 값 하나나 breakpoint table은 모든 방향, x, y, top, bottom, left, right table은 방향별
+fn spacing_classes(table: &Table, key: &str, prefix: &str, value: Option<&InlineTable>) -> Vec<String> {
+    let sides = match table.get(key).and_then(|item| item.as_table_like()) {
+        Some(sides) if sides.iter().any(|(k, _)| is_side(k)) => sides,
+        _ => return breakpoint_classes(table, key, prefix, value),
+    };
+    let side_table = to_table(sides);
+    SIDES
+        .iter()
+        .flat_map(|(side, suffix)| breakpoint_classes(&side_table, side, &format!("{}{}", prefix, suffix), value))
+        .collect()
+}
+
+pub fn is_side(key: &str) -> bool {
+    SIDES.iter().any(|(side, _)| *side == key)
+}
+
+/// border = "1px" 또는 { width = "1px", color = "#ddd", radius = "8px" }
+fn border_classes(table: &Table, value: Option<&InlineTable>) -> Vec<String> {
+    let border = match table.get("border").and_then(|item| item.as_table_like()) {
+        Some(border) if border.iter().any(|(k, _)| BORDER_KEYS.contains(&k)) => to_table(border),
+        _ => return breakpoint_classes(table, "border", "border", value),
+    };
+    let mut classes = breakpoint_classes(&border, "width", "border", value);
+    classes.extend(breakpoint_classes_with(&border, "color", value, |raw| format!("border-[{}]", raw.trim())));
+    classes.extend(breakpoint_classes_with(&border, "radius", value, |raw| class_with_value("rounded", raw)));
+    classes
+}
+
+fn to_table(table_like: &dyn TableLike) -> Table {
+    let mut table = Table::new();
+    for (k, item) in table_like.iter() {
+        table.insert(k, item.clone());
+    }
+    table
+}
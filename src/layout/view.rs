@@ -1,23 +1,28 @@
 use crate::file::find_files;
-use crate::html::{filter_attrs, HTMLView};
+use crate::html::HTMLView;
 use crate::layout::nav::make_nav;
-use crate::markdown::{markdown_to_htmlview, metas_table_from_markdown};
-use crate::page::Page;
+use crate::layout::pagination::{page_depth, paginate, Pagination};
+use crate::data::{lookup_data, DATA_KEY};
+use crate::layout::query::{group_name, item_to_keys, query_metas};
+use crate::layout::search::make_search;
+use crate::markdown::{computed_metas, is_draft, markdown_to_htmlview, metas_from_markdown};
+use crate::markdown::parser::node_from_markdown;
+use crate::theme::Theme;
 use std::collections::HashMap;
 use std::fmt::{self, Formatter};
 use std::path::{Path, PathBuf};
-use chrono::{DateTime, Utc};
-use dateparser::parse;
 use toml_edit::InlineTable;
-use toml_edit::{value, DocumentMut, Item, Table, Value};
+use toml_edit::{Item, Table, Value};
 
 use std::any::Any;
+use maplit::hashmap;
 
-use super::common::{get_items, item_to_bool, layout_to_tomlview};
-use super::common::item_to_string;
+use super::base::{common_accessors, ViewCommon};
+use super::common::{get_items, include_drafts, item_to_bool, layout_to_tomlview};
+use super::common::{item_to_integer, item_to_string};
 use super::common::item_to_strings;
 use super::common::table_to_tomlview;
-use super::padding::PaddingType;
+use super::responsive::{breakpoint_classes, breakpoint_classes_in_value, direction_classes};
 
 impl fmt::Display for dyn TOMLView {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -49,130 +54,54 @@ pub trait TOMLView: Any {
     fn dark(&self) -> bool;
     fn views(&self) -> &Vec<Box<dyn TOMLView>>;
     fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView;
+    /// 상위 view의 key들까지 붙인 key path, 나뉜 목록의 쪽 번호를 찾는 데 쓴다.
+    fn key_path(&self) -> String {
+        self.key()
+    }
+    /// page_size로 나뉜 목록이면 그 쪽 정보
+    fn pagination(&self) -> Option<Pagination> {
+        None
+    }
 }
 
-pub struct ColumnView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
+/// 하위 table을 view로 만든다. 만들 수 없는 table은 건너뛴다.
+fn sub_views(index_path: &Path, table: &Table, super_view: &dyn TOMLView, layout_tables: &HashMap<String, Table>) -> Vec<Box<dyn TOMLView>> {
+    let value = super_view.value();
+    table
+        .iter()
+        .filter_map(|(k, v)| match v {
+            Item::Table(inner_table) => table_to_tomlview(index_path, k, inner_table, value.as_ref(), Some(super_view), layout_tables).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// ### flex
+/// column, row, list처럼 하위 view를 한 방향으로 늘어놓는 shape의 key들
+struct Flex {
     is_scroll: bool,
+    /// 스크롤 방향, column은 y, row는 x
+    scroll_class: &'static str,
     inner_padding: String,
-    align_absolute: String,
     align_subs: String,
     fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
-    views: Vec<Box<dyn TOMLView>>,
+    direction: Vec<String>,
 }
 
-impl ColumnView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> ColumnView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-        
-        let mut view = ColumnView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
+impl Flex {
+    fn new(table: &Table, value: Option<&InlineTable>, is_scroll: bool, default_direction: &str) -> Flex {
+        Flex {
             is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            align_subs: item_to_string(&table, "align_subs", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
-            views: vec![],
-        };
-
-        let views = table
-            .iter()
-            .filter_map(|(k, v)| {
-                if let Item::Table(inner_table) = v {
-                    match table_to_tomlview(index_path, k, inner_table, value, Some(&view), layout_tables.clone()) {
-                        Ok(view) => Some(view),
-                        Err(_) => None,
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect();
-        view.views = views;
-        view
-    }
-}
-
-impl TOMLView for ColumnView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-    fn shape(&self) -> String {
-        if self.is_scroll {
-            return "scroll_column".to_string();
+            scroll_class: if default_direction == "row" { "overflow-x-auto" } else { "overflow-y-auto" },
+            inner_padding: item_to_string(table, "inner_padding", "0px", value),
+            align_subs: item_to_string(table, "align_subs", "", value),
+            fixed: item_to_string(table, "fixed", "", value),
+            direction: direction_classes(table, value, default_direction),
         }
-        return "column".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
     }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
-        let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
 
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-            // "flex-shrink: 0".to_string(),
-        ];
+    fn htmlview(&self, common: &ViewCommon, views: Vec<HTMLView>) -> HTMLView {
+        let mut style_parts = vec![];
         if !self.fixed.is_empty() {
             style_parts.push(format!("position: fixed; {}: 0", self.fixed));
         }
@@ -180,424 +109,252 @@ impl TOMLView for ColumnView {
             style_parts.push(format!("gap:{}", self.inner_padding));
         }
 
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
         let mut class_parts = vec![
-            "flex flex-col".to_string()
+            format!("flex {}", self.direction.join(" "))
         ];
         if self.is_scroll {
-            class_parts.push("overflow-y-auto".to_string());
+            class_parts.push(self.scroll_class.to_string());
         } else {
             class_parts.push("overflow-hidden".to_string());
         }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
         if !self.align_subs.is_empty() {
             class_parts.push(format!("items-{}", self.align_subs));
         }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
 
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        
-        let class = class_parts.join(" ");
-
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
+        let mut attrs = common.attrs(common.class(class_parts));
+        attrs.insert("style".to_string(), common.style(style_parts));
 
         HTMLView {
             tag: "div".to_string(),
-            attrs: attrs,
+            attrs,
             value: "".to_string(),
-            views: views,
+            views,
         }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        .wrap_href(common.path.clone())
     }
 }
 
-pub struct RowView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    is_scroll: bool,
-    inner_padding: String,
-    align_absolute: String,
-    align_subs: String,
-    fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
-    views: Vec<Box<dyn TOMLView>>,
+/// values, 또는 data 파일의 inline table마다 layout을 만든다. page_size가 있으면 지금 쪽의 것만
+///
+/// data = "team" 이면 data/team.* 의 배열을 쓰고, values처럼 filter, order_by, offset, limit을 거친다.
+fn values_to_views(view: &dyn TOMLView, table: &Table, layout_tables: &HashMap<String, Table>) -> (Vec<Box<dyn TOMLView>>, Option<Pagination>) {
+    let value = view.value();
+    let layout = item_to_string(table, "layout", "", value.as_ref());
+    let data = item_to_string(table, "data", "", value.as_ref());
+    let values = if data.is_empty() {
+        table.get("values").and_then(|item| item.as_array())
+    } else {
+        lookup_data(layout_tables, &format!("{}.{}", DATA_KEY, data.trim())).and_then(|x| x.as_array())
+    };
+    let values: Vec<InlineTable> = values
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|v| if let Value::InlineTable(tbl) = v { Some(tbl.clone()) } else { None })
+                .collect()
+        })
+        .unwrap_or_default();
+    let values = query_metas(values, table, value.as_ref());
+    let page_size = item_to_integer(table, "page_size", 0, value.as_ref());
+    let (values, pagination) = paginate(values, page_size, &view.key_path(), layout_tables);
+    let views = values
+        .into_iter()
+        .filter_map(|tbl| layout_to_tomlview(view, layout.clone(), layout_tables, Some(&tbl)).ok())
+        .collect();
+    (views, pagination)
 }
 
-impl RowView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> RowView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
+/// files에 맞는 markdown마다 layout을 만든다. page_size가 있으면 지금 쪽의 것만
+fn markdowns_to_views(view: &dyn TOMLView, table: &Table, layout_tables: &HashMap<String, Table>) -> (Vec<Box<dyn TOMLView>>, Option<Pagination>) {
+    let value = view.value();
+    let layout = item_to_string(table, "layout", "", value.as_ref());
+    let metas = markdown_list_metas(view.index_path().as_path(), table, value.as_ref(), layout_tables);
+    let group_by = item_to_string(table, "group_by", "", value.as_ref());
+    let group_layout = item_to_string(table, "group_layout", "", value.as_ref());
+    let groups: Vec<String> = metas.iter().map(|metas| group_name(metas, &group_by)).collect();
+    let page_size = item_to_integer(table, "page_size", 0, value.as_ref());
+    let (metas, pagination) = paginate(metas.into_iter().zip(groups.clone()).collect(), page_size, &view.key_path(), layout_tables);
+
+    let mut views = vec![];
+    let mut last_group = None;
+    for (mut metas, group) in metas {
+        if group_by.is_empty() {
+            views.extend(layout_to_tomlview(view, layout.clone(), layout_tables, Some(&metas)).ok());
+            continue;
+        }
+        // 묶음이 바뀔 때마다 group_layout에 {group}과 그 묶음의 {count}를 넘긴다.
+        if last_group.as_ref() != Some(&group) && !group_layout.is_empty() {
+            let mut header = InlineTable::new();
+            header.insert("group", group.as_str().into());
+            header.insert("count", groups.iter().filter(|x| **x == group).count().to_string().into());
+            views.extend(layout_to_tomlview(view, group_layout.clone(), layout_tables, Some(&header)).ok());
+        }
+        if !metas.contains_key("group") {
+            metas.insert("group", group.as_str().into());
+        }
+        views.extend(layout_to_tomlview(view, layout.clone(), layout_tables, Some(&metas)).ok());
+        last_group = Some(group);
+    }
+    (views, pagination)
+}
+
+/// ### pager
+/// 나뉜 목록의 쪽 정보와 pagination layout
+struct Pager {
+    pagination: Option<Pagination>,
+    view: Option<Box<dyn TOMLView>>,
+}
+
+impl Pager {
+    fn new(view: &dyn TOMLView, table: &Table, pagination: Option<Pagination>, layout_tables: &HashMap<String, Table>) -> Pager {
+        let value = view.value();
+        let layout = item_to_string(table, "pagination", "", value.as_ref());
+        let pager_view = match &pagination {
+            Some(pagination) if !layout.is_empty() => {
+                layout_to_tomlview(view, layout, layout_tables, Some(&pagination.values())).ok()
             }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
+            _ => None,
         };
+        Pager { pagination, view: pager_view }
+    }
+
+    /// pagination layout이 있으면 목록 바로 뒤에 둔다.
+    ///
+    /// 둘을 감싸는 div는 contents라서 상위 layout에서는 목록과 pagination이 나란히 놓인다.
+    fn htmlview(&self, list: HTMLView, super_view: &dyn TOMLView) -> HTMLView {
+        match &self.view {
+            Some(view) => HTMLView::new("div", hashmap! {
+                "class".to_string() => "contents".to_string(),
+            }, "", vec![list, view.htmlview(Some(super_view))]),
+            None => list,
+        }
+    }
+}
 
-        let custom_class = item_to_string(&table, "custom_class", "", value);
+pub struct ColumnView {
+    common: ViewCommon,
+    flex: Flex,
+    views: Vec<Box<dyn TOMLView>>,
+}
 
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-        
-        let mut view = RowView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            align_subs: item_to_string(&table, "align_subs", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+impl ColumnView {
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> ColumnView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let mut view = ColumnView {
+            flex: Flex::new(table, common.value.as_ref(), is_scroll, "column"),
+            common,
             views: vec![],
         };
-
-        let views = table
-            .iter()
-            .filter_map(|(k, v)| {
-                if let Item::Table(inner_table) = v {
-                    match table_to_tomlview(index_path, k, inner_table, value, Some(&view), layout_tables.clone()) {
-                        Ok(view) => Some(view),
-                        Err(_) => None,
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect();
-        view.views = views;
+        view.views = sub_views(index_path, table, &view, layout_tables);
         view
     }
 }
 
-impl TOMLView for RowView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+impl TOMLView for ColumnView {
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "scroll_row".to_string();
+        if self.flex.is_scroll {
+            return "scroll_column".to_string();
         }
-        return "row".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
+        "column".to_string()
     }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
+        self.flex.htmlview(&self.common, views)
+    }
+}
 
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-            // "flex-shrink: 0".to_string(),
-        ];
-        if !self.inner_padding.trim().is_empty() {
-            style_parts.push(format!("gap:{}", self.inner_padding));
-        }
-        if !self.fixed.is_empty() {
-            style_parts.push(format!("position: fixed; {}: 0", self.fixed));
-        }
-
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
-        let mut class_parts = vec![
-            "flex flex-row".to_string()
-        ];
-        if self.is_scroll {
-            class_parts.push("overflow-x-auto".to_string());
-        } else {
-            class_parts.push("overflow-hidden".to_string());
-        }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_subs.is_empty() {
-            class_parts.push(format!("items-{}", self.align_subs));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
+pub struct RowView {
+    common: ViewCommon,
+    flex: Flex,
+    views: Vec<Box<dyn TOMLView>>,
+}
 
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
+impl RowView {
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> RowView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let mut view = RowView {
+            flex: Flex::new(table, common.value.as_ref(), is_scroll, "row"),
+            common,
+            views: vec![],
+        };
+        view.views = sub_views(index_path, table, &view, layout_tables);
+        view
+    }
+}
 
-        HTMLView {
-            tag: "div".to_string(),
-            attrs: attrs,
-            value: "".to_string(),
-            views: views,
+impl TOMLView for RowView {
+    common_accessors!();
+    fn shape(&self) -> String {
+        if self.flex.is_scroll {
+            return "scroll_row".to_string();
         }
-        .wrap_href(self.path.clone())
+        "row".to_string()
     }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
+        let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
+        self.flex.htmlview(&self.common, views)
     }
 }
 
 pub struct BoxView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    inner_padding: String,
-    align_absolute: String,
+    common: ViewCommon,
     align_subs: String,
     fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl BoxView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>) -> BoxView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-        
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-        
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>) -> BoxView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
         let mut view = BoxView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            align_subs: item_to_string(&table, "align_subs", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            common,
+            align_subs: item_to_string(table, "align_subs", "", value),
+            fixed: item_to_string(table, "fixed", "", value),
             views: vec![],
         };
-
-        let views = table
-            .iter()
-            .filter_map(|(k, v)| {
-                if let Item::Table(inner_table) = v {
-                    match table_to_tomlview(index_path, k, inner_table, value, Some(&view), layout_tables.clone()) {
-                        Ok(view) => Some(view),
-                        Err(_) => None,
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect();
-        view.views = views;
+        view.views = sub_views(index_path, table, &view, layout_tables);
         view
     }
 }
 
 impl TOMLView for BoxView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        return "box".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
+        "box".to_string()
     }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
 
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-            // "display:flex".to_string(),
-            // "flex-shrink: 0".to_string(),
-        ];
+        let mut style_parts = vec![];
         if !self.fixed.is_empty() {
             style_parts.push(format!("position: fixed; {}: 0", self.fixed));
         }
 
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
         let mut class_parts = vec![
             "relative".to_string(),
         ];
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
         if !self.align_subs.is_empty() {
             class_parts.push(format!("items-{}", self.align_subs));
         }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-            
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
 
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
+        let mut attrs = self.common.attrs(self.common.class(class_parts));
+        attrs.insert("style".to_string(), self.common.style(style_parts));
 
         HTMLView {
             tag: "div".to_string(),
-            attrs: attrs,
+            attrs,
             value: "".to_string(),
-            views: views,
+            views,
         }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        .wrap_href(self.common.path.clone())
     }
 }
 
 pub struct TextView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    align_absolute: String,
+    common: ViewCommon,
     size: String,
     text: String,
     color: String,
@@ -606,111 +363,48 @@ pub struct TextView {
     underline: bool,
     horizontal_align: String,
     vertical_align: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl TextView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>) -> TextView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-        
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-        
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, _layout_tables: &HashMap<String, Table>) -> TextView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
         TextView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            size: item_to_string(&table, "size", "", value),
-            text: item_to_string(&table, "text", "", value),
-            color: item_to_string(&table, "color", "", value),
-            family: item_to_string(&table, "family", "", value),
-            weight: item_to_string(&table, "weight", "", value),
-            underline: item_to_bool(&table, "underline", false, value),
-            horizontal_align: item_to_string(&table, "horizontal_align", "left", value),
-            vertical_align: item_to_string(&table, "vertical_align", "top", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            common,
+            size: item_to_string(table, "size", "", value),
+            text: item_to_string(table, "text", "", value),
+            color: item_to_string(table, "color", "", value),
+            family: item_to_string(table, "family", "", value),
+            weight: item_to_string(table, "weight", "", value),
+            underline: item_to_bool(table, "underline", false, value),
+            horizontal_align: item_to_string(table, "horizontal_align", "left", value),
+            vertical_align: item_to_string(table, "vertical_align", "top", value),
             views: vec![],
         }
     }
 }
 
 impl TOMLView for TextView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        return "text".to_string();
+        "text".to_string()
     }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
-        let mut class_parts = vec![
-        ];
-        if !self.background.is_empty() {
-            class_parts.push(format!("bg-[{}]", self.background));
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
+        let mut class_parts = vec![];
+        if !self.common.background.is_empty() {
+            class_parts.push(format!("bg-[{}]", self.common.background));
         }
         if !self.size.is_empty() {
             class_parts.push(format!("text-[{}]", self.size));
         }
         if !self.color.is_empty() {
             class_parts.push(format!("text-[{}]", self.color));
+        } else if self.common.dark {
+            class_parts.push("text-slate-50".to_string());
         } else {
-            if self.dark {
-                class_parts.push("text-slate-50".to_string());
-            } else {
-                class_parts.push("text-slate-950".to_string());
-            }
+            class_parts.push("text-slate-950".to_string());
         }
         if !self.family.is_empty() {
             class_parts.push(format!("font-{}", self.family));
@@ -727,188 +421,62 @@ impl TOMLView for TextView {
         if !self.vertical_align.is_empty() || !self.horizontal_align.is_empty() {
             class_parts.push("flex".to_string());
         }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
         if self.underline {
             class_parts.push("underline".to_string());
         }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
-
-        let mut span_attrs = HashMap::new();
-        span_attrs.insert("id".to_string(), self.key.clone());
-        span_attrs.insert("class".to_string(), class);
 
         HTMLView {
             tag: "span".to_string(),
-            attrs: span_attrs,
+            attrs: self.common.attrs(self.common.class(class_parts)),
             value: self.text.clone(),
             views: vec![],
         }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        .wrap_href(self.common.path.clone())
     }
 }
 
 pub struct ImageView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    align_absolute: String,
+    common: ViewCommon,
     image_path: String,
     content_size: String,
     rounded: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl ImageView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>) -> ImageView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, _layout_tables: &HashMap<String, Table>) -> ImageView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
         ImageView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            image_path: item_to_string(&table, "image_path", "", value),
-            content_size: item_to_string(&table, "content_size", "", value),
-            rounded: item_to_string(&table, "rounded", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            common,
+            image_path: item_to_string(table, "image_path", "", value),
+            content_size: item_to_string(table, "content_size", "", value),
+            rounded: item_to_string(table, "rounded", "", value),
             views: vec![],
         }
     }
 }
 
 impl TOMLView for ImageView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        return "text".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
+        "image".to_string()
     }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
-        let mut img_style_parts = vec![
-            format!("background:{}", self.background),
-        ];
-        let img_style = img_style_parts.join("; ") + ";"; // 끝에 세
-
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let mut class_parts = vec![
             "flex".to_string()
         ];
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
         if !self.content_size.is_empty() {
             class_parts.push(format!("object-{}", self.content_size));
         }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
         if !self.rounded.is_empty() {
             class_parts.push(format!("rounded-[{}]", self.rounded));
         }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
 
-        let mut img_attrs = HashMap::new();
-        img_attrs.insert("id".to_string(), self.key.clone());
-        img_attrs.insert("style".to_string(), img_style);
+        let mut img_attrs = self.common.attrs(self.common.class(class_parts));
+        img_attrs.insert("style".to_string(), self.common.style(vec![]));
         img_attrs.insert("src".to_string(), self.image_path.clone());
-        img_attrs.insert("class".to_string(), class);
-        // img_attrs.insert("width".to_string(), self.width.clone());
-        // img_attrs.insert("height".to_string(), self.height.clone());
 
         HTMLView {
             tag: "img".to_string(),
@@ -916,1036 +484,284 @@ impl TOMLView for ImageView {
             value: "".to_string(),
             views: vec![],
         }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        .wrap_href(self.common.path.clone())
     }
 }
 
 pub struct NavView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    align_absolute: String,
+    common: ViewCommon,
     title: String,
     headers: Vec<String>,
     headers_map: HashMap<String, Item>,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
+    theme: Theme,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl NavView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>) -> NavView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-        
-        let headers_map = get_items(&table, vec!["key", "shape", "width", "height", "background", "title", "values", "path", "dark"]);
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-        
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>) -> NavView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
         NavView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            title: item_to_string(&table, "title", "", value),
-            headers: item_to_strings(&table, "headers"),
-            headers_map: headers_map,
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            common,
+            title: item_to_string(table, "title", "", value),
+            headers: item_to_strings(table, "headers"),
+            headers_map: get_items(table, vec!["key", "shape", "width", "height", "background", "title", "values", "path", "dark"]),
+            theme: Theme::from_layout_tables(layout_tables),
             views: vec![],
         }
     }
 }
 
 impl TOMLView for NavView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        return "nav".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
+        "nav".to_string()
     }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView { 
-        let mut class_parts = vec![
-        ];
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
+        let nav_view = make_nav(self.title.clone(), self.headers.clone(), self.headers_map.clone(), self.common.dark, &self.theme);
 
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
-
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("class".to_string(), class);
-        
-        let nav_view = make_nav(self.title.clone(), self.headers.clone(), self.headers_map.clone(), self.dark);
-        
         HTMLView {
             tag: "div".to_string(),
-            attrs: attrs,
+            attrs: self.common.attrs(self.common.class(vec![])),
             value: "".to_string(),
             views: vec![nav_view],
         }
     }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
-    }
 }
 
-pub struct ListColumnView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    is_scroll: bool,
-    inner_padding: String,
-    align_absolute: String,
-    align_subs: String,
-    fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
+pub struct SearchView {
+    common: ViewCommon,
+    placeholder: String,
+    limit: i64,
+    theme: Theme,
     views: Vec<Box<dyn TOMLView>>,
 }
 
-impl ListColumnView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> ListColumnView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
+impl SearchView {
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>) -> SearchView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
+        SearchView {
+            common,
+            placeholder: item_to_string(table, "placeholder", "Search", value),
+            limit: item_to_integer(table, "limit", 10, value),
+            theme: Theme::from_layout_tables(layout_tables),
+            views: vec![],
         }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
+    }
+}
+
+impl TOMLView for SearchView {
+    common_accessors!();
+    fn shape(&self) -> String {
+        "search".to_string()
+    }
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
+        let mut attrs = self.common.attrs(self.common.class(vec![]));
+        attrs.insert("style".to_string(), self.common.style(vec![]));
+
+        HTMLView {
+            tag: "div".to_string(),
+            attrs,
+            value: "".to_string(),
+            views: make_search(&self.placeholder, self.limit, self.common.dark, &self.theme),
         }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
+    }
+}
 
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-        
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
+pub struct ListColumnView {
+    common: ViewCommon,
+    flex: Flex,
+    pager: Pager,
+    views: Vec<Box<dyn TOMLView>>,
+}
 
+impl ListColumnView {
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> ListColumnView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
         let mut view = ListColumnView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            align_subs: item_to_string(&table, "align_subs", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            flex: Flex::new(table, common.value.as_ref(), is_scroll, "column"),
+            common,
+            pager: Pager { pagination: None, view: None },
             views: vec![],
         };
-        
-        let layout = item_to_string(&table, "layout", "", value);
-        let mut views: Vec<Box<dyn TOMLView>> = vec![];
-        if let Some(values) = table.get("values").and_then(|item| item.as_array()) {
-            views.extend(
-                values
-                    .iter()
-                    .filter_map(|v| if let Value::InlineTable(tbl) = v { Some(tbl) } else { None })
-                    .filter_map(|tbl| layout_to_tomlview(&view, layout.clone(), layout_tables.clone(), Some(tbl)).ok())
-            );
-        }
-
+        let (views, pagination) = values_to_views(&view, table, layout_tables);
         view.views = views;
+        view.pager = Pager::new(&view, table, pagination, layout_tables);
         view
     }
 }
 
 impl TOMLView for ListColumnView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "list_column".to_string();
-        }
-        return "list_column".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
+        "list_column".to_string()
     }
-    fn dark(&self) -> bool {
-        return self.dark;
+    fn pagination(&self) -> Option<Pagination> {
+        self.pager.pagination.clone()
     }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
-
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-        ];
-        if !self.fixed.is_empty() {
-            style_parts.push(format!("position: fixed; {}: 0", self.fixed));
-        }
-
-        if !self.inner_padding.trim().is_empty() {
-            style_parts.push(format!("gap:{}", self.inner_padding));
-        }
-
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
-        let mut class_parts = vec![
-            "flex flex-col".to_string()
-        ];
-        if self.is_scroll {
-            class_parts.push("overflow-y-auto".to_string());
-        } else {
-            class_parts.push("overflow-hidden".to_string());
-        }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_subs.is_empty() {
-            class_parts.push(format!("items-{}", self.align_subs));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
-
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
-
-        HTMLView {
-            tag: "div".to_string(),
-            attrs: attrs,
-            value: "".to_string(),
-            views: views,
-        }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        self.pager.htmlview(self.flex.htmlview(&self.common, views), self)
     }
 }
 
 pub struct ListRowView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    is_scroll: bool,
-    inner_padding: String,
-    align_absolute: String,
-    align_subs: String,
-    fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
+    common: ViewCommon,
+    flex: Flex,
+    pager: Pager,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl ListRowView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> ListRowView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> ListRowView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
         let mut view = ListRowView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            align_subs: item_to_string(&table, "align_subs", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            flex: Flex::new(table, common.value.as_ref(), is_scroll, "row"),
+            common,
+            pager: Pager { pagination: None, view: None },
             views: vec![],
         };
-
-        let layout = item_to_string(&table, "layout", "", value);
-        let mut views: Vec<Box<dyn TOMLView>> = vec![];
-        if let Some(values) = table.get("values").and_then(|item| item.as_array()) {
-            views.extend(
-                values
-                    .iter()
-                    .filter_map(|v| if let Value::InlineTable(tbl) = v { Some(tbl) } else { None })
-                    .filter_map(|tbl| layout_to_tomlview(&view, layout.clone(), layout_tables.clone(), Some(tbl)).ok())
-            );
-        }
-
+        let (views, pagination) = values_to_views(&view, table, layout_tables);
         view.views = views;
+        view.pager = Pager::new(&view, table, pagination, layout_tables);
         view
     }
 }
 
 impl TOMLView for ListRowView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "list_row".to_string();
-        }
-        return "list_row".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
+        "list_row".to_string()
     }
-    fn height(&self) -> String {
-        return self.height.clone();
+    fn pagination(&self) -> Option<Pagination> {
+        self.pager.pagination.clone()
     }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
-
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-        ];
-        if !self.fixed.is_empty() {
-            style_parts.push(format!("position: fixed; {}: 0", self.fixed));
-        }
-
-        if !self.inner_padding.trim().is_empty() {
-            style_parts.push(format!("gap:{}", self.inner_padding));
-        }
-
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
-        let mut class_parts = vec![
-            "flex flex-row".to_string()
-        ];
-        if self.is_scroll {
-            class_parts.push("overflow-x-auto".to_string());
-        } else {
-            class_parts.push("overflow-hidden".to_string());
-        }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_subs.is_empty() {
-            class_parts.push(format!("items-{}", self.align_subs));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
-
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
-
-        HTMLView {
-            tag: "div".to_string(),
-            attrs: attrs,
-            value: "".to_string(),
-            views: views,
-        }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        self.pager.htmlview(self.flex.htmlview(&self.common, views), self)
     }
 }
 
 pub struct MarkdownListColumnView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    is_scroll: bool,
-    inner_padding: String,
-    align_absolute: String,
-    align_subs: String,
-    fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
+    common: ViewCommon,
+    flex: Flex,
+    pager: Pager,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl MarkdownListColumnView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> MarkdownListColumnView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> MarkdownListColumnView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
         let mut view = MarkdownListColumnView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            align_subs: item_to_string(&table, "align_subs", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark,
+            flex: Flex::new(table, common.value.as_ref(), is_scroll, "column"),
+            common,
+            pager: Pager { pagination: None, view: None },
             views: vec![],
         };
-
-        let layout = item_to_string(&table, "layout", "", value);
-        let mut views: Vec<Box<dyn TOMLView>> = vec![];
-
-        let index_folder = index_path.parent().unwrap_or(index_path);
-        let files = item_to_string(&table, "files", "", value);
-        let order_by = item_to_string(&table, "order_by", "", value);
-        let file_paths = find_files(files.as_str(), index_folder);
-
-        let order_by = order_by.trim(); // 예: "created" 또는 "created_desc"
-        let (order_by, desc) = if let Some(k) = order_by.strip_suffix("_desc") {
-            (k, true)
-        } else {
-            (order_by, false)
-        };
-        
-        // (view, metas) 쌍으로 수집
-        let mut view_with_meta: Vec<_> = file_paths
-            .iter()
-            .filter_map(|path| {
-                let metas = metas_table_from_markdown(path.as_path()).ok()?;
-                let view = layout_to_tomlview(&view, layout.clone(), layout_tables.clone(), Some(&metas)).ok()?;
-                Some((view, metas))
-            })
-            .collect();
-
-        if !order_by.is_empty() {
-            if order_by == "created" {
-                view_with_meta.sort_by_key(|(_, metas)| {
-                    metas
-                        .get("created")
-                        .and_then(|v| v.as_str())
-                        .and_then(|s| parse(s).ok()) // 여러 포맷 자동 인식
-                        .unwrap_or_else(|| DateTime::<Utc>::MIN_UTC) // 실패 시 최소값
-                });
-            } else {
-                view_with_meta.sort_by_key(|(_, metas)| {
-                    metas
-                        .get(&order_by)
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string()
-                });
-            }
-        }
-        if desc {
-            view_with_meta.reverse();
-        }
-
-        // view만 꺼내서 views에 추가
-        views.extend(view_with_meta.into_iter().map(|(v, _)| v));
-
+        let (views, pagination) = markdowns_to_views(&view, table, layout_tables);
         view.views = views;
+        view.pager = Pager::new(&view, table, pagination, layout_tables);
         view
     }
 }
 
 impl TOMLView for MarkdownListColumnView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "mdlist_column".to_string();
-        }
-        return "mdlist_column".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
+        "mdlist_column".to_string()
     }
-    fn width(&self) -> String {
-        return self.width.clone();
+    fn pagination(&self) -> Option<Pagination> {
+        self.pager.pagination.clone()
     }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
-
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-        ];
-        if !self.fixed.is_empty() {
-            style_parts.push(format!("position: fixed; {}: 0", self.fixed));
-        }
-
-        if !self.inner_padding.trim().is_empty() {
-            style_parts.push(format!("gap:{}", self.inner_padding));
-        }
-
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
-        let mut class_parts = vec![
-            "flex flex-col".to_string()
-        ];
-        if self.is_scroll {
-            class_parts.push("overflow-y-auto".to_string());
-        } else {
-            class_parts.push("overflow-hidden".to_string());
-        }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_subs.is_empty() {
-            class_parts.push(format!("items-{}", self.align_subs));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
-
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
-
-        HTMLView {
-            tag: "div".to_string(),
-            attrs: attrs,
-            value: "".to_string(),
-            views: views,
-        }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        self.pager.htmlview(self.flex.htmlview(&self.common, views), self)
     }
 }
 
 pub struct MarkdownListRowView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    is_scroll: bool,
-    inner_padding: String,
-    align_absolute: String,
-    align_subs: String,
-    fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
+    common: ViewCommon,
+    flex: Flex,
+    pager: Pager,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl MarkdownListRowView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> MarkdownListRowView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> MarkdownListRowView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
         let mut view = MarkdownListRowView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            align_subs: item_to_string(&table, "align_subs", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark,
+            flex: Flex::new(table, common.value.as_ref(), is_scroll, "row"),
+            common,
+            pager: Pager { pagination: None, view: None },
             views: vec![],
         };
-
-        let layout = item_to_string(&table, "layout", "", value);
-        let mut views: Vec<Box<dyn TOMLView>> = vec![];
-
-        let index_folder = index_path.parent().unwrap_or(index_path);
-        let files = item_to_string(&table, "files", "", value);
-        let file_paths = find_files(files.as_str(), index_folder);
-
-        views.extend(
-            file_paths
-                .iter()
-                .filter_map(|v| metas_table_from_markdown(v.as_path()).ok() )
-                .filter_map(|tbl| layout_to_tomlview(&view, layout.clone(), layout_tables.clone(), Some(&tbl)).ok())
-        );
-
+        let (views, pagination) = markdowns_to_views(&view, table, layout_tables);
         view.views = views;
+        view.pager = Pager::new(&view, table, pagination, layout_tables);
         view
     }
 }
 
 impl TOMLView for MarkdownListRowView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "mdlist_row".to_string();
-        }
-        return "mdlist_row".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
+        "mdlist_row".to_string()
     }
-    fn background(&self) -> String {
-        return self.background.clone();
+    fn pagination(&self) -> Option<Pagination> {
+        self.pager.pagination.clone()
     }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
-
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-        ];
-        if !self.fixed.is_empty() {
-            style_parts.push(format!("position: fixed; {}: 0", self.fixed));
-        }
-
-        if !self.inner_padding.trim().is_empty() {
-            style_parts.push(format!("gap:{}", self.inner_padding));
-        }
-
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
-        let mut class_parts = vec![
-            "flex flex-row".to_string()
-        ];
-        if self.is_scroll {
-            class_parts.push("overflow-x-auto".to_string());
-        } else {
-            class_parts.push("overflow-hidden".to_string());
-        }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_subs.is_empty() {
-            class_parts.push(format!("items-{}", self.align_subs));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
-
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
-
-        HTMLView {
-            tag: "div".to_string(),
-            attrs: attrs,
-            value: "".to_string(),
-            views: views,
-        }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        self.pager.htmlview(self.flex.htmlview(&self.common, views), self)
     }
 }
 
 pub struct MarkdownView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
+    common: ViewCommon,
     is_scroll: bool,
     inner_padding: String,
-    align_absolute: String,
     fixed: String,
     markdown_path: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
+    layout_tables: HashMap<String, Table>,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl MarkdownView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> MarkdownView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> MarkdownView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
         MarkdownView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
+            common,
             is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            markdown_path: item_to_string(&table, "markdown_path", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            inner_padding: item_to_string(table, "inner_padding", "0px", value),
+            fixed: item_to_string(table, "fixed", "", value),
+            markdown_path: item_to_string(table, "markdown_path", "", value),
+            layout_tables: layout_tables.clone(),
             views: vec![],
         }
     }
 }
 
 impl TOMLView for MarkdownView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "scroll_column".to_string();
-        }
-        return "column".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
+        "markdown".to_string()
     }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
 
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-            // "flex-shrink: 0".to_string(),
-        ];
+        let mut style_parts = vec![];
         if !self.fixed.is_empty() {
             style_parts.push(format!("position: fixed; {}: 0", self.fixed));
         }
-
         if !self.inner_padding.trim().is_empty() {
             style_parts.push(format!("gap:{}", self.inner_padding));
         }
 
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
         let mut class_parts = vec![
             "flex flex-col".to_string()
         ];
@@ -1954,313 +770,265 @@ impl TOMLView for MarkdownView {
         } else {
             class_parts.push("overflow-hidden".to_string());
         }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
 
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
+        let mut attrs = self.common.attrs(self.common.class(class_parts));
+        attrs.insert("style".to_string(), self.common.style(style_parts));
 
-        let parent = self.index_path.parent().expect("index_path has no parent");
+        let parent = self.common.index_path.parent().expect("index_path has no parent");
         let target_path = parent.join(self.markdown_path.clone());
-        let markdown_view = match markdown_to_htmlview(target_path.as_path(), self.dark) {
-            Ok(markdown_view) => {
-                markdown_view
-            },
-            Err(message) => {
-                println!("markdown_to_htmlview ... {}, {}", self.markdown_path.clone(), self.index_path.to_str().unwrap_or(""));
+        let markdown_view = match markdown_to_htmlview(target_path.as_path(), self.common.dark, &self.layout_tables) {
+            Ok(markdown_view) => markdown_view,
+            Err(_) => {
+                println!("markdown_to_htmlview ... {}, {}", self.markdown_path.clone(), self.common.index_path.to_str().unwrap_or(""));
                 HTMLView {
                     tag: "div".to_string(),
                     attrs: attrs.clone(),
                     value: "".to_string(),
-                    views: views,
+                    views,
                 }
-                .wrap_href(self.path.clone())
+                .wrap_href(self.common.path.clone())
             }
         };
 
         HTMLView {
             tag: "div".to_string(),
-            attrs: attrs,
+            attrs,
             value: "".to_string(),
             views: vec![markdown_view],
         }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        .wrap_href(self.common.path.clone())
     }
 }
 
 pub struct GridView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
+    common: ViewCommon,
     is_scroll: bool,
+    is_markdown: bool,
     inner_padding: String,
-    align_absolute: String,
+    row_gap: String,
+    column_gap: String,
     fixed: String,
-    row_count: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
+    columns: Vec<String>,
     views: Vec<Box<dyn TOMLView>>,
+    /// views와 같은 순서의 col-span, row-span class
+    spans: Vec<Vec<String>>,
 }
 
 impl GridView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> GridView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, is_scroll: bool) -> GridView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
+
+        // row_count는 예전 이름, 실제로는 열 개수
+        let mut columns = breakpoint_classes(table, "columns", "grid-cols", value);
+        if columns.is_empty() {
+            columns = breakpoint_classes(table, "row_count", "grid-cols", value);
         }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
+        if columns.is_empty() {
+            columns = vec!["grid-cols-3".to_string()];
         }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
+        let inner_padding = item_to_string(table, "inner_padding", "0px", value);
 
         let mut view = GridView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
+            common,
             is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            row_count: item_to_string(&table, "row_count", "3", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            is_markdown: item_to_string(table, "shape", "", value) == "mdgrid",
+            inner_padding: item_to_string(table, "gap", &inner_padding, value),
+            row_gap: item_to_string(table, "row_gap", "", value),
+            column_gap: item_to_string(table, "column_gap", "", value),
+            fixed: item_to_string(table, "fixed", "", value),
+            columns,
             views: vec![],
+            spans: vec![],
         };
 
-        let layout = item_to_string(&table, "layout", "", value);
         let mut views: Vec<Box<dyn TOMLView>> = vec![];
+        let mut spans: Vec<Vec<String>> = vec![];
+
+        // 하위 table은 칸 하나씩, span, row_span으로 여러 칸을 차지한다.
+        for (k, v) in table.iter() {
+            if let Item::Table(inner_table) = v {
+                if let Ok(sub_view) = table_to_tomlview(index_path, k, inner_table, value, Some(&view), layout_tables) {
+                    let mut span = breakpoint_classes(inner_table, "span", "col-span", value);
+                    span.extend(breakpoint_classes(inner_table, "row_span", "row-span", value));
+                    views.push(sub_view);
+                    spans.push(span);
+                }
+            }
+        }
 
-        if let Some(values) = table.get("values").and_then(|item| item.as_array()) {
-            views.extend(
-                values
-                    .iter()
-                    .filter_map(|v| if let Value::InlineTable(tbl) = v { Some(tbl) } else { None })
-                    .filter_map(|tbl| layout_to_tomlview(&view, layout.clone(), layout_tables.clone(), Some(tbl)).ok())
-            );
+        let layout = item_to_string(table, "layout", "", value);
+        let items: Vec<InlineTable> = if view.is_markdown {
+            markdown_list_metas(index_path, table, value, layout_tables)
+        } else {
+            table
+                .get("values")
+                .and_then(|item| item.as_array())
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(|v| if let Value::InlineTable(tbl) = v { Some(tbl.clone()) } else { None })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        for item in items {
+            if let Ok(sub_view) = layout_to_tomlview(&view, layout.clone(), layout_tables, Some(&item)) {
+                let mut span = breakpoint_classes_in_value(&item, "span", "col-span");
+                span.extend(breakpoint_classes_in_value(&item, "row_span", "row-span"));
+                views.push(sub_view);
+                spans.push(span);
+            }
         }
 
         view.views = views;
+        view.spans = spans;
         view
     }
 }
 
-impl TOMLView for GridView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
+/// ### markdown list metas
+/// files에 맞는 markdown들의 frontmatter를 filter로 거르고 order_by 순서로 모아 offset, limit 만큼 자른다.
+///
+/// publish: false 인 markdown은 drafts가 켜져 있을 때만 들어간다. 목록이 있는 페이지의 index.md는 빠진다.
+pub fn markdown_list_metas(index_path: &Path, table: &Table, value: Option<&InlineTable>, layout_tables: &HashMap<String, Table>) -> Vec<InlineTable> {
+    let index_folder = index_path.parent().unwrap_or(index_path);
+    let own_index = index_folder.join("index.md");
+    let depth = page_depth(layout_tables);
+    // 잘못된 패턴이나 읽을 수 없는 폴더는 validate_toml이 알려주므로 여기서는 빈 목록으로 둔다.
+    let file_paths = find_files(&item_to_keys(table, "files", value), index_folder).unwrap_or_default();
+    let drafts = include_drafts(layout_tables);
+
+    let metas_list: Vec<InlineTable> = file_paths
+        .iter()
+        .filter(|path| **path != own_index)
+        .filter_map(|path| markdown_list_item(path, index_folder, depth))
+        .filter(|metas| drafts || !is_draft(metas))
+        .collect();
+    query_metas(metas_list, table, value)
+}
+
+/// frontmatter에 url, word_count, excerpt 같은 계산한 값 중 없는 것을 더한다.
+///
+/// first_image는 markdown 페이지 기준이라 url 아래로 옮긴다.
+fn markdown_list_item(md_path: &Path, index_folder: &Path, depth: usize) -> Option<InlineTable> {
+    let content = std::fs::read_to_string(md_path).ok()?;
+    let mut metas = metas_from_markdown(md_path, &content).ok()?;
+    let url = markdown_url(md_path, index_folder, depth);
+    let mut computed = node_from_markdown(md_path, &content).map(|node| computed_metas(md_path, &node)).unwrap_or_default();
+    if let Some(image) = computed.get("first_image").and_then(|x| x.as_str()).map(|x| x.to_string()) {
+        let is_relative = !image.is_empty() && !image.starts_with('/') && !image.contains(':');
+        if is_relative {
+            computed.insert("first_image", format!("{}{}", url, image.trim_start_matches("./")).into());
+        }
     }
-    fn as_any(&self) -> &dyn Any {
-        self
+    computed.insert("url", url.into());
+    for (key, value) in computed.iter() {
+        if !metas.contains_key(key) {
+            metas.insert(key, value.clone());
+        }
     }
+    Some(metas)
+}
+
+/// 목록이 있는 페이지에서 markdown 페이지로 가는 상대 주소, "posts/a/" 처럼 폴더로 끝난다.
+///
+/// index.md는 그 폴더 자신이고, 2쪽부터는 page/N/ 같은 폴더 아래에서 그리므로 depth만큼 ../ 를 붙인다.
+fn markdown_url(md_path: &Path, index_folder: &Path, depth: usize) -> String {
+    let relative = md_path.strip_prefix(index_folder).unwrap_or(md_path);
+    let mut parts: Vec<String> = relative
+        .parent()
+        .map(|parent| parent.iter().map(|x| x.to_string_lossy().to_string()).collect())
+        .unwrap_or_default();
+    let stem = md_path.file_stem().and_then(|x| x.to_str()).unwrap_or("");
+    if stem != "index" {
+        parts.push(stem.to_string());
+    }
+    let prefix = "../".repeat(depth);
+    let url = format!("{}{}", prefix, parts.iter().map(|x| format!("{}/", x)).collect::<String>());
+    if url.is_empty() { "./".to_string() } else { url }
+}
+
+impl TOMLView for GridView {
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "grid".to_string();
+        if self.is_markdown {
+            return "mdgrid".to_string();
         }
-        return "grid".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
+        "grid".to_string()
     }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
-    }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
-        let views = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
+        let views = self.views
+            .iter()
+            .zip(self.spans.iter())
+            .map(|(x, span)| {
+                let mut html_view = x.htmlview(Some(self));
+                if !span.is_empty() {
+                    let class = html_view.attrs.entry("class".to_string()).or_default();
+                    *class = format!("{} {}", class, span.join(" ")).trim().to_string();
+                }
+                html_view
+            })
+            .collect();
 
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-            // "display:flex".to_string(),
-            // "flex-direction:row".to_string(),
-        ];
+        let mut style_parts = vec![];
         if !self.fixed.is_empty() {
             style_parts.push(format!("position: fixed; {}: 0", self.fixed));
         }
 
-        // if !self.inner_padding.trim().is_empty() {
-        //     style_parts.push(format!("gap:{}", self.inner_padding));
-        // }
-
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
         let mut class_parts = vec![
-            format!("grid grid-cols-{} gap-[{}]", self.row_count, self.inner_padding),
+            format!("grid {} gap-[{}]", self.columns.join(" "), self.inner_padding),
         ];
+        if !self.row_gap.is_empty() {
+            class_parts.push(format!("gap-y-[{}]", self.row_gap));
+        }
+        if !self.column_gap.is_empty() {
+            class_parts.push(format!("gap-x-[{}]", self.column_gap));
+        }
         if self.is_scroll {
             class_parts.push("overflow-x-auto".to_string());
         } else {
             class_parts.push("overflow-hidden".to_string());
         }
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
-
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
-        }
-        let class = class_parts.join(" ");
 
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), self.key.clone());
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
+        let mut attrs = self.common.attrs(self.common.class(class_parts));
+        attrs.insert("style".to_string(), self.common.style(style_parts));
 
         HTMLView {
             tag: "div".to_string(),
-            attrs: attrs,
+            attrs,
             value: "".to_string(),
-            views: views,
+            views,
         }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        .wrap_href(self.common.path.clone())
     }
 }
 
 pub struct EmbedView {
-    index_path: PathBuf,
-    key: String,
-    width: String,
-    height: String,
-    background: String,
-    path: String,
-    is_scroll: bool,
+    common: ViewCommon,
     inner_padding: String,
-    align_absolute: String,
     fixed: String,
-    custom_class: String,
-    value: Option<InlineTable>,
-    dark: bool,
     views: Vec<Box<dyn TOMLView>>,
 }
 
 impl EmbedView {
-    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: HashMap<String, Table>, is_scroll: bool) -> EmbedView {
-        let table_value = table.get("value").and_then(|v| v.as_inline_table());
-        let mut merged = InlineTable::new();
-        if let Some(v) = value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        if let Some(v) = table_value {
-            for (k, v) in v.iter() {
-                merged.insert(k, v.clone());
-            }
-        }
-        let value = if merged.is_empty() {
-            None
-        } else {
-            Some(&merged)
-        };
-
-        let custom_class = item_to_string(&table, "custom_class", "", value);
-
-        let default_dark = super_view
-            .and_then(|x| Some(x.dark()))
-            .unwrap_or(false);
-        let dark = item_to_bool(&table, "dark", default_dark, value);
-
+    pub fn new(index_path: &Path, key: &str, table: &Table, value: Option<&InlineTable>, super_view: Option<&dyn TOMLView>, layout_tables: &HashMap<String, Table>, _is_scroll: bool) -> EmbedView {
+        let common = ViewCommon::new(index_path, key, table, value, super_view);
+        let value = common.value.clone();
+        let value = value.as_ref();
         let mut view = EmbedView {
-            index_path: index_path.to_path_buf(),
-            key: key.to_string(),
-            width: item_to_string(&table, "width", "wrap", value),
-            height: item_to_string(&table, "height", "wrap", value),
-            background: item_to_string(&table, "background", "transparent", value),
-            path: item_to_string(&table, "path", "", value),
-            is_scroll,
-            inner_padding: item_to_string(&table, "inner_padding", "0px", value),
-            align_absolute: item_to_string(&table, "align_absolute", "", value),
-            fixed: item_to_string(&table, "fixed", "", value),
-            custom_class,
-            value: value.cloned(),
-            dark: dark,
+            common,
+            inner_padding: item_to_string(table, "inner_padding", "0px", value),
+            fixed: item_to_string(table, "fixed", "", value),
             views: vec![],
         };
 
-        let layout = item_to_string(&table, "layout", "", value);
+        let layout = item_to_string(table, "layout", "", value);
 
         if let Some(layout_view) = layout_tables.iter()
-            .filter_map(|(key, x)| table_to_tomlview(index_path, key, x, value, Some(&view), layout_tables.clone()).ok())
-            .filter(|x| x.key() == layout)
-            .next() {
+            .filter_map(|(key, x)| table_to_tomlview(index_path, key, x, value, Some(&view), layout_tables).ok())
+            .find(|x| x.key() == layout) {
                 view.views = vec![layout_view];
             }
         view
@@ -2268,110 +1036,52 @@ impl EmbedView {
 }
 
 impl TOMLView for EmbedView {
-    fn index_path(&self) -> PathBuf {
-        return self.index_path.clone()
-    }
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+    common_accessors!();
     fn shape(&self) -> String {
-        if self.is_scroll {
-            return "embed".to_string();
-        }
-        return "embed".to_string();
-    }
-    fn key(&self) -> String {
-        return self.key.clone();
-    }
-    fn width(&self) -> String {
-        return self.width.clone();
-    }
-    fn height(&self) -> String {
-        return self.height.clone();
-    }
-    fn background(&self) -> String {
-        return self.background.clone();
-    }
-    fn path(&self) -> String {
-        return self.path.clone();
-    }
-    fn dark(&self) -> bool {
-        return self.dark;
-    }
-    fn views(&self) -> &Vec<Box<dyn TOMLView>> {
-        return &self.views;
+        "embed".to_string()
     }
-    fn htmlview(&self, super_view: Option<&dyn TOMLView>) -> HTMLView {
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
         let views: Vec<HTMLView> = self.views.iter().map(|x| x.htmlview(Some(self))).collect();
-        let sub_classes: Vec<String> = views.clone()
+        let sub_classes: Vec<String> = views
             .first()
-            .and_then(|first_view| first_view.attrs.get("class"))  // Option<&String>
+            .and_then(|first_view| first_view.attrs.get("class"))
             .map(|class_str| {
                 class_str
                     .split_whitespace()
                     .map(|s| s.to_string())
                     .collect()
             })
-            .unwrap_or_else(Vec::new);  // None이면 빈 Vec 반환
+            .unwrap_or_default();
 
-        let mut style_parts = vec![
-            format!("background:{}", self.background),
-        ];
+        let mut style_parts = vec![];
         if !self.fixed.is_empty() {
             style_parts.push(format!("position: fixed; {}: 0", self.fixed));
         }
-
         if !self.inner_padding.trim().is_empty() {
             style_parts.push(format!("gap:{}", self.inner_padding));
         }
 
-        let style = style_parts.join("; ") + ";"; // 끝에 세미콜론
-
-        let mut class_parts = vec![
-        ];
-        if self.width.starts_with("w-") {
-            class_parts.push(self.width.clone());
-        } else if self.width != "wrap" {
-            class_parts.push(format!("w-[{}]", self.width));
-        } else {
-            let sub_width = sub_classes.iter().find(|&s| s.starts_with("w-"));
-            sub_width.map(|width_class| class_parts.push(width_class.clone()));
+        // 크기가 wrap이면 embed한 layout의 크기를 따른다.
+        let mut class_parts = vec![];
+        if self.common.width == "wrap" {
+            class_parts.extend(sub_classes.iter().find(|s| s.starts_with("w-")).cloned());
         }
-        if self.height.starts_with("h-") {
-            class_parts.push(self.height.clone());
-        } else if self.height != "wrap" {
-            class_parts.push(format!("h-[{}]", self.height));
-        } else {
-            let sub_height = sub_classes.iter().find(|&s| s.starts_with("h-"));
-            sub_height.map(|width_class| class_parts.push(width_class.clone()));
+        if self.common.height == "wrap" {
+            class_parts.extend(sub_classes.iter().find(|s| s.starts_with("h-")).cloned());
         }
-        if !self.align_absolute.is_empty() {
-            let mut align_class = self.align_absolute.clone();
 
-            if !align_class.chars().any(|c| c.is_numeric()) {
-                align_class = format!("{}-0", align_class);
-            }
-            class_parts.push(format!("absolute {}", align_class));
-        }
-        if !self.custom_class.is_empty() {
-            class_parts.push(format!("{}", self.custom_class));
+        let mut attrs = self.common.attrs(self.common.class(class_parts));
+        attrs.insert("style".to_string(), self.common.style(style_parts));
+        if self.common.id == self.common.key {
+            attrs.insert("id".to_string(), format!("embed_{}", self.common.key));
         }
-        let class = class_parts.join(" ");
-
-        let mut attrs = HashMap::new();
-        attrs.insert("id".to_string(), format!("embed_{}", self.key.clone()));
-        attrs.insert("style".to_string(), style);
-        attrs.insert("class".to_string(), class);
 
         HTMLView {
             tag: "div".to_string(),
-            attrs: attrs,
+            attrs,
             value: "".to_string(),
-            views: views,
+            views,
         }
-        .wrap_href(self.path.clone())
-    }
-    fn value(&self) -> Option<InlineTable> {
-        return self.value.clone();
+        .wrap_href(self.common.path.clone())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,51 @@
+//! # prema
+//!
+//! markdown과 index.toml로 만든 폴더를 html 사이트로 바꾼다.
+//!
+//! ```no_run
+//! use prema::Site;
+//!
+//! let report = Site::new("md")
+//!     .output("html")
+//!     .base_url("https://example.com/blog/")
+//!     .drafts(false)
+//!     .build()?;
+//! println!("{} page(s) written", report.pages.len());
+//! # Ok::<(), prema::Error>(())
+//! ```
+
+mod asset;
+mod check;
+mod cli;
+mod config;
+mod data;
+mod error;
+mod page;
+mod search;
+mod server;
+mod file;
+mod head;
+mod html;
+mod layout;
+mod migrate;
+mod markdown;
+mod option;
+mod common;
+mod site;
+mod tailwind;
+mod theme;
+mod yaml;
+#[cfg(test)]
+mod test;
+
+pub use cli::{generate_html, run_cli};
+pub use config::{Config, CONFIG_FILE};
+pub use error::{Diagnostic, Error, Location, Result, Severity};
+pub use html::HTMLView;
+pub use layout::{toml_to_html, TOMLView};
+pub use layout::common::{item_to_string, table_to_tomlview};
+pub use layout::registry::{register_shape, ShapeConstructor};
+pub use markdown::markdown_wrap_to_html;
+pub use migrate::{migrate_site, Migration};
+pub use page::{read_dir_recursive, Page};
+pub use site::{BuildReport, Site};
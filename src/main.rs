@@ -1,15 +1,6 @@
-mod cli;
-mod page;
-mod server;
-mod file;
-mod html;
-mod layout;
-mod markdown;
-mod option;
-mod common;
-mod yaml;
-mod test;
-
-fn main() -> Result<(), String> {
-    cli::run_cli()
+fn main() {
+    if let Err(error) = prema::run_cli() {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
 }
@@ -1,15 +1,15 @@
 
-pub fn remove_frontmatter(input: &String) -> String {
+pub fn remove_frontmatter(input: &str) -> String {
     let lines = input.lines();
 
     // 첫 줄이 '-'만 반복된 문자열인지 확인 (공백 제거 후)
     if let Some(first) = lines.clone().next() {
         let first_trimmed = first.trim();
         if !first_trimmed.chars().all(|c| c == '-') {
-            return input.clone(); // frontmatter 없음
+            return input.to_string(); // frontmatter 없음
         }
     } else {
-        return input.clone(); // 빈 문자열
+        return input.to_string(); // 빈 문자열
     }
 
     let lines = lines.skip(1);
@@ -33,4 +33,19 @@ pub fn remove_frontmatter(input: &String) -> String {
     }
 
     result.join("\n")
-}
\ No newline at end of file
+}
+
+/// frontmatter가 차지하는 줄 수 (구분자 포함)
+///
+/// remove_frontmatter 이후의 markdown 위치를 원래 파일의 줄 번호로 되돌릴 때 쓴다.
+pub fn frontmatter_line_count(input: &str) -> usize {
+    let mut lines = input.lines();
+    match lines.next() {
+        Some(first) if !first.trim().is_empty() && first.trim().chars().all(|c| c == '-') => {}
+        _ => return 0,
+    }
+    lines
+        .position(|line| line.trim().chars().all(|c| c == '-'))
+        .map(|end| end + 2)
+        .unwrap_or(0)
+}
@@ -1,30 +1,36 @@
 use std::{collections::HashMap, env, fs::{self, File}, io::Write, path::{Path, PathBuf}};
 
-use parser::{get_node_for_markdown, node_to_html};
+use chrono::{DateTime, Local};
+use markdown::mdast::Node;
+use parser::{node_from_markdown, node_to_html, MarkdownStyle};
 // use serde_yaml::Value;
 use toml_edit::{InlineTable, Table, Value};
 
-use crate::{html::HTMLView, layout::{common::{get_tomlview_for_key, layout_to_tomlview}, toml_to_html}, yaml::yaml_hashmap_to_inline_table};
+use crate::{error::{Error, Result}, file::get_file_timestamps, head::HeadMeta, html::HTMLView, layout::{common::{get_tomlview_for_key, layout_to_tomlview, SITE_TABLE}, pagination::{paged_html, paged_lists}, toml_to_html}, yaml::yaml_hashmap_to_inline_table};
 pub mod parser;
 pub mod common;
 
 const MARKDOWN_TOML: &str = include_str!("../asset/markdown.toml");
 
-fn get_markdown_path() -> Result<PathBuf, String> {
+fn get_markdown_path() -> Result<PathBuf> {
     let mut file_path = std::env::temp_dir();
     file_path.push("markdown.toml");
 
     // if file_path.exists() {
     // }
     fs::write(&file_path, MARKDOWN_TOML)
-        .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+        .map_err(|e| Error::io(&file_path, format!("파일 쓰기 실패: {}", e)))?;
     Ok(file_path)
 }
 
-pub fn markdown_to_htmlview(md_path: &Path, is_dark: bool) -> Result<HTMLView, String> {
-    let metas = metas_table_from_markdown(md_path)
-        .unwrap_or_else(|_| InlineTable::new());
-    
+/// [theme], [markdown] table과 frontmatter의 markdown 이 있으면 기본 class 대신 그것으로 그린다.
+pub fn markdown_to_htmlview(md_path: &Path, is_dark: bool, layout_tables: &HashMap<String, Table>) -> Result<HTMLView> {
+    let (metas, node) = read_markdown(md_path)?;
+    Ok(node_to_htmlview(md_path, &node, &metas, is_dark, layout_tables))
+}
+
+/// 이미 읽은 node를 html로 바꾼다. frontmatter의 dark가 is_dark보다 먼저
+fn node_to_htmlview(md_path: &Path, node: &Node, metas: &InlineTable, is_dark: bool, layout_tables: &HashMap<String, Table>) -> HTMLView {
     let dark_value = metas
         .get("dark")     
         .map(|v| {
@@ -37,15 +43,36 @@ pub fn markdown_to_htmlview(md_path: &Path, is_dark: bool) -> Result<HTMLView, S
         })
         .unwrap_or(is_dark);    
     
-    let node = get_node_for_markdown(md_path)?;
-    let htmlview = node_to_html(&node, None, None, dark_value);
-    Ok(htmlview)
+    let style = MarkdownStyle::new(md_path, dark_value, layout_tables, metas);
+    node_to_html(node, None, None, &style)
+}
+
+pub fn markdown_wrap_to_htmlview(md_path: &Path, layout_tables: HashMap<String, Table>) -> Result<HTMLView> {
+    let (html_view, _) = markdown_wrap(md_path, &layout_tables)?;
+    Ok(html_view)
+}
+
+/// ### markdown wrap to paged html
+/// markdown_wrap_to_html과 같고, header, footer의 나뉜 목록은 2쪽부터를 쓸 폴더와 함께 돌려준다.
+pub fn markdown_wrap_to_paged_html(md_path: &Path, layout_tables: &HashMap<String, Table>) -> Result<(String, Vec<(String, String)>)> {
+    paged_html(layout_tables, |layout_tables| {
+        let (html_view, lists) = markdown_wrap(md_path, layout_tables)?;
+        Ok((html_view.html(), lists))
+    })
 }
 
-pub fn markdown_wrap_to_htmlview(md_path: &Path, layout_tables: HashMap<String, Table>) -> Result<HTMLView, String> {
+fn markdown_wrap(md_path: &Path, layout_tables: &HashMap<String, Table>) -> Result<(HTMLView, Vec<(String, i64)>)> {
     let md_wrap_path = get_markdown_path()?;
-    let metas = metas_table_from_markdown(md_path)
-        .unwrap_or_else(|_| InlineTable::new());
+    let (metas, node) = read_markdown(md_path)?;
+    let mut metas = with_computed_metas(metas, md_path, &node);
+    // header, footer가 없으면 [site]의 기본 layout
+    if let Some(site) = layout_tables.get(SITE_TABLE) {
+        for key in ["header", "footer"] {
+            if let (false, Some(layout)) = (metas.contains_key(key), site.get(key).and_then(|x| x.as_str())) {
+                metas.insert(key, Value::from(layout));
+            }
+        }
+    }
 
     let view = get_tomlview_for_key(md_wrap_path.as_path(), "root", Some(&metas), None, layout_tables)?;
 
@@ -61,15 +88,17 @@ pub fn markdown_wrap_to_htmlview(md_path: &Path, layout_tables: HashMap<String,
         })
         .unwrap_or(view.dark());               // 없으면 기존 view.dark() 사용
 
-    let md_html_view = markdown_to_htmlview(md_path, dark_value)?;
+    let md_html_view = node_to_htmlview(md_path, &node, &metas, dark_value, layout_tables);
 
     let html_view = view.htmlview(None)
         .inflate_view("contents", md_html_view)
-        .wrap_body(view.dark());
-    Ok(html_view)
+        .wrap_body(view.dark(), layout_tables, &HeadMeta::new(&metas, md_path.parent().unwrap_or(Path::new(".")), layout_tables));
+    Ok((html_view, paged_lists(view.as_ref())))
 }
 
-pub fn markdown_wrap_to_html(md_path: &Path, layout_tables: HashMap<String, Table>) -> Result<String, String> {
+/// ### markdown wrap to html
+/// markdown 파일을 frontmatter의 header, footer layout으로 감싼 html 문서로 만든다.
+pub fn markdown_wrap_to_html(md_path: &Path, layout_tables: HashMap<String, Table>) -> Result<String> {
     let html_view = markdown_wrap_to_htmlview(md_path, layout_tables)?;
     let html = html_view.html();
     Ok(html)
@@ -86,20 +115,37 @@ pub fn markdown_wrap_to_html(md_path: &Path, layout_tables: HashMap<String, Tabl
 //     return Ok(html);
 // }
 
-pub fn metas_table_from_markdown(md_path: &Path) -> Result<InlineTable, String> {
-    let content = fs::read_to_string(md_path)
-        .map_err(|e| format!("Failed to read the markdown file: {}", e))?;
+pub fn metas_table_from_markdown(md_path: &Path) -> Result<InlineTable> {
+    let content = read_markdown_file(md_path)?;
+    metas_from_markdown(md_path, &content)
+}
+
+/// ### read markdown
+/// 파일을 한 번만 읽어서 frontmatter와 본문 node를 같이 돌려준다. frontmatter가 없거나 틀리면 빈 table
+pub fn read_markdown(md_path: &Path) -> Result<(InlineTable, Node)> {
+    let content = read_markdown_file(md_path)?;
+    let metas = metas_from_markdown(md_path, &content).unwrap_or_default();
+    let node = node_from_markdown(md_path, &content)?;
+    Ok((metas, node))
+}
+
+fn read_markdown_file(md_path: &Path) -> Result<String> {
+    fs::read_to_string(md_path)
+        .map_err(|e| Error::io(md_path, format!("Failed to read the markdown file: {}", e)))
+}
 
+/// 이미 읽은 markdown 파일 내용의 frontmatter
+pub fn metas_from_markdown(md_path: &Path, content: &str) -> Result<InlineTable> {
     let mut metadata = InlineTable::new();
     let mut lines = content.lines();
 
     // 첫 줄이 --- 여야 frontmatter 시작
     if let Some(first_line) = lines.next() {
         if first_line.trim() != "---" {
-            return Err("unable to find frontmatter first line".to_string());
+            return Err(frontmatter_error(md_path, "unable to find frontmatter first line"));
         }
     } else {
-        return Err("empty file".to_string()); // 빈 파일
+        return Err(frontmatter_error(md_path, "empty file")); // 빈 파일
     }
 
     // --- 사이의 메타데이터 추출
@@ -115,7 +161,7 @@ pub fn metas_table_from_markdown(md_path: &Path) -> Result<InlineTable, String>
 
     // 종료 구분자가 없으면 빈 HashMap 반환
     if !found_end {
-        return Err("unable to find frontmatter end line".to_string());
+        return Err(frontmatter_error(md_path, "unable to find frontmatter end line"));
     }
 
     let yaml_str = yaml_lines.join("\n");
@@ -133,4 +179,98 @@ pub fn metas_table_from_markdown(md_path: &Path) -> Result<InlineTable, String>
         metadata.extend(yaml_table);
     }
     Ok(metadata)
-}
\ No newline at end of file
+}
+
+/// reading_time을 셀 때 1분에 읽는 단어 수
+const WORDS_PER_MINUTE: usize = 200;
+
+/// 이 주석 앞의 문단들이 excerpt가 된다.
+pub const MORE_MARKER: &str = "<!-- more -->";
+
+/// ### computed metas
+/// 본문과 파일에서 계산한 word_count, reading_time(분), excerpt, modified, first_image
+///
+/// excerpt는 <!-- more --> 앞의 문단들, 없으면 첫 문단이다. {key}로 바로 쓰도록 값은 모두 문자열이다.
+pub fn computed_metas(md_path: &Path, node: &Node) -> InlineTable {
+    let word_count = plain_text(node).split_whitespace().count();
+    let modified = get_file_timestamps(md_path)
+        .ok()
+        .and_then(|(_, modified)| modified)
+        .map(|x| DateTime::<Local>::from(x).format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    let mut metas = InlineTable::new();
+    metas.insert("word_count", word_count.to_string().into());
+    metas.insert("reading_time", word_count.div_ceil(WORDS_PER_MINUTE).max(1).to_string().into());
+    metas.insert("excerpt", excerpt(node).into());
+    metas.insert("modified", modified.into());
+    metas.insert("first_image", first_image(node).unwrap_or_default().into());
+    metas
+}
+
+/// frontmatter에 없는 key만 computed metas로 채운다.
+pub fn with_computed_metas(mut metas: InlineTable, md_path: &Path, node: &Node) -> InlineTable {
+    for (key, value) in computed_metas(md_path, node).iter() {
+        if !metas.contains_key(key) {
+            metas.insert(key, value.clone());
+        }
+    }
+    metas
+}
+
+fn excerpt(node: &Node) -> String {
+    let children = node.children().map(|x| x.as_slice()).unwrap_or(&[]);
+    let more = children
+        .iter()
+        .position(|x| matches!(x, Node::Html(html) if html.value.trim() == MORE_MARKER));
+    let paragraphs = children[..more.unwrap_or(children.len())]
+        .iter()
+        .filter(|x| matches!(x, Node::Paragraph(_)))
+        .map(|x| x.to_string());
+    let excerpt: Vec<String> = match more {
+        Some(_) => paragraphs.collect(),
+        None => paragraphs.take(1).collect(),
+    };
+    excerpt.join(" ").split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// 본문 글자, 문단 안의 글자는 붙이고 문단끼리는 띄운다.
+fn plain_text(node: &Node) -> String {
+    let children = |separator: &str| {
+        node.children()
+            .map(|x| x.iter().map(plain_text).collect::<Vec<String>>().join(separator))
+            .unwrap_or_default()
+    };
+    match node {
+        Node::Text(text) => text.value.clone(),
+        Node::InlineCode(code) => code.value.clone(),
+        Node::Code(code) => code.value.clone(),
+        Node::Html(_) | Node::Yaml(_) | Node::Toml(_) => String::new(),
+        Node::Paragraph(_) | Node::Heading(_) | Node::TableCell(_) | Node::Emphasis(_) | Node::Strong(_) | Node::Delete(_) | Node::Link(_) => children(""),
+        _ => children(" "),
+    }
+}
+
+/// 본문에 처음 나오는 이미지의 주소
+fn first_image(node: &Node) -> Option<String> {
+    match node {
+        Node::Image(image) => Some(image.url.clone()),
+        _ => node.children()?.iter().find_map(first_image),
+    }
+}
+
+/// frontmatter에 publish: false 가 있으면 초안으로 본다.
+pub fn is_draft(metas: &InlineTable) -> bool {
+    metas
+        .get("publish")
+        .map(|v| v.as_bool() == Some(false) || v.as_str().is_some_and(|s| s.eq_ignore_ascii_case("false")))
+        .unwrap_or(false)
+}
+
+fn frontmatter_error(md_path: &Path, message: &str) -> Error {
+    Error::Markdown {
+        path: md_path.to_path_buf(),
+        message: message.to_string(),
+        location: None,
+    }
+}
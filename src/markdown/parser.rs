@@ -6,36 +6,169 @@ use markdown::message::Message;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use toml_edit::{InlineTable, Item, Table, Value};
+use crate::layout::common::table_to_tomlview;
 use crate::option::ThemeValue;
+use crate::theme::Theme;
 use crate::option::MDOption;
 
-use super::common::remove_frontmatter;
+use crate::error::{Error, Result};
 
-pub fn get_node_for_markdown(index_path: &Path) -> Result<Node, &'static str> {
-    let mut file = File::open(&index_path).ok().ok_or("open fails")?;
+use super::common::{frontmatter_line_count, remove_frontmatter};
+
+pub fn get_node_for_markdown(index_path: &Path) -> Result<Node> {
+    let mut file = File::open(index_path).map_err(|e| Error::io(index_path, e))?;
     let mut markdown_contents = String::new();
-    file.read_to_string(&mut markdown_contents).ok().ok_or("read fails")?;
-    let markdown_contents_removed_frontmatter = remove_frontmatter(&markdown_contents);
+    file.read_to_string(&mut markdown_contents).map_err(|e| Error::io(index_path, e))?;
+    node_from_markdown(index_path, &markdown_contents)
+}
+
+/// 이미 읽은 markdown 파일 내용에서 frontmatter를 빼고 mdast로 읽는다.
+pub fn node_from_markdown(index_path: &Path, markdown_contents: &str) -> Result<Node> {
+    let markdown_contents_removed_frontmatter = remove_frontmatter(markdown_contents);
+
+    markdown::to_mdast(&markdown_contents_removed_frontmatter, &markdown::ParseOptions::gfm())
+        .map_err(|message| Error::markdown(index_path, &message, frontmatter_line_count(markdown_contents)))
+}
+
+/// node 종류마다 class나 layout을 덮어쓰는 최상위 table 이름
+pub const MARKDOWN_TABLE: &str = "markdown";
+
+/// [markdown] table에 쓸 수 있는 node 종류
+pub const MARKDOWN_ELEMENTS: [&str; 14] = ["h1", "h2", "h3", "h4", "h5", "h6", "p", "blockquote", "table", "code", "a", "img", "li", "hr"];
 
-    match markdown::to_mdast(&markdown_contents_removed_frontmatter, &markdown::ParseOptions::gfm()) {
-        Ok(node) => Ok(node),
-        Err(message) => Err("no file"),
+/// node 하나를 기본 class 대신 어떻게 그릴지
+#[derive(Debug, Clone)]
+pub enum MarkdownOverride {
+    /// h1 = "text-5xl font-black", 기본 class를 통째로 바꾼다.
+    Class(String),
+    /// blockquote = { layout = "quote" }, layout을 그리고 그 안의 contents에 node를 넣는다.
+    Layout(String),
+}
+
+impl MarkdownOverride {
+    pub fn from_item(item: &Item) -> Option<MarkdownOverride> {
+        if let Some(class) = item.as_str() {
+            return Some(MarkdownOverride::Class(class.to_string()));
+        }
+        item.as_table_like()
+            .and_then(|table| table.get("layout"))
+            .and_then(|layout| layout.as_str())
+            .map(|layout| MarkdownOverride::Layout(layout.to_string()))
     }
 }
 
-pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark: bool) -> HTMLView {
+/// markdown node를 html로 바꿀 때 하위 node까지 물려주는 값
+#[derive(Debug, Clone)]
+pub struct MarkdownStyle<'a> {
+    pub is_dark: bool,
+    pub theme: Theme,
+    /// h1, p 같은 node 종류마다 덮어쓸 class나 layout
+    pub overrides: HashMap<String, MarkdownOverride>,
+    /// layout override를 그릴 때 쓰는 markdown 파일 경로와 layout table들
+    pub md_path: PathBuf,
+    pub layout_tables: &'a HashMap<String, Table>,
+}
+
+impl<'a> MarkdownStyle<'a> {
+    /// ### markdown style
+    /// [theme], [markdown] table과 frontmatter의 markdown 으로 만든다.
+    ///
+    /// frontmatter의 값이 [markdown] table의 같은 node 종류를 덮어쓴다.
+    pub fn new(md_path: &Path, is_dark: bool, layout_tables: &'a HashMap<String, Table>, metas: &InlineTable) -> MarkdownStyle<'a> {
+        let mut overrides = HashMap::new();
+        if let Some(table) = layout_tables.get(MARKDOWN_TABLE) {
+            for (kind, item) in table.iter() {
+                if let Some(found) = MarkdownOverride::from_item(item) {
+                    overrides.insert(kind.to_string(), found);
+                }
+            }
+        }
+        if let Some(table) = metas.get(MARKDOWN_TABLE).and_then(|x| x.as_inline_table()) {
+            for (kind, value) in table.iter() {
+                if let Some(found) = MarkdownOverride::from_item(&Item::Value(value.clone())) {
+                    overrides.insert(kind.to_string(), found);
+                }
+            }
+        }
+        MarkdownStyle {
+            is_dark,
+            theme: Theme::from_layout_tables(layout_tables),
+            overrides,
+            md_path: md_path.to_path_buf(),
+            layout_tables,
+        }
+    }
+
+    /// layout을 그리고 id가 contents인 하위 layout에 node를 넣는다. {text} 는 node의 글자
+    fn layout_view(&self, layout: &str, md: &Node, view: HTMLView) -> HTMLView {
+        let Some(table) = self.layout_tables.get(layout) else {
+            return view;
+        };
+        let mut value = InlineTable::new();
+        value.insert("text", Value::from(md.to_string()));
+        match table_to_tomlview(&self.md_path, layout, table, Some(&value), None, self.layout_tables) {
+            Ok(layout_view) => layout_view.htmlview(None).inflate_view("contents", view),
+            Err(_) => view,
+        }
+    }
+}
+
+/// [markdown] table에서 쓰는 node 종류 이름
+fn node_kind(md: &Node) -> Option<String> {
+    let kind = match md {
+        Node::Heading(node) => return Some(format!("h{}", node.depth)),
+        Node::Paragraph(..) => "p",
+        Node::Blockquote(..) => "blockquote",
+        Node::Table(..) => "table",
+        Node::Code(..) => "code",
+        Node::Link(..) => "a",
+        Node::Image(..) => "img",
+        Node::ListItem(..) => "li",
+        Node::ThematicBreak(..) => "hr",
+        _ => return None,
+    };
+    Some(kind.to_string())
+}
+
+/// ### node to html
+/// markdown node를 html로 바꾸고, [markdown] table에 있는 node 종류면 class나 layout을 덮어쓴다.
+pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, style: &MarkdownStyle) -> HTMLView {
+    let mut view = node_to_default_html(md, sup, index, style);
+    match node_kind(md).and_then(|kind| style.overrides.get(&kind)) {
+        None => view,
+        Some(MarkdownOverride::Class(class)) => {
+            view.attrs.insert("class".to_string(), filter_attrs(class, style.is_dark));
+            view
+        }
+        Some(MarkdownOverride::Layout(layout)) => style.layout_view(layout, md, view),
+    }
+}
+
+/// node 종류마다 정해진 기본 class로 그린다.
+fn node_to_default_html(md: &Node, sup: Option<&Node>, index: Option<usize>, style: &MarkdownStyle) -> HTMLView {
+    let is_dark = style.is_dark;
+    let theme = &style.theme;
+    let with_theme = |class: String, extras: Vec<Option<String>>| {
+        let mut parts = vec![class];
+        parts.extend(extras.into_iter().flatten());
+        filter_attrs(&parts.join(" "), is_dark)
+    };
+    let heading = |class: &str| {
+        with_theme(format!("{} {}", class, theme.color_class("text", "text", "text-slate-900 dark:text-white")), vec![theme.font_class("font_heading")])
+    };
     match md {
         Node::Root(node) => HTMLView {
             tag: "div".to_string(),
             attrs: hashmap! {
-                "class".to_string() => filter_attrs("w-full pb-[50px] dark:bg-slate-900", is_dark)
+                "class".to_string() => with_theme(format!("w-full pb-[50px] {}", theme.color_class("surface", "bg", "dark:bg-slate-900")), vec![theme.font_class("font_body")])
             },
             value: "".to_string(),
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::Heading(node) => HTMLView {
@@ -47,12 +180,12 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             },
             attrs: hashmap! {
                 "class".to_string() => match node.depth {
-                    1 => filter_attrs("text-4xl font-bold mb-8 mt-5 text-slate-900 dark:text-white", is_dark),
-                    2 => filter_attrs("text-3xl font-bold mb-6 mt-5 text-slate-900 dark:text-white", is_dark),
-                    3 => filter_attrs("text-2xl font-bold mb-4 mt-5 text-slate-900 dark:text-white", is_dark),
-                    4 => filter_attrs("text-xl font-bold mb-3 mt-5 text-slate-900 dark:text-white", is_dark),
-                    5 => filter_attrs("text-base font-bold mb-3 mt-5 text-slate-900 dark:text-white", is_dark),
-                    6 => filter_attrs("text-sm font-bold mb-3 mt-5 text-slate-900 dark:text-white", is_dark),
+                    1 => heading("text-4xl font-bold mb-8 mt-5"),
+                    2 => heading("text-3xl font-bold mb-6 mt-5"),
+                    3 => heading("text-2xl font-bold mb-4 mt-5"),
+                    4 => heading("text-xl font-bold mb-3 mt-5"),
+                    5 => heading("text-base font-bold mb-3 mt-5"),
+                    6 => heading("text-sm font-bold mb-3 mt-5"),
                     _ => filter_attrs("", is_dark),
                 },
             },
@@ -60,7 +193,7 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::List(node) => HTMLView {
@@ -70,15 +203,15 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             },
             attrs: hashmap! {
                 "class".to_string() => match node.start {
-                    None => filter_attrs("list-disc pl-5 text-slate-500 dark:text-slate-400", is_dark),
-                    Some(_) => filter_attrs("list-decimal pl-5 text-slate-500 dark:text-slate-400", is_dark),
+                    None => with_theme(format!("list-disc pl-5 {}", theme.color_class("muted", "text", "text-slate-500 dark:text-slate-400")), vec![]),
+                    Some(_) => with_theme(format!("list-decimal pl-5 {}", theme.color_class("muted", "text", "text-slate-500 dark:text-slate-400")), vec![]),
                 },
             },
             value: "".to_string(),
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::ListItem(node) => HTMLView {
@@ -89,21 +222,26 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::Blockquote(node) => HTMLView {
             tag: "blockquote".to_string(),
             attrs: hashmap! {
-                "class".to_string() => filter_attrs(
-                    "p-4 my-4 border-s-4 border-gray-300 bg-gray-50 dark:border-gray-500 dark:bg-gray-800", is_dark
+                "class".to_string() => with_theme(
+                    format!(
+                        "p-4 my-4 border-s-4 {} {}",
+                        theme.color_class("border", "border", "border-gray-300 dark:border-gray-500"),
+                        theme.color_class("surface", "bg", "bg-gray-50 dark:bg-gray-800")
+                    ),
+                    vec![theme.radius_class()]
                 ),
             },
             value: "".to_string(),
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::Paragraph(node) => HTMLView {
@@ -112,10 +250,10 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
                 "class".to_string() => match sup {
                     None => String::from(""),
                     Some(node) => match node {
-                        Node::Blockquote(..) => filter_attrs(
-                            "text-xl italic font-medium leading-relaxed text-gray-900 dark:text-white", is_dark
+                        Node::Blockquote(..) => with_theme(
+                            format!("text-xl italic font-medium leading-relaxed {}", theme.color_class("text", "text", "text-gray-900 dark:text-white")), vec![]
                         ),
-                        _ => filter_attrs("text-slate-500 dark:text-slate-400 mt-1", is_dark),
+                        _ => with_theme(format!("{} mt-1", theme.color_class("muted", "text", "text-slate-500 dark:text-slate-400")), vec![]),
                     },
                 }
             },
@@ -123,7 +261,7 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::Text(node) => HTMLView {
@@ -141,7 +279,7 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::Emphasis(node) => HTMLView {
@@ -152,13 +290,13 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::Code(node) => HTMLView {
             tag: "pre".to_string(),
             attrs: hashmap! {
-                "class".to_string() => filter_attrs("flex", is_dark),
+                "class".to_string() => with_theme("flex".to_string(), vec![theme.font_class("font_mono"), theme.radius_class()]),
             },
             value: "".to_string(),
             views: vec![
@@ -177,7 +315,7 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             tag: "a".to_string(),
             attrs: hashmap! {
                 "href".to_string() => node.url.to_string(),
-                "class".to_string() => match &node.title {
+                "class".to_string() => with_theme(match &node.title {
                     None => match sup {
                         None => String::from(""),
                         Some(node) => match node {
@@ -189,13 +327,13 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
                         },
                     }
                     Some(title) => title.to_string(),
-                },
+                }, vec![Some(theme.color_class("primary", "text", "")).filter(|x| !x.is_empty())]),
             },
             value: "".to_string(),
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::Image(node) => HTMLView {
@@ -207,10 +345,10 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
                 //     None => "".to_string(),
                 //     Some(title) => title.to_string(),
                 // },
-                "class".to_string() => match &node.title {
+                "class".to_string() => with_theme(match &node.title {
                     None => "".to_string(),
                     Some(title) => title.to_string(),
-                },
+                }, vec![theme.radius_class()]),
             },
             value: "".to_string(),
             views: vec![],
@@ -218,7 +356,7 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
         Node::ThematicBreak(node) => HTMLView {
             tag: "hr".to_string(),
             attrs: hashmap! {
-                "class".to_string() => filter_attrs("h-px bg-slate-200 dark:bg-slate-700 border-0 my-1.5", is_dark),
+                "class".to_string() => with_theme(format!("h-px {} border-0 my-1.5", theme.color_class("border", "bg", "bg-slate-200 dark:bg-slate-700")), vec![]),
             },
             value: "".to_string(),
             views: vec![],
@@ -226,14 +364,14 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
         Node::Table(node) => HTMLView {
             tag: "table".to_string(),
             attrs: hashmap! {
-                "class".to_string() => filter_attrs("w-full text-sm text-left rtl:text-right text-gray-500 dark:text-gray-400", is_dark),
+                "class".to_string() => with_theme(format!("w-full text-sm text-left rtl:text-right {}", theme.color_class("muted", "text", "text-gray-500 dark:text-gray-400")), vec![]),
             },
             value: "".to_string(),
             views: node
                 .children
                 .iter()
                 .enumerate()
-                .map(|(index, x)| node_to_html(x, Some(md), Some(index), is_dark))
+                .map(|(index, x)| node_to_html(x, Some(md), Some(index), style))
                 .collect(),
         },
         Node::TableRow(node) => HTMLView {
@@ -252,7 +390,7 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         Node::TableCell(node) => HTMLView {
@@ -264,7 +402,7 @@ pub fn node_to_html(md: &Node, sup: Option<&Node>, index: Option<usize>, is_dark
             views: node
                 .children
                 .iter()
-                .map(|x| node_to_html(x, Some(md), None, is_dark))
+                .map(|x| node_to_html(x, Some(md), None, style))
                 .collect(),
         },
         _ => HTMLView::zero(),
@@ -0,0 +1,350 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, Array, DocumentMut, Item, Table};
+
+use crate::check::NAV_RESERVED_KEYS;
+use crate::error::{Diagnostic, Error, Result};
+use crate::file;
+use crate::layout::common::read_document;
+use crate::option::{table_to_basic, table_to_footer, table_to_menus, table_to_themes, Basic, Footer, Menu, ThemeValue};
+
+/// 예전 페이지 설정 파일 이름
+pub const OPTION_FILE: &str = "option.toml";
+
+/// 옮기고 난 option.toml 은 이 이름으로 남긴다.
+const BACKUP_FILE: &str = "option.toml.bak";
+
+/// ### migration
+/// prema migrate의 결과
+#[derive(Debug, Default)]
+pub struct Migration {
+    /// 만들거나 고친 index.toml, markdown 파일들
+    pub written: Vec<PathBuf>,
+    /// 옮기지 못한 것들
+    pub issues: Vec<Diagnostic>,
+}
+
+/// ### migrate site
+/// md_root_path 아래의 option.toml을 모두 index.toml layout과 frontmatter로 옮긴다.
+///
+/// - [nav] -> shape = "nav" layout, frontmatter의 header
+/// - [footer] -> title과 sns 링크를 text로 가진 row layout, frontmatter의 footer
+/// - [theme] night -> frontmatter의 dark
+/// - [basic] -> frontmatter의 title, created, tags
+///
+/// layout은 폴더의 index.toml, 없으면 가장 가까운 상위 폴더의 index.toml에 넣는다.
+/// 둘 다 없고 index.md도 비어 있으면 index.toml을 새로 만든다.
+pub fn migrate_site(md_root_path: &Path) -> Result<Migration> {
+    let mut migration = Migration::default();
+    migrate_dir(md_root_path, md_root_path, &mut migration)?;
+    Ok(migration)
+}
+
+fn migrate_dir(root: &Path, dir: &Path, migration: &mut Migration) -> Result<()> {
+    let option_path = dir.join(OPTION_FILE);
+    if option_path.is_file() {
+        migrate_option(root, &option_path, migration)?;
+    }
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| Error::io(dir, e))?
+        .filter_map(|x| x.ok())
+        .map(|x| x.path())
+        .filter(|x| x.is_dir())
+        .collect();
+    dirs.sort();
+    for sub_dir in dirs {
+        migrate_dir(root, &sub_dir, migration)?;
+    }
+    Ok(())
+}
+
+/// frontmatter에 넣을 값
+enum Front {
+    Text(String),
+    Bool(bool),
+    List(Vec<String>),
+}
+
+fn migrate_option(root: &Path, option_path: &Path, migration: &mut Migration) -> Result<()> {
+    let dir = option_path.parent().unwrap_or(Path::new("."));
+    let doc = read_document(option_path)?;
+
+    let mut menus: Vec<Menu> = vec![];
+    let mut footer: Option<Footer> = None;
+    let mut basic: Option<Basic> = None;
+    let mut dark: Option<bool> = None;
+    for (key, item) in doc.as_table() {
+        let Some(table) = item.as_table() else {
+            migration.issues.push(Diagnostic::warning(option_path, key, format!("\"{}\" is not a table, left out", key)));
+            continue;
+        };
+        match key {
+            "nav" => menus = table_to_menus(table),
+            "footer" => footer = Some(table_to_footer(table)),
+            "basic" => basic = Some(table_to_basic(table)),
+            "theme" => {
+                for (name, theme) in table_to_themes(table) {
+                    match (name.as_str(), theme) {
+                        ("night", ThemeValue::Bool(night)) => dark = Some(night),
+                        _ => migration.issues.push(Diagnostic::warning(
+                            option_path,
+                            &format!("theme.{}", name),
+                            "only theme.night can be converted (to dark), left out",
+                        )),
+                    }
+                }
+            }
+            _ => migration.issues.push(Diagnostic::warning(option_path, key, format!("unknown table \"{}\", left out", key))),
+        }
+    }
+
+    let title = basic.as_ref().map(|x| x.title.clone()).unwrap_or_default();
+    let mut layouts: Vec<(&str, Table)> = vec![];
+    if !menus.is_empty() {
+        layouts.push(("nav", nav_layout(&title, &menus, option_path, &mut migration.issues)));
+    }
+    if let Some(footer) = footer.filter(|x| !x.title.is_empty() || !x.snss.is_empty()) {
+        layouts.push(("footer", footer_layout(&footer, option_path, &mut migration.issues)));
+    }
+
+    let mut front: Vec<(&str, Front)> = vec![];
+    if !layouts.is_empty() {
+        match place_layouts(root, dir, &layouts, dark, migration)? {
+            Some(names) => {
+                for ((key, _), name) in layouts.iter().zip(names) {
+                    let front_key = if *key == "nav" { "header" } else { "footer" };
+                    front.push((front_key, Front::Text(name)));
+                }
+            }
+            None => {
+                migration.issues.push(Diagnostic::error(
+                    option_path,
+                    "",
+                    "no index.toml to hold the nav and footer layouts, index.md is not empty; add an index.toml above this folder and migrate again",
+                ));
+                return Ok(());
+            }
+        }
+    }
+    if let Some(dark) = dark {
+        front.push(("dark", Front::Bool(dark)));
+    }
+    if let Some(basic) = &basic {
+        if !basic.title.is_empty() {
+            front.push(("title", Front::Text(basic.title.clone())));
+        }
+        if !basic.created.is_empty() {
+            front.push(("created", Front::Text(basic.created.clone())));
+        }
+        if !basic.tags.is_empty() {
+            front.push(("tags", Front::List(basic.tags.clone())));
+        }
+    }
+
+    if !front.is_empty() {
+        let mut md_paths = file::find_all_ext_files(dir, |ext| ext == "md")?;
+        md_paths.sort();
+        for md_path in md_paths {
+            let content = fs::read_to_string(&md_path).map_err(|e| Error::io(&md_path, e))?;
+            if content.trim().is_empty() {
+                continue;
+            }
+            let migrated = add_frontmatter(&content, &front);
+            if migrated != content {
+                fs::write(&md_path, migrated).map_err(|e| Error::io(&md_path, e))?;
+                migration.written.push(md_path);
+            }
+        }
+    }
+
+    let backup = dir.join(BACKUP_FILE);
+    fs::rename(option_path, &backup).map_err(|e| Error::io(option_path, e))?;
+    Ok(())
+}
+
+/// [nav] 메뉴들을 nav layout으로, 드롭다운은 service.etc1 = "etc1" 꼴의 하위 table로
+fn nav_layout(title: &str, menus: &[Menu], option_path: &Path, issues: &mut Vec<Diagnostic>) -> Table {
+    let mut table = Table::new();
+    table.insert("shape", value("nav"));
+    table.insert("width", value("100%"));
+    table.insert("height", value("70px"));
+    table.insert("title", value(title));
+    let mut headers = Array::new();
+    for menu in menus {
+        if NAV_RESERVED_KEYS.contains(&menu.name.as_str()) {
+            issues.push(Diagnostic::warning(
+                option_path,
+                &format!("nav.{}", menu.name),
+                format!("menu \"{}\" is a reserved nav key, left out", menu.name),
+            ));
+            continue;
+        }
+        headers.push(menu.name.as_str());
+        if menu.dropdowns.is_empty() {
+            table.insert(&menu.name, value(menu.path.as_str()));
+        } else {
+            let mut dropdowns = Table::new();
+            for dropdown in &menu.dropdowns {
+                dropdowns.insert(&dropdown.name, value(dropdown.path.as_str()));
+            }
+            table.insert(&menu.name, Item::Table(dropdowns));
+        }
+    }
+    table.insert("headers", value(headers));
+    table
+}
+
+/// [footer]의 title과 sns 링크를 text로 가진 row layout
+fn footer_layout(footer: &Footer, option_path: &Path, issues: &mut Vec<Diagnostic>) -> Table {
+    let mut table = Table::new();
+    table.insert("shape", value("row"));
+    table.insert("width", value("100%"));
+    table.insert("height", value("wrap"));
+    let text = |text: &str, path: Option<&str>| {
+        let mut table = Table::new();
+        table.insert("shape", value("text"));
+        table.insert("width", value("wrap"));
+        table.insert("height", value("wrap"));
+        table.insert("text", value(text));
+        if let Some(path) = path {
+            table.insert("path", value(path));
+        }
+        Item::Table(table)
+    };
+    if !footer.title.is_empty() {
+        table.insert("copyright", text(&footer.title, None));
+    }
+    for sns in &footer.snss {
+        table.insert(&sns.name, text(&sns.name, Some(&sns.path)));
+    }
+    if !footer.snss.is_empty() {
+        issues.push(Diagnostic::warning(
+            option_path,
+            "footer.sns",
+            "sns icons are converted to text links",
+        ));
+    }
+    table
+}
+
+/// ### place layouts
+/// layout들을 넣을 index.toml을 찾아 쓰고, frontmatter에서 부를 이름들을 돌려준다.
+///
+/// 같은 이름의 다른 layout이 이미 있으면 "nav_{폴더 이름}" 처럼 바꾼다.
+fn place_layouts(root: &Path, dir: &Path, layouts: &[(&str, Table)], dark: Option<bool>, migration: &mut Migration) -> Result<Option<Vec<String>>> {
+    let index_path = match find_index_toml(root, dir) {
+        Some(index_path) => index_path,
+        None => {
+            let index_md = dir.join("index.md");
+            let is_empty = fs::read_to_string(&index_md).map(|x| x.trim().is_empty()).unwrap_or(true);
+            if !is_empty {
+                return Ok(None);
+            }
+            if index_md.exists() {
+                fs::remove_file(&index_md).map_err(|e| Error::io(&index_md, e))?;
+            }
+            let index_path = dir.join("index.toml");
+            fs::write(&index_path, root_layout(layouts, dark)).map_err(|e| Error::io(&index_path, e))?;
+            index_path
+        }
+    };
+
+    let mut doc = read_document(&index_path)?;
+    let folder = dir.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+    let mut names = vec![];
+    for (key, table) in layouts {
+        let mut name = key.to_string();
+        if let Some(existing) = doc.get(&name) {
+            if existing.as_table().map(|x| x.to_string()) != Some(table.to_string()) {
+                name = format!("{}_{}", key, folder);
+            }
+        }
+        doc.insert(&name, Item::Table(table.clone()));
+        names.push(name);
+    }
+    fs::write(&index_path, doc.to_string()).map_err(|e| Error::io(&index_path, e))?;
+    if !migration.written.contains(&index_path) {
+        migration.written.push(index_path);
+    }
+    Ok(Some(names))
+}
+
+/// dir부터 root까지 올라가며 처음 만나는 index.toml
+fn find_index_toml(root: &Path, dir: &Path) -> Option<PathBuf> {
+    let mut dir = Some(dir);
+    while let Some(current) = dir {
+        let index_path = current.join("index.toml");
+        if index_path.is_file() {
+            return Some(index_path);
+        }
+        if current == root {
+            break;
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// 새 index.toml의 [root], 위아래에 nav와 footer를 embed로 둔다.
+fn root_layout(layouts: &[(&str, Table)], dark: Option<bool>) -> String {
+    let mut doc = DocumentMut::new();
+    let mut root = Table::new();
+    root.insert("shape", value("column"));
+    root.insert("width", value("100%"));
+    root.insert("height", value("100%"));
+    if let Some(dark) = dark {
+        root.insert("dark", value(dark));
+    }
+    for (key, _) in layouts {
+        let mut embed = Table::new();
+        embed.insert("shape", value("embed"));
+        embed.insert("layout", value(*key));
+        root.insert(key, Item::Table(embed));
+    }
+    doc.insert("root", Item::Table(root));
+    doc.to_string()
+}
+
+/// frontmatter에 없는 key들만 더한다. frontmatter가 없으면 새로 만든다.
+fn add_frontmatter(content: &str, front: &[(&str, Front)]) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let end = match lines.first() {
+        Some(first) if first.trim() == "---" => lines.iter().skip(1).position(|x| x.trim() == "---").map(|x| x + 1),
+        _ => None,
+    };
+    let existing: Vec<&str> = match end {
+        Some(end) => lines[1..end].iter().filter_map(|x| x.split_once(':')).map(|(key, _)| key.trim()).collect(),
+        None => vec![],
+    };
+    let added: Vec<String> = front
+        .iter()
+        .filter(|(key, _)| !existing.contains(key))
+        .map(|(key, front)| format!("{}: {}", key, yaml_value(front)))
+        .collect();
+    if added.is_empty() {
+        return content.to_string();
+    }
+    match end {
+        Some(end) => {
+            let mut migrated: Vec<String> = lines[..end].iter().map(|x| x.to_string()).collect();
+            migrated.extend(added);
+            migrated.extend(lines[end..].iter().map(|x| x.to_string()));
+            let mut migrated = migrated.join("\n");
+            if content.ends_with('\n') {
+                migrated.push('\n');
+            }
+            migrated
+        }
+        None => format!("---\n{}\n---\n{}", added.join("\n"), content),
+    }
+}
+
+fn yaml_value(front: &Front) -> String {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    match front {
+        Front::Text(text) => quote(text),
+        Front::Bool(bool) => bool.to_string(),
+        Front::List(list) => format!("[{}]", list.iter().map(|x| quote(x)).collect::<Vec<String>>().join(", ")),
+    }
+}
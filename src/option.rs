@@ -129,16 +129,16 @@ impl MDOption {
 }
 
 #[derive(Clone)]
-struct Menu {
-    name: String,
-    path: String,
-    dropdowns: Vec<DropDown>,
+pub(crate) struct Menu {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) dropdowns: Vec<DropDown>,
 }
 
 #[derive(Clone)]
-struct DropDown {
-    name: String,
-    path: String,
+pub(crate) struct DropDown {
+    pub(crate) name: String,
+    pub(crate) path: String,
 }
 
 fn menus_to_html(menus: &Vec<Menu>, md_option: &Option<MDOption>) -> String {
@@ -201,7 +201,7 @@ fn menus_to_html(menus: &Vec<Menu>, md_option: &Option<MDOption>) -> String {
     html
 }
 
-fn table_to_menus(table: &Table) -> Vec<Menu> {
+pub(crate) fn table_to_menus(table: &Table) -> Vec<Menu> {
     let mut menus: Vec<Menu> = vec![];
     for (key, value) in table {
         if let Item::Table(table) = value {
@@ -261,21 +261,21 @@ fn table_to_dropdowns(table: &Table) -> Vec<DropDown> {
 
 #[derive(Clone)]
 pub struct Basic {
-    title: String,
-    created: String,
+    pub(crate) title: String,
+    pub(crate) created: String,
     pub tags: Vec<String>,
 }
 
 #[derive(Clone)]
 pub struct Footer {
-    title: String,
-    snss: Vec<FooterSNS>,
+    pub(crate) title: String,
+    pub(crate) snss: Vec<FooterSNS>,
 }
 
 #[derive(Clone)]
 pub struct FooterSNS {
-    name: String,
-    path: String
+    pub(crate) name: String,
+    pub(crate) path: String
 }
 
 impl FooterSNS {
@@ -336,7 +336,7 @@ fn footer_to_html(footer: &Footer, md_option: &Option<MDOption>) -> String {
     html
 }
 
-fn table_to_footer(table: &Table) -> Footer {
+pub(crate) fn table_to_footer(table: &Table) -> Footer {
     let mut footer = Footer {
         title: "".to_string(),
         snss: vec![],
@@ -382,7 +382,7 @@ pub enum ThemeValue {
     Float(f64),
 }
 
-fn table_to_themes(table: &Table) -> HashMap<String, ThemeValue> {
+pub(crate) fn table_to_themes(table: &Table) -> HashMap<String, ThemeValue> {
     let mut hash_map: HashMap<String, ThemeValue> = HashMap::new();
 
     for (key, value) in table {
@@ -397,7 +397,7 @@ fn table_to_themes(table: &Table) -> HashMap<String, ThemeValue> {
     hash_map
 }
 
-fn table_to_basic(table: &Table) -> Basic {
+pub(crate) fn table_to_basic(table: &Table) -> Basic {
     let mut basic = Basic {
         title: "".to_string(),
         created: "".to_string(),
@@ -2,15 +2,21 @@ use std::fs;
 use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use maplit::hashmap;
-use toml_edit::Table;
+use regex::Regex;
+use toml_edit::{Table, TableLike};
 
 use crate::common::{copy_img_files_to_path, remove_code_indentation};
+use crate::data::is_data_dir;
+use crate::error::{Diagnostic, Error, Result};
 use crate::file::{self, has_stem_dir};
 use crate::html::HTMLView;
-use crate::layout::{self, layouts_from_toml, toml_to_html};
-use crate::markdown::{markdown_wrap_to_html, markdown_wrap_to_htmlview};
+use crate::asset::relative_assets;
+use crate::head::{copy_extra_file, extra_files, page_path, snippet_diagnostics};
+use crate::layout::{self, layouts_from_toml, toml_to_paged_html};
+use crate::tailwind::collect_classes;
+use crate::markdown::{is_draft, markdown_wrap_to_paged_html, metas_table_from_markdown};
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::Formatter;
 use std::fs::File;
 use std::io::{Read, Write};
@@ -20,13 +26,38 @@ pub struct Page {
     path: PathBuf,
     layout_path: PathBuf,
     pub layout_html: String,
+    /// page_size로 나뉜 목록의 2쪽부터, ("page/2", html) 처럼 쓸 폴더와 함께
+    paged_html: Vec<(String, String)>,
     title: String,
     pages: Vec<Page>,
     markdowns: Vec<PathBuf>,
     markdowns_html: HashMap<PathBuf, String>,
+    /// markdown마다 나뉜 목록의 2쪽부터, paged_html과 같다.
+    paged_markdowns_html: HashMap<PathBuf, Vec<(String, String)>>,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl Page {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn layout_path(&self) -> &Path {
+        &self.layout_path
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn pages(&self) -> &Vec<Page> {
+        &self.pages
+    }
+
+    pub fn markdowns(&self) -> &Vec<PathBuf> {
+        &self.markdowns
+    }
+
     pub fn print(&self, depth: usize) -> String {
         let indent = "-".repeat(depth);
         let mut filename = format!("{}{}\n", indent,
@@ -42,29 +73,42 @@ impl Page {
     }
 
     /// Page의 layout_html을 실제 html로 채운다. 
-    pub fn inflate_html(&mut self, layout_tables: HashMap<String, Table>) -> Result<(), String> {
+    pub fn inflate_html(&mut self, layout_tables: HashMap<String, Table>) -> Result<()> {
         let mut layout_tables = layout_tables;
         let layout_path: &Path = self.layout_path.as_ref();
         if let Some(ext) = layout_path.extension() {
             if ext == "md" {
-                let html = markdown_wrap_to_html(layout_path, layout_tables.clone())?;
+                let (html, paged_html) = markdown_wrap_to_paged_html(layout_path, &layout_tables)?;
                 self.layout_html = html;
+                self.paged_html = paged_html;
             } else if ext == "toml" {
+                self.diagnostics.extend(layout::schema::validate_toml(layout_path, &layout_tables));
                 let layout_tables_local = layouts_from_toml(layout_path).unwrap_or(hashmap! {});
                 layout_tables.extend(layout_tables_local);
-                let html = toml_to_html(layout_path, layout_tables.clone())?;
+                let (html, paged_html) = toml_to_paged_html(layout_path, &layout_tables)?;
                 self.layout_html = html;
+                self.paged_html = paged_html;
             }
         }
+        for (_, html) in self.paged_html.iter_mut() {
+            *html = remove_code_indentation(std::mem::take(html));
+        }
         self.layout_html = remove_code_indentation(self.layout_html.clone());
+        let snippet_diagnostics = self.snippet_diagnostics()?;
+        self.diagnostics.extend(snippet_diagnostics);
 
         let mut markdowns_html: HashMap<PathBuf, String> = hashmap! {};
+        let mut paged_markdowns_html: HashMap<PathBuf, Vec<(String, String)>> = hashmap! {};
         for md_path in &self.markdowns {
-            let view = markdown_wrap_to_htmlview(&md_path, layout_tables.clone())?;
-            let html = remove_code_indentation(view.html());
-            markdowns_html.insert(md_path.clone(), html);
+            let (html, paged_html) = markdown_wrap_to_paged_html(md_path, &layout_tables)?;
+            markdowns_html.insert(md_path.clone(), remove_code_indentation(html));
+            if !paged_html.is_empty() {
+                let paged_html = paged_html.into_iter().map(|(dir, html)| (dir, remove_code_indentation(html))).collect();
+                paged_markdowns_html.insert(md_path.clone(), paged_html);
+            }
         }
         self.markdowns_html = markdowns_html; 
+        self.paged_markdowns_html = paged_markdowns_html;
         
         for page in &mut self.pages {
             page.inflate_html(layout_tables.clone())?;
@@ -73,23 +117,121 @@ impl Page {
         Ok(())
     }
 
-    pub fn make_html_file(&mut self, root: &Path) -> Result<(), String> {
-        // self.path의 첫 부분을 root로 교체
-        // 예: self.path = "test/sub1", root = "a/b"
+    /// 빌드 중 모인 경고와 schema 에러, 에러로 멈출지는 Site가 정한다.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.diagnostics.clone();
+        for page in &self.pages {
+            diagnostics.extend(page.diagnostics());
+        }
+        diagnostics
+    }
+
+    /// 만들어진 html들이 쓰는 class들
+    pub fn classes(&self) -> BTreeSet<String> {
+        let mut classes = BTreeSet::new();
+        collect_classes(&self.layout_html, &mut classes);
+        for (_, html) in &self.paged_html {
+            collect_classes(html, &mut classes);
+        }
+        for html in self.markdowns_html.values() {
+            collect_classes(html, &mut classes);
+        }
+        for (_, html) in self.paged_markdowns_html.values().flatten() {
+            collect_classes(html, &mut classes);
+        }
+        for page in &self.pages {
+            classes.extend(page.classes());
+        }
+        classes
+    }
+
+    /// 만들어진 html 중에 text가 들어있는 것이 있는지
+    pub fn contains(&self, text: &str) -> bool {
+        self.layout_html.contains(text)
+            || self.paged_html.iter().any(|(_, html)| html.contains(text))
+            || self.markdowns_html.values().any(|html| html.contains(text))
+            || self.paged_markdowns_html.values().flatten().any(|(_, html)| html.contains(text))
+            || self.pages.iter().any(|page| page.contains(text))
+    }
+
+    /// publish: false 인 markdown과 index.md 폴더를 빼고, 뺀 파일들을 돌려준다.
+    pub fn remove_drafts(&mut self) -> Vec<PathBuf> {
+        let mut drafts = vec![];
+        self.markdowns.retain(|md_path| {
+            let draft = metas_table_from_markdown(md_path).is_ok_and(|metas| is_draft(&metas));
+            if draft {
+                drafts.push(md_path.clone());
+            }
+            !draft
+        });
+        self.pages.retain(|page| {
+            let draft = page.layout_path.extension().is_some_and(|ext| ext == "md")
+                && metas_table_from_markdown(&page.layout_path).is_ok_and(|metas| is_draft(&metas));
+            if draft {
+                drafts.push(page.layout_path.clone());
+            }
+            !draft
+        });
+        for page in &mut self.pages {
+            drafts.extend(page.remove_drafts());
+        }
+        drafts
+    }
+
+    /// "/"로 시작하는 href, src 앞에 base_path를 붙인다.
+    ///
+    /// 사이트가 도메인의 하위 경로에 올라갈 때 쓴다.
+    pub fn prefix_root_links(&mut self, base_path: &str) {
+        let base_path = base_path.trim_end_matches('/');
+        if base_path.is_empty() {
+            return;
+        }
+        let re = Regex::new(r#"(href|src)="/([^/])"#).unwrap();
+        let replace = |html: &str| re.replace_all(html, format!("$1=\"{}/$2", base_path).as_str()).to_string();
+        self.layout_html = replace(&self.layout_html);
+        for (_, html) in self.paged_html.iter_mut() {
+            *html = replace(html);
+        }
+        for html in self.markdowns_html.values_mut() {
+            *html = replace(html);
+        }
+        for (_, html) in self.paged_markdowns_html.values_mut().flatten() {
+            *html = replace(html);
+        }
+        for page in &mut self.pages {
+            page.prefix_root_links(base_path);
+        }
+    }
+
+    /// html 파일들을 root 아래에 쓰고, 쓴 html 파일 경로들을 돌려준다.
+    pub fn make_html_file(&mut self, root: &Path) -> Result<Vec<PathBuf>> {
+        let md_root = self.path.clone();
+        self.make_html_file_under(&md_root, root)
+    }
+
+    fn make_html_file_under(&mut self, md_root: &Path, root: &Path) -> Result<Vec<PathBuf>> {
+        // self.path의 md_root 부분을 root로 교체
+        // 예: self.path = "test/sub1", md_root = "test", root = "a/b"
         // → "a/b/sub1"
-        let relative = self.path.strip_prefix(
-            self.path.components().next().unwrap().as_os_str()
-        ).unwrap_or(self.path.as_path());
+        let relative = self.path.strip_prefix(md_root).unwrap_or(self.path.as_path());
 
         let mut new_path = PathBuf::from(root);
         new_path.push(relative);
 
         // 디렉토리 생성
-        fs::create_dir_all(&new_path).map_err(|e| format!("디렉토리 생성 실패: {e}"))?;
+        fs::create_dir_all(&new_path).map_err(|e| Error::io(&new_path, format!("디렉토리 생성 실패: {e}")))?;
 
         // index.html 경로
+        // bundle된 assets는 폴더 깊이만큼 ../ 를 붙이고, canonical 같은 주소에는 페이지 경로를 채운다.
+        let depth = relative.components().count();
+        let url_path = relative.to_string_lossy().replace('\\', "/");
         let index_file = new_path.join("index.html");
-        fs::write(&index_file, &self.layout_html).map_err(|e| format!("HTML 파일 생성 실패: {e}"))?;
+        let index_html = page_path(&relative_assets(&self.layout_html, depth), &url_path);
+        fs::write(&index_file, index_html).map_err(|e| Error::io(&index_file, format!("HTML 파일 생성 실패: {e}")))?;
+        let mut html_files = vec![index_file];
+
+        // 나뉜 목록의 2쪽부터는 page/{쪽}/index.html 이나 page/{목록 key}/{쪽}/index.html
+        html_files.extend(write_paged_html(&self.paged_html, &new_path, depth, &url_path)?);
 
         // ✅ markdowns 처리
         for md_path in &self.markdowns {
@@ -97,29 +239,95 @@ impl Page {
             let stem = md_path
                 .file_stem()
                 .and_then(|s| s.to_str())
-                .ok_or_else(|| format!("잘못된 파일 이름: {}", md_path.display()))?;
+                .ok_or_else(|| Error::site(md_path, "잘못된 파일 이름"))?;
 
             // stem 디렉토리 생성
             let stem_dir = new_path.join(stem);
             fs::create_dir_all(&stem_dir)
-                .map_err(|e| format!("디렉토리 생성 실패: {e}"))?;
+                .map_err(|e| Error::io(&stem_dir, format!("디렉토리 생성 실패: {e}")))?;
 
             // markdown html 내용 가져오기
             let content = self.markdowns_html.get(md_path)
-                .ok_or_else(|| format!("markdowns_html 에서 {} 내용을 찾을 수 없음", md_path.display()))?;
+                .ok_or_else(|| Error::site(md_path, "markdowns_html 에서 내용을 찾을 수 없음"))?;
 
             // stem/index.html 작성
             let md_index = stem_dir.join("index.html");
-            fs::write(&md_index, content)
-                .map_err(|e| format!("마크다운 HTML 파일 생성 실패: {e}"))?;
+            let md_html = page_path(&relative_assets(content, depth + 1), &format!("{}/{}", url_path, stem));
+            fs::write(&md_index, md_html)
+                .map_err(|e| Error::io(&md_index, format!("마크다운 HTML 파일 생성 실패: {e}")))?;
+            html_files.push(md_index);
+            if let Some(paged_html) = self.paged_markdowns_html.get(md_path) {
+                html_files.extend(write_paged_html(paged_html, &stem_dir, depth + 1, &format!("{}/{}", url_path, stem))?);
+            }
         }
         
         copy_img_files_to_path(self.path.as_path(), new_path.as_path())?;
+        self.copy_extra_files(root)?;
         for page in &mut self.pages {
-            page.make_html_file(root)?;
+            html_files.extend(page.make_html_file_under(md_root, root)?);
+        }
+        Ok(html_files)
+    }
+}
+
+impl Page {
+    /// index.toml [root]와 frontmatter의 extra_css, extra_js 파일을 root/assets 에 복사한다.
+    fn copy_extra_files(&self, root: &Path) -> Result<()> {
+        for source in self.head_sources() {
+            let files = read_head_table(&source, |_, table| extra_files(table))?;
+            let dir = source.parent().unwrap_or(Path::new("."));
+            for file in files {
+                copy_extra_file(&file, dir, root)?;
+            }
         }
         Ok(())
     }
+
+    /// index.toml [root]와 frontmatter의 head_extra, body_end 중 읽을 수 없는 파일들
+    fn snippet_diagnostics(&self) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = vec![];
+        for source in self.head_sources() {
+            let dir = source.parent().unwrap_or(Path::new("."));
+            diagnostics.extend(read_head_table(&source, |key_prefix, table| snippet_diagnostics(table, dir, &source, key_prefix))?);
+        }
+        Ok(diagnostics)
+    }
+
+    /// head 값을 가진 파일들, 이 폴더의 index 파일과 markdown들
+    fn head_sources(&self) -> Vec<PathBuf> {
+        let mut sources = vec![self.layout_path.clone()];
+        sources.extend(self.markdowns.iter().cloned());
+        sources
+    }
+}
+
+/// 나뉜 목록의 2쪽부터를 folder 아래 ("page/2", html)의 폴더에 쓴다.
+///
+/// depth와 url_path는 folder의 것, 쪽 폴더의 깊이만큼 더해서 assets와 {page_path}를 채운다.
+fn write_paged_html(paged_html: &[(String, String)], folder: &Path, depth: usize, url_path: &str) -> Result<Vec<PathBuf>> {
+    let mut html_files = vec![];
+    for (dir, html) in paged_html {
+        let page_dir = folder.join(dir);
+        fs::create_dir_all(&page_dir).map_err(|e| Error::io(&page_dir, format!("디렉토리 생성 실패: {e}")))?;
+        let page_file = page_dir.join("index.html");
+        let page_html = page_path(&relative_assets(html, depth + Path::new(dir).iter().count()), &format!("{}/{}", url_path, dir));
+        fs::write(&page_file, page_html).map_err(|e| Error::io(&page_file, format!("HTML 파일 생성 실패: {e}")))?;
+        html_files.push(page_file);
+    }
+    Ok(html_files)
+}
+
+/// index.toml이면 [root]와 "root.", markdown이면 frontmatter와 ""를 read에 넘긴다.
+fn read_head_table<T: Default>(source: &Path, read: impl Fn(&str, &dyn TableLike) -> T) -> Result<T> {
+    if source.extension().is_some_and(|ext| ext == "toml") {
+        Ok(layout::common::read_document(source)?
+            .get("root")
+            .and_then(|x| x.as_table())
+            .map(|root| read("root.", root))
+            .unwrap_or_default())
+    } else {
+        Ok(metas_table_from_markdown(source).map(|metas| read("", &metas)).unwrap_or_default())
+    }
 }
 
 impl fmt::Display for Page {
@@ -140,7 +348,12 @@ impl fmt::Debug for Page {
 }
 
 /// read files without directory file recursively
-pub fn read_dir_recursive(path: &Path) -> Result<Page, String> {
+pub fn read_dir_recursive(path: &Path) -> Result<Page> {
+    read_dir_in(path, path)
+}
+
+/// root는 사이트의 md 폴더, root의 data 폴더만 건너뛴다.
+fn read_dir_in(path: &Path, root: &Path) -> Result<Page> {
     let this_path = path.to_path_buf();
     let index_path = find_index_path(path)?;
     let title = find_title(path)?;
@@ -151,17 +364,20 @@ pub fn read_dir_recursive(path: &Path) -> Result<Page, String> {
         path: this_path.clone(),
         layout_path: index_path,
         layout_html: String::new(),
+        paged_html: vec![],
+        paged_markdowns_html: hashmap! {},
         title,
         pages: vec![],
         markdowns: markdown_paths,
         markdowns_html: hashmap! {},
+        diagnostics: vec![],
     };
 
-    let paths = fs::read_dir(path).ok().ok_or("")?;
+    let paths = fs::read_dir(path).map_err(|e| Error::io(path, e))?;
     for path in paths.filter_map(|x| x.ok()) {
         let path = path.path();
-        if path.is_dir() {
-            match read_dir_recursive(path.as_path()) {
+        if path.is_dir() && !is_data_dir(root, &path) {
+            match read_dir_in(path.as_path(), root) {
                 Ok(sub_page) => {
                     page.pages.push(sub_page)
                 }
@@ -174,7 +390,7 @@ pub fn read_dir_recursive(path: &Path) -> Result<Page, String> {
     Ok(page)
 }
 
-pub fn find_index_path(path: &Path) -> Result<PathBuf, String> {
+pub fn find_index_path(path: &Path) -> Result<PathBuf> {
     let index_paths = file::find_all_ext_files(path, |ext| {
         ext == "md" || ext == "toml"
     })?;
@@ -192,9 +408,9 @@ pub fn find_index_path(path: &Path) -> Result<PathBuf, String> {
 
     // index.* 파일이 둘 이상 있으면 에러
     if index_candidates.len() > 1 {
-        return Err(format!(
-            "multiple index files detected in {:?}. please keep only one of index.md or index.toml",
-            path
+        return Err(Error::site(
+            path,
+            "multiple index files detected. please keep only one of index.md or index.toml",
         ));
     }
 
@@ -205,13 +421,13 @@ pub fn find_index_path(path: &Path) -> Result<PathBuf, String> {
         let new_index = path.join("index.md");
         if !new_index.exists() {
             fs::write(&new_index, "")
-                .map_err(|e| format!("failed to create {:?}: {}", new_index, e))?;
+                .map_err(|e| Error::io(&new_index, format!("failed to create: {}", e)))?;
         }
         Ok(new_index)
     }
 }
 
-pub fn find_md_paths_except_index(path: &Path) -> Result<Vec<PathBuf>, String> {
+pub fn find_md_paths_except_index(path: &Path) -> Result<Vec<PathBuf>> {
     let md_paths = file::find_all_ext_files(path, |ext| {
        ext == "md"
     })?;
@@ -224,9 +440,9 @@ pub fn find_md_paths_except_index(path: &Path) -> Result<Vec<PathBuf>, String> {
 
     // 디렉토리 이름 모으기
     let mut folder_names = HashSet::new();
-    for entry in std::fs::read_dir(path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let meta = entry.metadata().map_err(|e| e.to_string())?;
+    for entry in std::fs::read_dir(path).map_err(|e| Error::io(path, e))? {
+        let entry = entry.map_err(|e| Error::io(path, e))?;
+        let meta = entry.metadata().map_err(|e| Error::io(&entry.path(), e))?;
         if meta.is_dir() {
             if let Some(name) = entry.file_name().to_str() {
                 folder_names.insert(name.to_string());
@@ -238,10 +454,7 @@ pub fn find_md_paths_except_index(path: &Path) -> Result<Vec<PathBuf>, String> {
     for md in &filtered {
         if let Some(stem) = md.file_stem().and_then(|s| s.to_str()) {
             if folder_names.contains(stem) {
-                return Err(format!(
-                    "Folder name conflicts with markdown file: {}",
-                    md.display()
-                ));
+                return Err(Error::site(md, "Folder name conflicts with markdown file"));
             }
         }
     }
@@ -249,7 +462,7 @@ pub fn find_md_paths_except_index(path: &Path) -> Result<Vec<PathBuf>, String> {
     Ok(filtered)
 }
 
-pub fn make_md_files_to_folder_except_index(path: &Path) -> Result<Vec<PathBuf>, String> {
+pub fn make_md_files_to_folder_except_index(path: &Path) -> Result<Vec<PathBuf>> {
     let md_paths = find_md_paths_except_index(path)?;
     let mut new_paths = Vec::new();
 
@@ -257,22 +470,22 @@ pub fn make_md_files_to_folder_except_index(path: &Path) -> Result<Vec<PathBuf>,
         let stem = md
             .file_stem()
             .and_then(|s| s.to_str())
-            .ok_or_else(|| format!("Invalid file name: {}", md.display()))?;
+            .ok_or_else(|| Error::site(&md, "Invalid file name"))?;
 
         let parent = md.parent().unwrap_or_else(|| Path::new("."));
         let new_dir = parent.join(stem);
         let new_path = new_dir.join("index.md");
 
         fs::create_dir_all(&new_dir)
-            .map_err(|e| format!("Failed to create {:?}: {}", new_dir, e))?;
+            .map_err(|e| Error::io(&new_dir, format!("Failed to create: {}", e)))?;
 
         if new_path.exists() {
             fs::remove_file(&new_path)
-                .map_err(|e| format!("Failed to remove existing {:?}: {}", new_path, e))?;
+                .map_err(|e| Error::io(&new_path, format!("Failed to remove existing: {}", e)))?;
         }
 
         fs::rename(&md, &new_path)
-            .map_err(|e| format!("Failed to move {:?} -> {:?}: {}", md, new_path, e))?;
+            .map_err(|e| Error::io(&md, format!("Failed to move to {:?}: {}", new_path, e)))?;
 
         new_paths.push(new_path);
     }
@@ -280,6 +493,18 @@ pub fn make_md_files_to_folder_except_index(path: &Path) -> Result<Vec<PathBuf>,
     Ok(new_paths)
 }
 
-pub fn find_title(path: &Path) -> Result<String, String> {
-    Ok("".to_string())
+/// index.md의 title, index.toml [root]의 title, 없으면 폴더 이름
+pub fn find_title(path: &Path) -> Result<String> {
+    let index_path = find_index_path(path)?;
+    let title = if index_path.extension().is_some_and(|ext| ext == "md") {
+        metas_table_from_markdown(&index_path)
+            .ok()
+            .and_then(|metas| metas.get("title").and_then(|x| x.as_str()).map(|x| x.to_string()))
+    } else {
+        layout::common::read_document(&index_path)
+            .ok()
+            .and_then(|doc| doc.get("root").and_then(|root| root.get("title")).and_then(|x| x.as_str()).map(|x| x.to_string()))
+    };
+    let folder = path.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+    Ok(title.filter(|x| !x.is_empty()).unwrap_or(folder))
 }
\ No newline at end of file
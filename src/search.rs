@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown::mdast::Node;
+use serde::Serialize;
+
+use crate::asset::ASSETS_DIR;
+use crate::error::{Error, Result};
+use crate::markdown::metas_table_from_markdown;
+use crate::markdown::parser::get_node_for_markdown;
+use crate::page::Page;
+
+/// search shape가 html에 남기는 속성, 이게 있는 사이트는 검색 색인을 만든다.
+pub const SEARCH_MARKER: &str = "data-prema-search";
+
+/// output/assets 에 쓰는 검색 색인
+pub const SEARCH_INDEX: &str = "search.json";
+
+/// output/assets 에 쓰는 검색 스크립트
+pub const SEARCH_SCRIPT: &str = "search.js";
+
+const SEARCH_JS: &str = include_str!("asset/search.js");
+
+/// ### search entry
+/// markdown 페이지 하나의 검색 색인
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchEntry {
+    pub title: String,
+    /// output 폴더 기준 주소, "posts/a/" 처럼 앞에 "/"가 없다.
+    pub url: String,
+    pub headings: Vec<String>,
+    pub tags: Vec<String>,
+    /// 공백을 하나로 줄인 본문
+    pub text: String,
+}
+
+/// ### search entries
+/// page 아래의 모든 markdown 페이지(index.md 포함)의 검색 색인
+pub fn search_entries(page: &Page) -> Result<Vec<SearchEntry>> {
+    let mut entries = vec![];
+    collect_entries(page, page.path(), &mut entries)?;
+    Ok(entries)
+}
+
+fn collect_entries(page: &Page, md_root: &Path, entries: &mut Vec<SearchEntry>) -> Result<()> {
+    let relative = page.path().strip_prefix(md_root).unwrap_or(page.path());
+    let url_path = relative.to_string_lossy().replace('\\', "/");
+    let url = |stem: Option<&str>| {
+        [url_path.as_str(), stem.unwrap_or("")]
+            .iter()
+            .filter(|x| !x.is_empty())
+            .map(|x| format!("{}/", x))
+            .collect::<String>()
+    };
+
+    let layout_path = page.layout_path();
+    if layout_path.extension().is_some_and(|ext| ext == "md") && !is_empty_file(layout_path) {
+        entries.push(search_entry(layout_path, url(None), page.title())?);
+    }
+    for md_path in page.markdowns() {
+        let stem = md_path.file_stem().and_then(|x| x.to_str()).unwrap_or("");
+        entries.push(search_entry(md_path, url(Some(stem)), stem)?);
+    }
+    for sub_page in page.pages() {
+        collect_entries(sub_page, md_root, entries)?;
+    }
+    Ok(())
+}
+
+/// frontmatter의 title, 없으면 첫 제목, 그것도 없으면 default_title
+fn search_entry(md_path: &Path, url: String, default_title: &str) -> Result<SearchEntry> {
+    let metas = metas_table_from_markdown(md_path).unwrap_or_default();
+    let node = get_node_for_markdown(md_path)?;
+
+    let mut headings = vec![];
+    let mut texts = vec![];
+    collect_text(&node, &mut headings, &mut texts);
+    let tags = match metas.get("tags") {
+        Some(tags) if tags.is_array() => tags
+            .as_array()
+            .map(|x| x.iter().filter_map(|x| x.as_str()).map(|x| x.to_string()).collect())
+            .unwrap_or_default(),
+        Some(tags) => tags.as_str().map(|x| vec![x.to_string()]).unwrap_or_default(),
+        None => vec![],
+    };
+    let title = metas
+        .get("title")
+        .and_then(|x| x.as_str())
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+        .or_else(|| headings.first().cloned())
+        .unwrap_or_else(|| default_title.to_string());
+
+    Ok(SearchEntry {
+        title,
+        url,
+        headings,
+        tags,
+        text: texts.join(" ").split_whitespace().collect::<Vec<&str>>().join(" "),
+    })
+}
+
+/// 제목은 headings에, 나머지 글자는 texts에 모은다.
+fn collect_text(node: &Node, headings: &mut Vec<String>, texts: &mut Vec<String>) {
+    match node {
+        Node::Heading(_) => {
+            let heading = node.to_string().trim().to_string();
+            if !heading.is_empty() {
+                headings.push(heading);
+            }
+        }
+        Node::Text(text) => texts.push(text.value.clone()),
+        Node::InlineCode(code) => texts.push(code.value.clone()),
+        Node::Code(code) => texts.push(code.value.clone()),
+        Node::Html(_) | Node::Yaml(_) | Node::Toml(_) => {}
+        _ => {
+            for child in node.children().into_iter().flatten() {
+                collect_text(child, headings, texts);
+            }
+        }
+    }
+}
+
+fn is_empty_file(path: &Path) -> bool {
+    fs::read_to_string(path).map(|x| x.trim().is_empty()).unwrap_or(true)
+}
+
+/// ### write search index
+/// 검색 색인과 스크립트를 output/assets 에 쓴다.
+pub fn write_search_index(output: &Path, entries: &[SearchEntry]) -> Result<PathBuf> {
+    let assets_dir = output.join(ASSETS_DIR);
+    fs::create_dir_all(&assets_dir).map_err(|e| Error::io(&assets_dir, e))?;
+    let target = assets_dir.join(SEARCH_INDEX);
+    let json = serde_json::to_string(entries).map_err(|e| Error::io(&target, e))?;
+    fs::write(&target, json).map_err(|e| Error::io(&target, e))?;
+    let script = assets_dir.join(SEARCH_SCRIPT);
+    fs::write(&script, SEARCH_JS).map_err(|e| Error::io(&script, e))?;
+    Ok(target)
+}
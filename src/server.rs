@@ -7,8 +7,10 @@ use tiny_http::{Header, Response, Server};
 use structopt::StructOpt;
 use chrono::Local;
 
+use crate::error::Result;
 
-pub fn run_server(html_path: &Path) -> Result<(), &'static str> {
+
+pub fn run_server(html_path: &Path) -> Result<()> {
     let server = Server::http("127.0.0.1:3000").unwrap();
     println!("서버 실행 중: http://127.0.0.1:3000");
 
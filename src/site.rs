@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use toml_edit::{value, Array, Item, Table};
+
+use crate::config::{relative_to, Config};
+use crate::data::{load_data, DATA_DIR, DATA_KEY};
+use crate::asset::{bundle_assets, write_tailwind, ASSETS_KEY, TAILWIND_CSS};
+use crate::error::{Diagnostic, Error, Result, Severity};
+use crate::head::{copy_extra_file, read_snippet};
+use crate::layout::common::SITE_TABLE;
+use crate::page;
+use crate::search::{search_entries, write_search_index, SEARCH_MARKER};
+
+/// ### site
+/// 폴더 하나를 html 사이트로 만드는 진입점
+///
+/// ```no_run
+/// let report = prema::Site::new("md").output("html").build()?;
+/// # Ok::<(), prema::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Site {
+    input: PathBuf,
+    output: PathBuf,
+    base_url: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    lang: Option<String>,
+    image: Option<String>,
+    dark: Option<bool>,
+    header: Option<String>,
+    footer: Option<String>,
+    plugins: Table,
+    head_extra: Vec<String>,
+    body_end: Vec<String>,
+    extra_css: Vec<String>,
+    extra_js: Vec<String>,
+    drafts: bool,
+    assets: Option<PathBuf>,
+    tailwind: bool,
+    search: bool,
+    strict: bool,
+    /// prema.toml을 읽으며 생긴 경고
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// build 결과
+#[derive(Debug, Clone)]
+pub struct BuildReport {
+    /// 만들어진 html 파일들
+    pub pages: Vec<PathBuf>,
+    /// publish: false 라서 빠진 markdown 파일들
+    pub drafts: Vec<PathBuf>,
+    /// 빌드를 멈추지 않은 경고들
+    pub diagnostics: Vec<Diagnostic>,
+    pub elapsed: Duration,
+}
+
+impl Site {
+    /// output은 기본으로 현재 폴더의 html
+    pub fn new(input: impl AsRef<Path>) -> Site {
+        Site {
+            input: input.as_ref().to_path_buf(),
+            output: PathBuf::from("html"),
+            base_url: None,
+            title: None,
+            description: None,
+            lang: None,
+            image: None,
+            dark: None,
+            header: None,
+            footer: None,
+            plugins: Table::new(),
+            head_extra: vec![],
+            body_end: vec![],
+            extra_css: vec![],
+            extra_js: vec![],
+            drafts: false,
+            assets: None,
+            tailwind: false,
+            search: false,
+            strict: false,
+            diagnostics: vec![],
+        }
+    }
+
+    /// ### from config
+    /// input 폴더에 prema.toml이 있으면 그 설정으로 시작하는 Site, 없으면 Site::new와 같다.
+    ///
+    /// 뒤에 부르는 builder 메서드가 설정 파일의 값을 덮어쓴다.
+    pub fn from_config(input: impl AsRef<Path>) -> Result<Site> {
+        let site = Site::new(input.as_ref());
+        match Config::find(input.as_ref()) {
+            Some(path) => site.config(path),
+            None => Ok(site),
+        }
+    }
+
+    /// 설정 파일의 값을 적용한다.
+    pub fn config(mut self, path: impl AsRef<Path>) -> Result<Site> {
+        let config = Config::load(path.as_ref())?;
+        self.base_url = config.base_url.or(self.base_url);
+        self.title = config.title.or(self.title);
+        self.description = config.description.or(self.description);
+        self.lang = config.lang.or(self.lang);
+        self.image = config.image.or(self.image);
+        self.output = config.output.unwrap_or(self.output);
+        self.drafts = config.drafts.unwrap_or(self.drafts);
+        self.assets = config.assets.or(self.assets);
+        self.tailwind = config.tailwind.unwrap_or(self.tailwind);
+        self.search = config.search.unwrap_or(self.search);
+        self.dark = config.dark.or(self.dark);
+        self.header = config.header.or(self.header);
+        self.footer = config.footer.or(self.footer);
+        self.head_extra.extend(config.head_extra);
+        self.body_end.extend(config.body_end);
+        self.extra_css.extend(config.extra_css);
+        self.extra_js.extend(config.extra_js);
+        self.plugins.extend(config.plugins);
+        self.diagnostics.extend(config.diagnostics);
+        Ok(self)
+    }
+
+    pub fn output(mut self, output: impl AsRef<Path>) -> Site {
+        self.output = output.as_ref().to_path_buf();
+        self
+    }
+
+    pub fn output_dir(&self) -> &Path {
+        &self.output
+    }
+
+    /// `https://example.com/blog/` 처럼 하위 경로가 있으면 "/"로 시작하는 링크 앞에 "/blog"를 붙인다.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Site {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// 사이트 이름, 페이지 title 뒤에 "| title" 로 붙고 og:site_name 이 된다.
+    pub fn title(mut self, title: impl Into<String>) -> Site {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// description이 없는 페이지가 쓰는 기본 description
+    pub fn description(mut self, description: impl Into<String>) -> Site {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// lang이 없는 페이지의 `<html lang>`
+    pub fn lang(mut self, lang: impl Into<String>) -> Site {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// image가 없는 페이지의 og:image
+    pub fn image(mut self, image: impl Into<String>) -> Site {
+        self.image = Some(image.into());
+        self
+    }
+
+    /// dark가 없는 root layout의 기본값
+    pub fn dark(mut self, dark: bool) -> Site {
+        self.dark = Some(dark);
+        self
+    }
+
+    /// frontmatter에 header가 없는 markdown이 쓰는 layout
+    pub fn header(mut self, layout: impl Into<String>) -> Site {
+        self.header = Some(layout.into());
+        self
+    }
+
+    /// frontmatter에 footer가 없는 markdown이 쓰는 layout
+    pub fn footer(mut self, layout: impl Into<String>) -> Site {
+        self.footer = Some(layout.into());
+        self
+    }
+
+    /// 사용자 shape 같은 plugin 설정, [site] table의 plugins로 물려준다.
+    pub fn plugins(mut self, plugins: Table) -> Site {
+        self.plugins = plugins;
+        self
+    }
+
+    /// 모든 페이지의 head 끝에 넣는 html, 파일 경로면 input 폴더 기준 그 파일의 내용을 넣는다.
+    pub fn head_extra(mut self, html: impl Into<String>) -> Site {
+        self.head_extra.push(relative_to(&self.input, &html.into()));
+        self
+    }
+
+    /// 모든 페이지의 body 끝에 넣는 html, analytics 스크립트 같은 것
+    pub fn body_end(mut self, html: impl Into<String>) -> Site {
+        self.body_end.push(relative_to(&self.input, &html.into()));
+        self
+    }
+
+    /// 모든 페이지가 부르는 css, input 폴더 기준 파일이면 output/assets 에 복사한다.
+    pub fn extra_css(mut self, path: impl Into<String>) -> Site {
+        self.extra_css.push(relative_to(&self.input, &path.into()));
+        self
+    }
+
+    /// 모든 페이지가 부르는 js, input 폴더 기준 파일이면 output/assets 에 복사한다.
+    pub fn extra_js(mut self, path: impl Into<String>) -> Site {
+        self.extra_js.push(relative_to(&self.input, &path.into()));
+        self
+    }
+
+    /// true면 publish: false 인 markdown도 만든다.
+    pub fn drafts(mut self, drafts: bool) -> Site {
+        self.drafts = drafts;
+        self
+    }
+
+    /// CDN 대신 vendor 폴더의 tailwind, flowbite, highlight.js를 output/assets 에 복사해서 쓴다.
+    ///
+    /// vendor 폴더에 tailwind.css가 있으면 tailwind.js 대신 그 css를 쓴다.
+    pub fn assets(mut self, vendor: impl AsRef<Path>) -> Site {
+        self.assets = Some(vendor.as_ref().to_path_buf());
+        self
+    }
+
+    /// true면 tailwind CDN 대신 사이트가 쓰는 class만 담은 assets/tailwind.css를 만들어 쓴다.
+    pub fn tailwind(mut self, tailwind: bool) -> Site {
+        self.tailwind = tailwind;
+        self
+    }
+
+    /// true면 search shape가 없어도 assets/search.json 검색 색인을 만든다.
+    pub fn search(mut self, search: bool) -> Site {
+        self.search = search;
+        self
+    }
+
+    /// true면 index.toml의 schema 에러가 빌드를 멈춘다. 기본은 경고로 알리고 계속 만든다.
+    pub fn strict(mut self, strict: bool) -> Site {
+        self.strict = strict;
+        self
+    }
+
+    pub fn build(&self) -> Result<BuildReport> {
+        let started = Instant::now();
+        let mut page = page::read_dir_recursive(&self.input)?;
+        let drafts = if self.drafts { vec![] } else { page.remove_drafts() };
+
+        let mut diagnostics = self.diagnostics.clone();
+        let mut layout_tables = self.layout_tables(&mut diagnostics)?;
+        let mut integrities = match &self.assets {
+            Some(vendor) => bundle_assets(vendor, &self.output, !self.tailwind)?,
+            None => Table::new(),
+        };
+        if self.tailwind {
+            // 빌드가 끝나야 내용이 정해지므로 integrity는 비워 둔다.
+            integrities.insert(TAILWIND_CSS.file, value(""));
+        }
+        if !integrities.is_empty() {
+            if let Some(site) = layout_tables.get_mut(SITE_TABLE) {
+                site.insert(ASSETS_KEY, Item::Table(integrities));
+            }
+        }
+        page.inflate_html(layout_tables)?;
+        let page_diagnostics = page.diagnostics();
+        if self.strict && page_diagnostics.iter().any(|x| x.is_error()) {
+            return Err(Error::Diagnostics(page_diagnostics));
+        }
+        if let Some(base_url) = &self.base_url {
+            page.prefix_root_links(base_path(base_url));
+        }
+        let pages = page.make_html_file(&self.output)?;
+        if self.tailwind {
+            diagnostics.extend(write_tailwind(&self.output, &page.classes())?);
+        }
+        if self.search || page.contains(SEARCH_MARKER) {
+            write_search_index(&self.output, &search_entries(&page)?)?;
+        }
+
+        // strict가 아니면 예전처럼 그리고, 에러는 경고로 알린다.
+        diagnostics.extend(page.diagnostics().into_iter().map(|x| Diagnostic { severity: Severity::Warning, ..x }));
+        Ok(BuildReport {
+            pages,
+            drafts,
+            diagnostics,
+            elapsed: started.elapsed(),
+        })
+    }
+
+    /// 빌드 옵션을 [site] table로 만들어 layout들에 물려준다.
+    ///
+    /// head_extra, body_end 파일은 읽어 두고, extra_css, extra_js 파일은 output/assets 에 복사한다.
+    /// 읽을 수 없는 head_extra, body_end 파일은 diagnostics에 경고로 남긴다.
+    fn layout_tables(&self, diagnostics: &mut Vec<Diagnostic>) -> Result<HashMap<String, Table>> {
+        let mut site = Table::new();
+        site.insert("drafts", value(self.drafts));
+        let options = [
+            ("base_url", &self.base_url),
+            ("title", &self.title),
+            ("description", &self.description),
+            ("lang", &self.lang),
+            ("image", &self.image),
+            ("header", &self.header),
+            ("footer", &self.footer),
+        ];
+        for (key, option) in options {
+            if let Some(option) = option {
+                site.insert(key, value(option.as_str()));
+            }
+        }
+        if let Some(dark) = self.dark {
+            site.insert("dark", value(dark));
+        }
+        if !self.plugins.is_empty() {
+            site.insert("plugins", Item::Table(self.plugins.clone()));
+        }
+        // 경로는 builder와 prema.toml이 이미 input 폴더, prema.toml 폴더 기준으로 바꿔 두었다.
+        let dir = Path::new("");
+        for (key, htmls) in [("head_extra", &self.head_extra), ("body_end", &self.body_end)] {
+            let mut snippets = Array::new();
+            for html in htmls {
+                match read_snippet(html, dir) {
+                    Ok(snippet) => snippets.push(snippet),
+                    Err(e) => diagnostics.push(Diagnostic::warning(e.path().unwrap_or(&self.input), key, format!("cannot read snippet {}", e))),
+                }
+            }
+            site.insert(key, value(snippets));
+        }
+        for (key, paths) in [("extra_css", &self.extra_css), ("extra_js", &self.extra_js)] {
+            let mut urls = Array::new();
+            for path in paths {
+                urls.push(copy_extra_file(path, dir, &self.output)?);
+            }
+            site.insert(key, value(urls));
+        }
+        let mut layout_tables = HashMap::from([(SITE_TABLE.to_string(), site)]);
+        // input/data 의 파일들은 한 번만 읽어서 {data.*} 와 list의 data로 쓴다.
+        let data = load_data(&self.input.join(DATA_DIR))?;
+        if !data.is_empty() {
+            layout_tables.insert(DATA_KEY.to_string(), data.into_table());
+        }
+        Ok(layout_tables)
+    }
+}
+
+/// `https://example.com/blog/` -> "/blog/"
+fn base_path(base_url: &str) -> &str {
+    match base_url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|i| &rest[i..]).unwrap_or(""),
+        None => base_url,
+    }
+}
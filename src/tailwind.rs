@@ -0,0 +1,771 @@
+use std::collections::BTreeSet;
+
+use regex::Regex;
+
+/// tailwind의 기본 breakpoint, min-width
+const SCREENS: [(&str, &str); 5] = [("sm", "640px"), ("md", "768px"), ("lg", "1024px"), ("xl", "1280px"), ("2xl", "1536px")];
+
+/// p-4, w-10 처럼 0.25rem 단위로 쓸 수 있는 값
+const SPACING: [&str; 34] = [
+    "0", "0.5", "1", "1.5", "2", "2.5", "3", "3.5", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+    "14", "16", "20", "24", "28", "32", "36", "40", "44", "48", "52", "56", "60", "64", "72", "80", "96",
+];
+
+const FONT_SIZES: [(&str, &str, &str); 13] = [
+    ("xs", "0.75rem", "1rem"), ("sm", "0.875rem", "1.25rem"), ("base", "1rem", "1.5rem"), ("lg", "1.125rem", "1.75rem"),
+    ("xl", "1.25rem", "1.75rem"), ("2xl", "1.5rem", "2rem"), ("3xl", "1.875rem", "2.25rem"), ("4xl", "2.25rem", "2.5rem"),
+    ("5xl", "3rem", "1"), ("6xl", "3.75rem", "1"), ("7xl", "4.5rem", "1"), ("8xl", "6rem", "1"), ("9xl", "8rem", "1"),
+];
+
+const FONT_WEIGHTS: [(&str, &str); 9] = [
+    ("thin", "100"), ("extralight", "200"), ("light", "300"), ("normal", "400"), ("medium", "500"),
+    ("semibold", "600"), ("bold", "700"), ("extrabold", "800"), ("black", "900"),
+];
+
+const FONT_FAMILIES: [(&str, &str); 3] = [
+    ("sans", r#"ui-sans-serif, system-ui, sans-serif, "Apple Color Emoji", "Segoe UI Emoji", "Segoe UI Symbol", "Noto Color Emoji""#),
+    ("serif", r#"ui-serif, Georgia, Cambria, "Times New Roman", Times, serif"#),
+    ("mono", r#"ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, "Liberation Mono", "Courier New", monospace"#),
+];
+
+const MAX_WIDTHS: [(&str, &str); 17] = [
+    ("none", "none"), ("xs", "20rem"), ("sm", "24rem"), ("md", "28rem"), ("lg", "32rem"), ("xl", "36rem"),
+    ("2xl", "42rem"), ("3xl", "48rem"), ("4xl", "56rem"), ("5xl", "64rem"), ("6xl", "72rem"), ("7xl", "80rem"),
+    ("prose", "65ch"), ("screen-sm", "640px"), ("screen-md", "768px"), ("screen-lg", "1024px"), ("screen-xl", "1280px"),
+];
+
+const LEADINGS: [(&str, &str); 14] = [
+    ("3", ".75rem"), ("4", "1rem"), ("5", "1.25rem"), ("6", "1.5rem"), ("7", "1.75rem"), ("8", "2rem"), ("9", "2.25rem"), ("10", "2.5rem"),
+    ("none", "1"), ("tight", "1.25"), ("snug", "1.375"), ("normal", "1.5"), ("relaxed", "1.625"), ("loose", "2"),
+];
+
+const TRACKINGS: [(&str, &str); 6] = [
+    ("tighter", "-0.05em"), ("tight", "-0.025em"), ("normal", "0em"), ("wide", "0.025em"), ("wider", "0.05em"), ("widest", "0.1em"),
+];
+
+const RADII: [(&str, &str); 9] = [
+    ("", "0.25rem"), ("none", "0px"), ("sm", "0.125rem"), ("md", "0.375rem"), ("lg", "0.5rem"),
+    ("xl", "0.75rem"), ("2xl", "1rem"), ("3xl", "1.5rem"), ("full", "9999px"),
+];
+
+const SHADOWS: [(&str, &str); 8] = [
+    ("", "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)"),
+    ("sm", "0 1px 2px 0 rgb(0 0 0 / 0.05)"),
+    ("md", "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)"),
+    ("lg", "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)"),
+    ("xl", "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)"),
+    ("2xl", "0 25px 50px -12px rgb(0 0 0 / 0.25)"),
+    ("inner", "inset 0 2px 4px 0 rgb(0 0 0 / 0.05)"),
+    ("none", "0 0 #0000"),
+];
+
+const SHADES: [&str; 11] = ["50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950"];
+
+/// tailwind v3 기본 색, SHADES 순서
+const PALETTE: [(&str, [&str; 11]); 22] = [
+    ("slate", ["#f8fafc", "#f1f5f9", "#e2e8f0", "#cbd5e1", "#94a3b8", "#64748b", "#475569", "#334155", "#1e293b", "#0f172a", "#020617"]),
+    ("gray", ["#f9fafb", "#f3f4f6", "#e5e7eb", "#d1d5db", "#9ca3af", "#6b7280", "#4b5563", "#374151", "#1f2937", "#111827", "#030712"]),
+    ("zinc", ["#fafafa", "#f4f4f5", "#e4e4e7", "#d4d4d8", "#a1a1aa", "#71717a", "#52525b", "#3f3f46", "#27272a", "#18181b", "#09090b"]),
+    ("neutral", ["#fafafa", "#f5f5f5", "#e5e5e5", "#d4d4d4", "#a3a3a3", "#737373", "#525252", "#404040", "#262626", "#171717", "#0a0a0a"]),
+    ("stone", ["#fafaf9", "#f5f5f4", "#e7e5e4", "#d6d3d1", "#a8a29e", "#78716c", "#57534e", "#44403c", "#292524", "#1c1917", "#0c0a09"]),
+    ("red", ["#fef2f2", "#fee2e2", "#fecaca", "#fca5a5", "#f87171", "#ef4444", "#dc2626", "#b91c1c", "#991b1b", "#7f1d1d", "#450a0a"]),
+    ("orange", ["#fff7ed", "#ffedd5", "#fed7aa", "#fdba74", "#fb923c", "#f97316", "#ea580c", "#c2410c", "#9a3412", "#7c2d12", "#431407"]),
+    ("amber", ["#fffbeb", "#fef3c7", "#fde68a", "#fcd34d", "#fbbf24", "#f59e0b", "#d97706", "#b45309", "#92400e", "#78350f", "#451a03"]),
+    ("yellow", ["#fefce8", "#fef9c3", "#fef08a", "#fde047", "#facc15", "#eab308", "#ca8a04", "#a16207", "#854d0e", "#713f12", "#422006"]),
+    ("lime", ["#f7fee7", "#ecfccb", "#d9f99d", "#bef264", "#a3e635", "#84cc16", "#65a30d", "#4d7c0f", "#3f6212", "#365314", "#1a2e05"]),
+    ("green", ["#f0fdf4", "#dcfce7", "#bbf7d0", "#86efac", "#4ade80", "#22c55e", "#16a34a", "#15803d", "#166534", "#14532d", "#052e16"]),
+    ("emerald", ["#ecfdf5", "#d1fae5", "#a7f3d0", "#6ee7b7", "#34d399", "#10b981", "#059669", "#047857", "#065f46", "#064e3b", "#022c22"]),
+    ("teal", ["#f0fdfa", "#ccfbf1", "#99f6e4", "#5eead4", "#2dd4bf", "#14b8a6", "#0d9488", "#0f766e", "#115e59", "#134e4a", "#042f2e"]),
+    ("cyan", ["#ecfeff", "#cffafe", "#a5f3fc", "#67e8f9", "#22d3ee", "#06b6d4", "#0891b2", "#0e7490", "#155e75", "#164e63", "#083344"]),
+    ("sky", ["#f0f9ff", "#e0f2fe", "#bae6fd", "#7dd3fc", "#38bdf8", "#0ea5e9", "#0284c7", "#0369a1", "#075985", "#0c4a6e", "#082f49"]),
+    ("blue", ["#eff6ff", "#dbeafe", "#bfdbfe", "#93c5fd", "#60a5fa", "#3b82f6", "#2563eb", "#1d4ed8", "#1e40af", "#1e3a8a", "#172554"]),
+    ("indigo", ["#eef2ff", "#e0e7ff", "#c7d2fe", "#a5b4fc", "#818cf8", "#6366f1", "#4f46e5", "#4338ca", "#3730a3", "#312e81", "#1e1b4b"]),
+    ("violet", ["#f5f3ff", "#ede9fe", "#ddd6fe", "#c4b5fd", "#a78bfa", "#8b5cf6", "#7c3aed", "#6d28d9", "#5b21b6", "#4c1d95", "#2e1065"]),
+    ("purple", ["#faf5ff", "#f3e8ff", "#e9d5ff", "#d8b4fe", "#c084fc", "#a855f7", "#9333ea", "#7e22ce", "#6b21a8", "#581c87", "#3b0764"]),
+    ("fuchsia", ["#fdf4ff", "#fae8ff", "#f5d0fe", "#f0abfc", "#e879f9", "#d946ef", "#c026d3", "#a21caf", "#86198f", "#701a75", "#4a044e"]),
+    ("pink", ["#fdf2f8", "#fce7f3", "#fbcfe8", "#f9a8d4", "#f472b6", "#ec4899", "#db2777", "#be185d", "#9d174d", "#831843", "#500724"]),
+    ("rose", ["#fff1f2", "#ffe4e6", "#fecdd3", "#fda4af", "#fb7185", "#f43f5e", "#e11d48", "#be123c", "#9f1239", "#881337", "#4c0519"]),
+];
+
+/// 자식 사이 간격, space-x-4 나 divide-y 가 쓰는 selector
+const CHILDREN: &str = " > :not([hidden]) ~ :not([hidden])";
+
+/// tailwind preflight 중 prema가 그리는 태그에 필요한 부분
+const PREFLIGHT: &str = r#"*,::before,::after{box-sizing:border-box;border-width:0;border-style:solid;border-color:#e5e7eb}
+html{line-height:1.5;-webkit-text-size-adjust:100%;tab-size:4;font-family:ui-sans-serif, system-ui, sans-serif, "Apple Color Emoji", "Segoe UI Emoji", "Segoe UI Symbol", "Noto Color Emoji"}
+body{margin:0;line-height:inherit}
+hr{height:0;color:inherit;border-top-width:1px}
+h1,h2,h3,h4,h5,h6{font-size:inherit;font-weight:inherit}
+a{color:inherit;text-decoration:inherit}
+b,strong{font-weight:bolder}
+code,kbd,samp,pre{font-family:ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, "Liberation Mono", "Courier New", monospace;font-size:1em}
+table{text-indent:0;border-color:inherit;border-collapse:collapse}
+button,input,select,textarea{font-family:inherit;font-size:100%;font-weight:inherit;line-height:inherit;color:inherit;margin:0;padding:0}
+button{background-color:transparent;background-image:none;cursor:pointer}
+blockquote,dl,dd,h1,h2,h3,h4,h5,h6,hr,figure,p,pre{margin:0}
+ol,ul,menu{list-style:none;margin:0;padding:0}
+img,svg,video,canvas,audio,iframe,embed,object{display:block;vertical-align:middle}
+img,video{max-width:100%;height:auto}
+[hidden]{display:none}
+"#;
+
+/// class 하나가 만드는 css
+struct Utility {
+    /// 같은 속성을 덮어쓰는 class끼리의 순서, 큰 쪽이 뒤에 온다.
+    order: u16,
+    declarations: Vec<(String, String)>,
+    /// space-x-4 처럼 자식에게 적용되는지
+    children: bool,
+}
+
+impl Utility {
+    fn new(order: u16, declarations: Vec<(&str, String)>) -> Option<Utility> {
+        Some(Utility {
+            order,
+            declarations: declarations.into_iter().map(|(p, v)| (p.to_string(), v)).collect(),
+            children: false,
+        })
+    }
+
+    fn one(order: u16, property: &str, value: impl Into<String>) -> Option<Utility> {
+        Utility::new(order, vec![(property, value.into())])
+    }
+}
+
+/// css 한 줄과 그 위치
+struct Rule {
+    /// breakpoint, dark, pseudo, utility order, class 이름 순서로 정렬한다.
+    key: (usize, bool, bool, u16, String),
+    media: Vec<String>,
+    css: String,
+}
+
+/// html 안의 class="..." 에서 class들을 모은다.
+pub fn collect_classes(html: &str, classes: &mut BTreeSet<String>) {
+    let re = Regex::new(r#"class="([^"]*)""#).unwrap();
+    for caps in re.captures_iter(html) {
+        classes.extend(caps[1].split_whitespace().map(|x| x.to_string()));
+    }
+}
+
+/// ### generate css
+/// 쓰인 class들만 담은 tailwind css를 만든다.
+///
+/// md:, dark:, hover: 같은 variant와 w-[200px] 같은 임의 값도 다룬다.
+/// 모르는 class는 css에서 빠지고, 경고로 쓸 수 있게 따로 돌려준다.
+pub fn generate_css(classes: &BTreeSet<String>) -> (String, Vec<String>) {
+    let mut rules = vec![];
+    let mut unsupported = vec![];
+    for class in classes {
+        match class_rule(class) {
+            Some(rule) => rules.push(rule),
+            None if !is_prema_class(class) => unsupported.push(class.clone()),
+            None => {}
+        }
+    }
+    // variant 없는 것, hover 같은 pseudo, dark, breakpoint 순서
+    rules.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut css = PREFLIGHT.to_string();
+    for rule in rules {
+        if rule.media.is_empty() {
+            css.push_str(&format!("{}\n", rule.css));
+        } else {
+            css.push_str(&format!("@media {}{{{}}}\n", rule.media.join(" and "), rule.css));
+        }
+    }
+    (css, unsupported)
+}
+
+/// utility가 아니지만 prema가 쓰는 class, 경고하지 않는다.
+///
+/// language-* 는 highlight.js가 읽는다. text의 정렬 기본값인 justify-left, items-top 같은 것은
+/// tailwind CDN에서도 아무 일을 하지 않는다.
+fn is_prema_class(class: &str) -> bool {
+    let name = split_variants(class).pop().unwrap_or(class);
+    name.starts_with("language-") || ["justify-left", "justify-right", "items-top", "items-bottom"].contains(&name)
+}
+
+fn class_rule(class: &str) -> Option<Rule> {
+    let mut parts = split_variants(class);
+    let name = parts.pop()?;
+    let utility = utility(name)?;
+
+    let mut selector = format!(".{}", escape(class));
+    let mut media = vec![];
+    let mut screen = 0;
+    let mut dark = false;
+    let mut pseudo = false;
+    for variant in parts {
+        if let Some(index) = SCREENS.iter().position(|(x, _)| *x == variant) {
+            screen = screen.max(index + 1);
+            media.push(format!("(min-width: {})", SCREENS[index].1));
+            continue;
+        }
+        match variant {
+            "dark" => {
+                dark = true;
+                media.push("(prefers-color-scheme: dark)".to_string());
+            }
+            "rtl" => selector.push_str(r#":where([dir="rtl"], [dir="rtl"] *)"#),
+            "ltr" => selector.push_str(r#":where([dir="ltr"], [dir="ltr"] *)"#),
+            "hover" | "focus" | "active" | "disabled" | "visited" | "focus-within" | "focus-visible" => {
+                pseudo = true;
+                selector.push_str(&format!(":{}", variant));
+            }
+            "first" | "last" => {
+                pseudo = true;
+                selector.push_str(&format!(":{}-child", variant));
+            }
+            _ => return None,
+        }
+    }
+    if utility.children {
+        selector.push_str(CHILDREN);
+    }
+    let body = utility
+        .declarations
+        .iter()
+        .map(|(p, v)| format!("{}:{}", p, v))
+        .collect::<Vec<String>>()
+        .join(";");
+    Some(Rule {
+        key: (screen, dark, pseudo, utility.order, class.to_string()),
+        media,
+        css: format!("{}{{{}}}", selector, body),
+    })
+}
+
+/// "md:dark:hover:bg-[#fff]" -> ["md", "dark", "hover", "bg-[#fff]"], [] 안의 : 는 나누지 않는다.
+fn split_variants(class: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ':' if depth == 0 => {
+                parts.push(&class[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&class[start..]);
+    parts
+}
+
+/// css selector 안에서 쓸 수 있게 특수문자 앞에 \ 를 붙인다.
+fn escape(class: &str) -> String {
+    let mut escaped = String::new();
+    for (i, c) in class.chars().enumerate() {
+        if i == 0 && c.is_ascii_digit() {
+            escaped.push_str(&format!("\\3{} ", c));
+        } else if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+            escaped.push(c);
+        } else {
+            escaped.push('\\');
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// [200px] -> 200px, 안의 _ 는 공백
+fn arbitrary(value: &str) -> Option<String> {
+    value
+        .strip_prefix('[')
+        .and_then(|x| x.strip_suffix(']'))
+        .map(|x| x.replace('_', " "))
+}
+
+fn is_length(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-')
+        || value.starts_with("calc(")
+        || value.starts_with("min(")
+        || value.starts_with("max(")
+        || value.starts_with("clamp(")
+}
+
+/// 4 -> 1rem, px -> 1px, [10px] -> 10px
+fn spacing(value: &str) -> Option<String> {
+    if let Some(value) = arbitrary(value) {
+        return Some(value);
+    }
+    match value {
+        "px" => Some("1px".to_string()),
+        "0" => Some("0px".to_string()),
+        _ if SPACING.contains(&value) => value.parse::<f64>().ok().map(|n| format!("{}rem", n * 0.25)),
+        _ => None,
+    }
+}
+
+/// 1/2 -> 50%
+fn fraction(value: &str) -> Option<String> {
+    let (a, b) = value.split_once('/')?;
+    let a = a.parse::<f64>().ok()?;
+    let b = b.parse::<f64>().ok()?;
+    if b == 0.0 || a >= b {
+        return None;
+    }
+    let percent = format!("{:.6}", a / b * 100.0);
+    Some(format!("{}%", percent.trim_end_matches('0').trim_end_matches('.')))
+}
+
+/// spacing, 분수, auto, full
+fn inset(value: &str) -> Option<String> {
+    match value {
+        "auto" => Some("auto".to_string()),
+        "full" => Some("100%".to_string()),
+        _ => spacing(value).or_else(|| fraction(value)),
+    }
+}
+
+/// 앞에 - 가 붙은 class의 값
+fn negate(value: String) -> String {
+    if value.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("-{}", value)
+    } else {
+        format!("calc({} * -1)", value)
+    }
+}
+
+/// blue-500, white, [#fff], black/50
+fn color(value: &str) -> Option<String> {
+    let (value, alpha) = match value.rsplit_once('/') {
+        Some((color, alpha)) if !color.contains('[') || color.ends_with(']') => {
+            let alpha = match arbitrary(alpha) {
+                Some(alpha) => alpha,
+                None => format!("{}", alpha.parse::<f64>().ok()? / 100.0),
+            };
+            (color, Some(alpha))
+        }
+        _ => (value, None),
+    };
+    let color = if let Some(color) = arbitrary(value) {
+        color
+    } else {
+        match value {
+            "inherit" => "inherit".to_string(),
+            "current" => "currentColor".to_string(),
+            "transparent" => "transparent".to_string(),
+            "black" => "#000".to_string(),
+            "white" => "#fff".to_string(),
+            _ => {
+                let (name, shade) = value.rsplit_once('-')?;
+                let (_, shades) = PALETTE.iter().find(|(x, _)| *x == name)?;
+                let index = SHADES.iter().position(|x| *x == shade)?;
+                shades[index].to_string()
+            }
+        }
+    };
+    match alpha {
+        Some(alpha) => Some(with_alpha(&color, &alpha)),
+        None => Some(color),
+    }
+}
+
+/// #2563eb 와 0.5 -> rgb(37 99 235 / 0.5)
+fn with_alpha(color: &str, alpha: &str) -> String {
+    let hex = match color.strip_prefix('#') {
+        Some(hex) if hex.len() == 3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+        Some(hex) if hex.len() == 6 => hex.to_string(),
+        _ => return color.to_string(),
+    };
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0);
+    format!("rgb({} {} {} / {})", channel(0), channel(2), channel(4), alpha)
+}
+
+fn lookup(table: &[(&str, &str)], key: &str) -> Option<String> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string())
+}
+
+/// ### utility
+/// class 이름 하나를 css 선언으로 바꾼다.
+///
+/// order는 tailwind처럼 p-4 보다 px-2 가, text-xl 보다 leading-7 이 뒤에 오도록 정한다.
+fn utility(name: &str) -> Option<Utility> {
+    // [font-family:Inter,sans-serif] 처럼 속성을 직접 쓴 class
+    if let Some(inner) = name.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+        let (property, value) = inner.split_once(':')?;
+        return Utility::one(90, property, value.replace('_', " "));
+    }
+    if let Some(utility) = static_utility(name) {
+        return Some(utility);
+    }
+    if let Some(name) = name.strip_prefix('-') {
+        return negative_utility(name);
+    }
+
+    let (prefix, value) = name.split_once('-')?;
+    match prefix {
+        "inset" => {
+            if let Some(value) = value.strip_prefix("x-") {
+                let value = inset(value)?;
+                return Utility::new(4, vec![("left", value.clone()), ("right", value)]);
+            }
+            if let Some(value) = value.strip_prefix("y-") {
+                let value = inset(value)?;
+                return Utility::new(4, vec![("top", value.clone()), ("bottom", value)]);
+            }
+            let value = inset(value)?;
+            Utility::new(3, vec![("top", value.clone()), ("right", value.clone()), ("bottom", value.clone()), ("left", value)])
+        }
+        "top" | "right" | "bottom" | "left" => Utility::one(5, prefix, inset(value)?),
+        "z" => match value {
+            "auto" => Utility::one(6, "z-index", "auto"),
+            _ => Utility::one(6, "z-index", arbitrary(value).or_else(|| value.parse::<u16>().ok().map(|n| n.to_string()))?),
+        },
+        "col" => {
+            let value = value.strip_prefix("span-")?;
+            match value {
+                "full" => Utility::one(7, "grid-column", "1 / -1"),
+                _ => {
+                    let n = value.parse::<u16>().ok()?;
+                    Utility::one(7, "grid-column", format!("span {} / span {}", n, n))
+                }
+            }
+        }
+        "row" => {
+            let value = value.strip_prefix("span-")?;
+            match value {
+                "full" => Utility::one(8, "grid-row", "1 / -1"),
+                _ => {
+                    let n = value.parse::<u16>().ok()?;
+                    Utility::one(8, "grid-row", format!("span {} / span {}", n, n))
+                }
+            }
+        }
+        "m" | "mx" | "my" | "mt" | "mr" | "mb" | "ml" | "ms" | "me" => {
+            let value = if value == "auto" { "auto".to_string() } else { spacing(value)? };
+            sides(prefix, "margin", 10, value)
+        }
+        "p" | "px" | "py" | "pt" | "pr" | "pb" | "pl" | "ps" | "pe" => sides(prefix, "padding", 57, spacing(value)?),
+        "aspect" => match value {
+            "square" => Utility::one(15, "aspect-ratio", "1 / 1"),
+            "video" => Utility::one(15, "aspect-ratio", "16 / 9"),
+            "auto" => Utility::one(15, "aspect-ratio", "auto"),
+            _ => Utility::one(15, "aspect-ratio", arbitrary(value)?),
+        },
+        "w" => Utility::one(19, "width", size(value, "100vw")?),
+        "h" => Utility::one(16, "height", size(value, "100vh")?),
+        "min" | "max" => {
+            let (axis, value) = value.split_once('-')?;
+            let (property, order, screen) = match (prefix, axis) {
+                ("min", "w") => ("min-width", 20, "100vw"),
+                ("min", "h") => ("min-height", 18, "100vh"),
+                ("max", "w") => ("max-width", 21, "100vw"),
+                ("max", "h") => ("max-height", 17, "100vh"),
+                _ => return None,
+            };
+            let value = match (prefix, axis) {
+                ("max", "w") => lookup(&MAX_WIDTHS, value).or_else(|| size(value, screen)),
+                ("max", _) if value == "none" => Some("none".to_string()),
+                _ => size(value, screen),
+            };
+            Utility::one(order, property, value?)
+        }
+        "basis" => Utility::one(22, "flex-basis", size(value, "100vw")?),
+        "cursor" => Utility::one(26, "cursor", value),
+        "list" => match value {
+            "inside" | "outside" => Utility::one(28, "list-style-position", value),
+            _ => Utility::one(28, "list-style-type", value),
+        },
+        "grid" => {
+            let (axis, value) = value.split_once('-')?;
+            let property = match axis {
+                "cols" => "grid-template-columns",
+                "rows" => "grid-template-rows",
+                _ => return None,
+            };
+            let value = match value {
+                "none" => "none".to_string(),
+                _ => arbitrary(value).or_else(|| value.parse::<u16>().ok().map(|n| format!("repeat({}, minmax(0, 1fr))", n)))?,
+            };
+            Utility::one(if axis == "cols" { 29 } else { 30 }, property, value)
+        }
+        "gap" => {
+            if let Some(value) = value.strip_prefix("x-") {
+                return Utility::one(37, "column-gap", spacing(value)?);
+            }
+            if let Some(value) = value.strip_prefix("y-") {
+                return Utility::one(37, "row-gap", spacing(value)?);
+            }
+            Utility::one(36, "gap", spacing(value)?)
+        }
+        "space" => {
+            let (axis, value) = value.split_once('-')?;
+            let utility = match (axis, value) {
+                ("x", "reverse") => Utility::new(39, vec![("--tw-space-x-reverse", "1".to_string())]),
+                ("y", "reverse") => Utility::new(39, vec![("--tw-space-y-reverse", "1".to_string())]),
+                ("x", _) => {
+                    let value = spacing(value)?;
+                    Utility::new(38, vec![
+                        ("--tw-space-x-reverse", "0".to_string()),
+                        ("margin-right", format!("calc({} * var(--tw-space-x-reverse))", value)),
+                        ("margin-left", format!("calc({} * calc(1 - var(--tw-space-x-reverse)))", value)),
+                    ])
+                }
+                ("y", _) => {
+                    let value = spacing(value)?;
+                    Utility::new(38, vec![
+                        ("--tw-space-y-reverse", "0".to_string()),
+                        ("margin-top", format!("calc({} * calc(1 - var(--tw-space-y-reverse)))", value)),
+                        ("margin-bottom", format!("calc({} * var(--tw-space-y-reverse))", value)),
+                    ])
+                }
+                _ => None,
+            };
+            utility.map(|x| Utility { children: true, ..x })
+        }
+        "divide" => {
+            let utility = match value.split_once('-') {
+                Some(("x", width)) => Utility::new(40, vec![("border-right-width", "0px".to_string()), ("border-left-width", border_width(width)?)]),
+                Some(("y", width)) => Utility::new(40, vec![("border-top-width", border_width(width)?), ("border-bottom-width", "0px".to_string())]),
+                _ => match value {
+                    "x" => Utility::new(40, vec![("border-right-width", "0px".to_string()), ("border-left-width", "1px".to_string())]),
+                    "y" => Utility::new(40, vec![("border-top-width", "1px".to_string()), ("border-bottom-width", "0px".to_string())]),
+                    _ => Utility::one(41, "border-color", color(value)?),
+                },
+            };
+            utility.map(|x| Utility { children: true, ..x })
+        }
+        "self" => Utility::one(42, "align-self", flex_align(value)?),
+        "items" => Utility::one(34, "align-items", flex_align(value)?),
+        "content" => Utility::one(33, "align-content", justify(value)?),
+        "justify" => Utility::one(35, "justify-content", justify(value)?),
+        "overflow" => match value.split_once('-') {
+            Some(("x", value)) => Utility::one(44, "overflow-x", value),
+            Some(("y", value)) => Utility::one(44, "overflow-y", value),
+            _ => Utility::one(43, "overflow", value),
+        },
+        "whitespace" => Utility::one(46, "white-space", value),
+        "rounded" => rounded(value),
+        "border" => border(value),
+        "bg" => match value {
+            "cover" | "contain" | "auto" => Utility::one(55, "background-size", value),
+            "center" | "top" | "bottom" | "left" | "right" => Utility::one(55, "background-position", value),
+            "no-repeat" | "repeat" => Utility::one(55, "background-repeat", value),
+            "none" => Utility::one(54, "background-image", "none"),
+            _ => match arbitrary(value) {
+                Some(image) if image.starts_with("url(") => Utility::one(54, "background-image", image),
+                _ => Utility::one(54, "background-color", color(value)?),
+            },
+        },
+        "object" => Utility::one(56, "object-fit", value),
+        "text" => match value {
+            "left" | "center" | "right" | "justify" | "start" | "end" => Utility::one(60, "text-align", value),
+            _ => {
+                if let Some((_, size, height)) = FONT_SIZES.iter().find(|(k, _, _)| *k == value) {
+                    return Utility::new(62, vec![("font-size", size.to_string()), ("line-height", height.to_string())]);
+                }
+                match arbitrary(value) {
+                    Some(size) if is_length(&size) => Utility::one(62, "font-size", size),
+                    _ => Utility::one(68, "color", color(value)?),
+                }
+            }
+        },
+        "font" => {
+            if let Some(weight) = lookup(&FONT_WEIGHTS, value) {
+                return Utility::one(63, "font-weight", weight);
+            }
+            if let Some(family) = lookup(&FONT_FAMILIES, value) {
+                return Utility::one(61, "font-family", family);
+            }
+            Utility::one(63, "font-weight", arbitrary(value)?)
+        }
+        "leading" => Utility::one(66, "line-height", lookup(&LEADINGS, value).or_else(|| arbitrary(value))?),
+        "tracking" => Utility::one(67, "letter-spacing", lookup(&TRACKINGS, value).or_else(|| arbitrary(value))?),
+        "decoration" => Utility::one(69, "text-decoration-color", color(value)?),
+        "opacity" => Utility::one(70, "opacity", arbitrary(value).or_else(|| value.parse::<u16>().ok().filter(|n| *n <= 100).map(|n| format!("{}", n as f64 / 100.0)))?),
+        "shadow" => Utility::one(71, "box-shadow", lookup(&SHADOWS, value).or_else(|| arbitrary(value))?),
+        "ring" => match value.parse::<u16>() {
+            Ok(width) => ring(width),
+            Err(_) => Utility::one(74, "--tw-ring-color", color(value)?),
+        },
+        "duration" => Utility::one(76, "transition-duration", format!("{}ms", value.parse::<u16>().ok()?)),
+        "pointer" => Utility::one(1, "pointer-events", value.strip_prefix("events-")?),
+        "select" => Utility::one(27, "user-select", value),
+        _ => None,
+    }
+}
+
+/// 값 없이 이름만으로 정해지는 class
+fn static_utility(name: &str) -> Option<Utility> {
+    match name {
+        "sr-only" => Utility::new(0, vec![
+            ("position", "absolute".to_string()), ("width", "1px".to_string()), ("height", "1px".to_string()),
+            ("padding", "0".to_string()), ("margin", "-1px".to_string()), ("overflow", "hidden".to_string()),
+            ("clip", "rect(0, 0, 0, 0)".to_string()), ("white-space", "nowrap".to_string()), ("border-width", "0".to_string()),
+        ]),
+        "static" | "fixed" | "absolute" | "relative" | "sticky" => Utility::one(2, "position", name),
+        "block" | "inline-block" | "inline" | "flex" | "inline-flex" | "grid" | "inline-grid" | "contents" | "table" | "table-row" | "table-cell" | "flow-root" => Utility::one(14, "display", name),
+        "hidden" => Utility::one(14, "display", "none"),
+        "flex-1" => Utility::one(22, "flex", "1 1 0%"),
+        "flex-auto" => Utility::one(22, "flex", "1 1 auto"),
+        "flex-initial" => Utility::one(22, "flex", "0 1 auto"),
+        "flex-none" => Utility::one(22, "flex", "none"),
+        "shrink" => Utility::one(23, "flex-shrink", "1"),
+        "shrink-0" => Utility::one(23, "flex-shrink", "0"),
+        "grow" => Utility::one(24, "flex-grow", "1"),
+        "grow-0" => Utility::one(24, "flex-grow", "0"),
+        "flex-row" => Utility::one(31, "flex-direction", "row"),
+        "flex-row-reverse" => Utility::one(31, "flex-direction", "row-reverse"),
+        "flex-col" => Utility::one(31, "flex-direction", "column"),
+        "flex-col-reverse" => Utility::one(31, "flex-direction", "column-reverse"),
+        "flex-wrap" => Utility::one(32, "flex-wrap", "wrap"),
+        "flex-wrap-reverse" => Utility::one(32, "flex-wrap", "wrap-reverse"),
+        "flex-nowrap" => Utility::one(32, "flex-wrap", "nowrap"),
+        "truncate" => Utility::new(45, vec![("overflow", "hidden".to_string()), ("text-overflow", "ellipsis".to_string()), ("white-space", "nowrap".to_string())]),
+        "break-words" => Utility::one(47, "overflow-wrap", "break-word"),
+        "break-all" => Utility::one(47, "word-break", "break-all"),
+        "italic" => Utility::one(65, "font-style", "italic"),
+        "not-italic" => Utility::one(65, "font-style", "normal"),
+        "uppercase" | "lowercase" | "capitalize" => Utility::one(64, "text-transform", name),
+        "normal-case" => Utility::one(64, "text-transform", "none"),
+        "underline" | "overline" | "line-through" => Utility::one(69, "text-decoration-line", name),
+        "no-underline" => Utility::one(69, "text-decoration-line", "none"),
+        "outline-none" => Utility::new(72, vec![("outline", "2px solid transparent".to_string()), ("outline-offset", "2px".to_string())]),
+        "ring" => ring(3),
+        "transition" => Utility::new(75, vec![
+            ("transition-property", "color, background-color, border-color, text-decoration-color, fill, stroke, opacity, box-shadow, transform, filter, backdrop-filter".to_string()),
+            ("transition-timing-function", "cubic-bezier(0.4, 0, 0.2, 1)".to_string()),
+            ("transition-duration", "150ms".to_string()),
+        ]),
+        "transition-colors" => Utility::new(75, vec![
+            ("transition-property", "color, background-color, border-color, text-decoration-color, fill, stroke".to_string()),
+            ("transition-timing-function", "cubic-bezier(0.4, 0, 0.2, 1)".to_string()),
+            ("transition-duration", "150ms".to_string()),
+        ]),
+        "rounded" => rounded(""),
+        "border" => border(""),
+        "shadow" => Utility::one(71, "box-shadow", lookup(&SHADOWS, "")?),
+        _ => None,
+    }
+}
+
+/// -mt-2, -top-4
+fn negative_utility(name: &str) -> Option<Utility> {
+    let (prefix, value) = name.split_once('-')?;
+    match prefix {
+        "m" | "mx" | "my" | "mt" | "mr" | "mb" | "ml" | "ms" | "me" => sides(prefix, "margin", 10, negate(spacing(value)?)),
+        "top" | "right" | "bottom" | "left" => Utility::one(5, prefix, negate(inset(value)?)),
+        "tracking" => Utility::one(67, "letter-spacing", negate(arbitrary(value)?)),
+        _ => None,
+    }
+}
+
+/// p, px, pt 처럼 방향이 붙은 margin, padding
+fn sides(prefix: &str, property: &str, order: u16, value: String) -> Option<Utility> {
+    let (order, sides): (u16, Vec<&str>) = match &prefix[1..] {
+        "" => (order, vec![""]),
+        "x" => (order + 1, vec!["-left", "-right"]),
+        "y" => (order + 1, vec!["-top", "-bottom"]),
+        "t" => (order + 2, vec!["-top"]),
+        "r" => (order + 2, vec!["-right"]),
+        "b" => (order + 2, vec!["-bottom"]),
+        "l" => (order + 2, vec!["-left"]),
+        "s" => (order + 2, vec!["-inline-start"]),
+        "e" => (order + 2, vec!["-inline-end"]),
+        _ => return None,
+    };
+    let declarations = sides.iter().map(|side| (format!("{}{}", property, side), value.clone())).collect();
+    Some(Utility { order, declarations, children: false })
+}
+
+/// w-, h- 의 값, screen은 100vw나 100vh
+fn size(value: &str, screen: &str) -> Option<String> {
+    match value {
+        "auto" => Some("auto".to_string()),
+        "full" => Some("100%".to_string()),
+        "screen" => Some(screen.to_string()),
+        "min" => Some("min-content".to_string()),
+        "max" => Some("max-content".to_string()),
+        "fit" => Some("fit-content".to_string()),
+        _ => spacing(value).or_else(|| fraction(value)),
+    }
+}
+
+fn flex_align(value: &str) -> Option<String> {
+    match value {
+        "start" => Some("flex-start".to_string()),
+        "end" => Some("flex-end".to_string()),
+        "center" | "baseline" | "stretch" | "auto" => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn justify(value: &str) -> Option<String> {
+    match value {
+        "start" => Some("flex-start".to_string()),
+        "end" => Some("flex-end".to_string()),
+        "between" => Some("space-between".to_string()),
+        "around" => Some("space-around".to_string()),
+        "evenly" => Some("space-evenly".to_string()),
+        "center" | "normal" | "stretch" => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// border-2 -> 2px, border-[3px] -> 3px
+fn border_width(value: &str) -> Option<String> {
+    match value {
+        "" => Some("1px".to_string()),
+        "0" | "2" | "4" | "8" => Some(format!("{}px", value)),
+        _ => arbitrary(value).filter(|x| is_length(x)),
+    }
+}
+
+/// rounded, rounded-lg, rounded-t-lg, rounded-[10px]
+fn rounded(value: &str) -> Option<Utility> {
+    let corners: &[(&str, &[&str])] = &[
+        ("t", &["border-top-left-radius", "border-top-right-radius"]),
+        ("r", &["border-top-right-radius", "border-bottom-right-radius"]),
+        ("b", &["border-bottom-right-radius", "border-bottom-left-radius"]),
+        ("l", &["border-top-left-radius", "border-bottom-left-radius"]),
+        ("s", &["border-start-start-radius", "border-end-start-radius"]),
+        ("e", &["border-start-end-radius", "border-end-end-radius"]),
+    ];
+    for (side, properties) in corners {
+        let rest = if value == *side { Some("") } else { value.strip_prefix(&format!("{}-", side)) };
+        if let Some(rest) = rest {
+            let radius = lookup(&RADII, rest).or_else(|| arbitrary(rest))?;
+            let declarations = properties.iter().map(|p| (p.to_string(), radius.clone())).collect();
+            return Some(Utility { order: 49, declarations, children: false });
+        }
+    }
+    Utility::one(48, "border-radius", lookup(&RADII, value).or_else(|| arbitrary(value))?)
+}
+
+/// border, border-2, border-t-4, border-dashed, border-gray-200, border-[#ddd]
+fn border(value: &str) -> Option<Utility> {
+    if let Some(width) = border_width(value) {
+        return Utility::one(50, "border-width", width);
+    }
+    let sides: [(&str, &[&str]); 8] = [
+        ("x", &["border-left-width", "border-right-width"]),
+        ("y", &["border-top-width", "border-bottom-width"]),
+        ("t", &["border-top-width"]),
+        ("r", &["border-right-width"]),
+        ("b", &["border-bottom-width"]),
+        ("l", &["border-left-width"]),
+        ("s", &["border-inline-start-width"]),
+        ("e", &["border-inline-end-width"]),
+    ];
+    for (side, properties) in sides {
+        let rest = if value == side { Some("") } else { value.strip_prefix(&format!("{}-", side)) };
+        if let Some(width) = rest.and_then(border_width) {
+            let declarations = properties.iter().map(|p| (p.to_string(), width.clone())).collect();
+            return Some(Utility { order: 51, declarations, children: false });
+        }
+    }
+    match value {
+        "solid" | "dashed" | "dotted" | "double" | "hidden" | "none" => Utility::one(52, "border-style", value),
+        _ => Utility::one(53, "border-color", color(value)?),
+    }
+}
+
+fn ring(width: u16) -> Option<Utility> {
+    Utility::one(73, "box-shadow", format!("0 0 0 {}px var(--tw-ring-color, rgb(59 130 246 / 0.5))", width))
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::check::check_site;
+use crate::error::Result;
+
+fn make_site(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("blog")).unwrap();
+    fs::write(root.join("index.toml"), r#"
+[root]
+shape = "column"
+
+[root.nav]
+shape = "nav"
+headers = ["home", "blog", "nope"]
+home = "/"
+blog = "blog"
+nope = "missing"
+
+[root.image]
+shape = "image"
+image_path = "none.png"
+
+[root.footer]
+shape = "embed"
+layout = "footer"
+"#).unwrap();
+    fs::write(root.join("blog/post.md"), "---\ntitle: post\n---\n# Post\n[home](/)\n[nowhere](../nowhere)\n").unwrap();
+    root
+}
+
+/// 깨진 링크와 없는 파일, layout이 key와 함께 보고되는지
+#[test]
+fn test_check_site() -> Result<()> {
+    let root = make_site("prema_test_check_site");
+    let issues = check_site(&root)?;
+    let found: Vec<String> = issues.iter().map(|x| format!("{}: {}", x.key, x.message)).collect();
+    let lines: Vec<usize> = issues.iter().filter_map(|x| x.location).map(|x| x.line).collect();
+    println!("{:#?}", found);
+
+    assert!(found.contains(&"root.nav.nope: broken link \"missing\"".to_string()));
+    assert!(found.contains(&"root.image.image_path: missing image \"none.png\"".to_string()));
+    assert!(found.contains(&"root.footer.layout: unknown layout \"footer\"".to_string()));
+    assert!(found.contains(&"link: broken link \"../nowhere\"".to_string()));
+    assert_eq!(issues.len(), 4);
+    // root.nav.nope, root.image.image_path, root.footer.layout, post.md 6번째 줄
+    assert_eq!(lines, vec![10, 14, 18, 6]);
+    Ok(())
+}
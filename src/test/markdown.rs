@@ -7,7 +7,7 @@ use std::{
 use maplit::hashmap;
 use serde_yaml::Value;
 
-use crate::{layout::{common::get_tomlview_for_key, layouts_from_toml}, markdown::{markdown_wrap_to_html, markdown_wrap_to_htmlview, metas_table_from_markdown}, page::make_md_files_to_folder_except_index};
+use crate::{layout::{common::get_tomlview_for_key, layouts_from_toml, schema::validate_toml}, markdown::{markdown_to_htmlview, markdown_wrap_to_html, markdown_wrap_to_htmlview, metas_table_from_markdown}, page::make_md_files_to_folder_except_index};
 
 
 ///
@@ -32,7 +32,7 @@ fn test_mdlist() -> Result<(), Box<dyn std::error::Error>> {
     let index_path = Path::new("test/portfolios/python/index.toml");
     let index_folder = index_path.parent().ok_or("failed to get index_folder")?;
     let layouts = layouts_from_toml(index_path).unwrap_or(hashmap! {});
-    let view = get_tomlview_for_key(index_path, "root", None, None, layouts)?;
+    let view = get_tomlview_for_key(index_path, "root", None, None, &layouts)?;
     for view in view.views() {
         if view.key() == "contents" {
             for view in view.views() {
@@ -70,7 +70,7 @@ fn test_mdlist() -> Result<(), Box<dyn std::error::Error>> {
 fn test_layout_pass_down() -> Result<(), Box<dyn std::error::Error>> {
     let index_path = Path::new("test/portfolios/index.toml");
     let layouts = layouts_from_toml(index_path).unwrap_or(hashmap! {});
-    let view = get_tomlview_for_key(index_path, "root", None, None, layouts)?;
+    let view = get_tomlview_for_key(index_path, "root", None, None, &layouts)?;
     println!("{:#?}", view);
     println!("1");
     assert_eq!(1, 1);
@@ -87,4 +87,54 @@ fn test_meta_layout() -> Result<(), Box<dyn std::error::Error>> {
     println!("{:?}", view);
     assert_eq!(1, 2);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// [markdown] table의 class와 layout, frontmatter의 markdown 이 기본 class를 덮어쓰는지
+#[test]
+fn test_markdown_overrides() {
+    let folder = std::env::temp_dir().join("prema_test_markdown_overrides");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, r##"[markdown]
+h1 = "text-5xl font-black"
+p = "leading-7"
+blockquote = { layout = "quote" }
+
+[quote]
+shape = "column"
+custom_class = "quote-card"
+
+[quote.contents]
+shape = "box"
+
+[root]
+shape = "box"
+"##).unwrap();
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let md_path = folder.join("a.md");
+    fs::write(&md_path, "---\ntitle: a\nmarkdown:\n  p: text-lg\n---\n# a\n\nbody\n\n> quote\n").unwrap();
+    let html = markdown_to_htmlview(&md_path, false, &layouts).unwrap().html();
+    assert!(html.contains(r#"<h1 class="text-5xl font-black">"#), "{}", html);
+    assert!(html.contains(r#"<p class="text-lg">"#), "{}", html);
+    assert!(!html.contains("leading-7"), "{}", html);
+    assert!(html.contains(r#"class="flex flex-col overflow-hidden quote-card" id="quote""#), "{}", html);
+    assert!(html.contains(r#"<div class="relative" id="contents" style="background:transparent;">"#), "{}", html);
+    assert!(html.contains("<blockquote"), "{}", html);
+}
+
+/// [markdown]의 모르는 node 종류와 없는 layout
+#[test]
+fn test_markdown_overrides_validation() {
+    let folder = std::env::temp_dir().join("prema_test_markdown_overrides_validation");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, "[markdown]\nh7 = \"big\"\ncode = { layout = \"snippet\" }\nimg = 3\n\n[root]\nshape = \"box\"\n").unwrap();
+    let found: Vec<String> = validate_toml(&index_path, &hashmap! {}).into_iter().map(|x| format!("{}: {}", x.key, x.message)).collect();
+    assert_eq!(found, vec![
+        "markdown.h7: unknown markdown element \"h7\", expected one of h1, h2, h3, h4, h5, h6, p, blockquote, table, code, a, img, li, hr",
+        "markdown.code: unknown layout \"snippet\"",
+        "markdown.img: expected a class string or a table like { layout = \"name\" }",
+    ]);
+}
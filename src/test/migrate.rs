@@ -0,0 +1,55 @@
+use std::fs;
+
+use crate::error::Result;
+use crate::migrate::migrate_site;
+use crate::site::Site;
+
+/// option.toml의 nav, footer, theme, basic이 index.toml layout과 frontmatter로 옮겨지는지
+#[test]
+fn test_migrate() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_migrate");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let post = input.join("post");
+    fs::create_dir_all(&post).unwrap();
+    fs::write(post.join("post.md"), "# Post\n").unwrap();
+    fs::write(post.join("option.toml"), r#"[basic]
+title = "STAR"
+tags = ["android", "ios"]
+created = "2025-04-10 05:10:32"
+
+[nav]
+home = "/"
+width = "wide"
+service.etc1 = "etc1"
+
+[footer]
+title = "© 2025 STAR"
+sns.github = "https://github.com/star"
+
+[theme]
+night = true
+font = "serif"
+"#).unwrap();
+
+    let migration = migrate_site(&input)?;
+    assert!(!post.join("option.toml").exists());
+    assert!(post.join("option.toml.bak").exists());
+    let keys: Vec<&str> = migration.issues.iter().map(|x| x.key.as_str()).collect();
+    assert_eq!(keys, vec!["theme.font", "nav.width", "footer.sns"]);
+
+    let index_toml = fs::read_to_string(post.join("index.toml")).unwrap();
+    assert!(index_toml.contains("[root.nav]\nshape = \"embed\"\nlayout = \"nav\""), "{}", index_toml);
+    assert!(index_toml.contains("headers = [\"home\", \"service\"]"), "{}", index_toml);
+    assert!(index_toml.contains("[nav.service]\netc1 = \"etc1\""), "{}", index_toml);
+    assert!(index_toml.contains("[footer.github]"), "{}", index_toml);
+
+    let post_md = fs::read_to_string(post.join("post.md")).unwrap();
+    assert_eq!(post_md, "---\nheader: \"nav\"\nfooter: \"footer\"\ndark: true\ntitle: \"STAR\"\ncreated: \"2025-04-10 05:10:32\"\ntags: [\"android\", \"ios\"]\n---\n# Post\n");
+
+    Site::new(&input).output(root.join("html")).build()?;
+    let post_html = fs::read_to_string(root.join("html/post/post/index.html")).unwrap();
+    assert!(post_html.contains("etc1"), "{}", post_html);
+    assert!(post_html.contains("https://github.com/star"), "{}", post_html);
+    Ok(())
+}
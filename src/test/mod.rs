@@ -2,6 +2,12 @@ mod test;
 mod markdown;
 mod page;
 mod toml;
+mod check;
+mod site;
+mod view;
+mod theme;
+mod tailwind;
+mod migrate;
 
 #[cfg(test)]
 mod test_toml {
@@ -0,0 +1,548 @@
+use std::fs;
+
+use crate::error::{Error, Result};
+use crate::site::Site;
+
+/// publish: false 인 markdown이 빠지고, base_url의 하위 경로가 링크에 붙는지
+#[test]
+fn test_site_build() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_build");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(input.join("index.md"), "---\ntitle: home\n---\n[post](/post)\n").unwrap();
+    fs::write(input.join("post.md"), "---\ntitle: post\n---\n# Post\n").unwrap();
+    fs::write(input.join("draft.md"), "---\ntitle: draft\npublish: false\n---\n# Draft\n").unwrap();
+
+    let report = Site::new(&input)
+        .output(&output)
+        .base_url("https://example.com/blog/")
+        .build()?;
+
+    assert_eq!(report.drafts, vec![input.join("draft.md")]);
+    assert_eq!(report.pages.len(), 2);
+    assert!(output.join("post/index.html").is_file());
+    assert!(!output.join("draft").exists());
+    let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(index_html.contains("href=\"/blog/post\""));
+    Ok(())
+}
+
+/// assets를 주면 vendor 파일이 output/assets 로 복사되고, 페이지 깊이에 맞는 상대 경로와 integrity로 링크되는지
+#[test]
+fn test_site_assets() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_assets");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    let vendor = root.join("vendor");
+    fs::create_dir_all(&input).unwrap();
+    fs::create_dir_all(&vendor).unwrap();
+    fs::write(input.join("index.md"), "---\ntitle: home\n---\n# Home\n").unwrap();
+    fs::write(input.join("post.md"), "---\ntitle: post\n---\n# Post\n").unwrap();
+    for file in ["atom-one-light.min.css", "atom-one-dark.min.css", "highlight.min.js", "go.min.js", "flowbite.min.js"] {
+        fs::write(vendor.join(file), file).unwrap();
+    }
+
+    let missing = Site::new(&input).output(&output).assets(&vendor).build();
+    assert!(missing.unwrap_err().to_string().contains("https://cdn.tailwindcss.com"));
+
+    fs::write(vendor.join("tailwind.css"), "body{}").unwrap();
+    Site::new(&input).output(&output).assets(&vendor).build()?;
+    assert!(output.join("assets/tailwind.css").is_file());
+
+    let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+    let post_html = fs::read_to_string(output.join("post/index.html")).unwrap();
+    let integrity = crate::asset::integrity(b"flowbite.min.js");
+    assert!(index_html.contains(&format!(r#"<script integrity="{}" src="assets/flowbite.min.js">"#, integrity)), "{}", index_html);
+    assert!(post_html.contains(r#"<link href="../assets/tailwind.css" integrity="#), "{}", post_html);
+    assert!(!post_html.contains("cdn.tailwindcss.com"));
+    assert!(!post_html.contains("cdnjs.cloudflare.com"));
+    Ok(())
+}
+
+/// frontmatter, [root], Site의 값으로 title, description, og, canonical, lang이 채워지는지
+#[test]
+fn test_site_head() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_head");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(input.join("posts")).unwrap();
+    fs::write(input.join("index.toml"), "[root]\nshape = \"text\"\ntext = \"home\"\ntitle = \"Home\"\nlang = \"en\"\n").unwrap();
+    fs::write(input.join("posts/index.md"), "---\ntitle: Posts\n---\n# Posts\n").unwrap();
+    fs::write(input.join("posts/hello.md"), "---\ntitle: Hello \"world\"\ndescription: first post\nimage: cover.png\n---\n# Hello\n").unwrap();
+
+    Site::new(&input)
+        .output(&output)
+        .base_url("https://example.com/blog/")
+        .title("Blog")
+        .description("a blog")
+        .lang("ko")
+        .build()?;
+
+    let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(index_html.contains(r#"<html lang="en">"#), "{}", index_html);
+    assert!(index_html.contains("<title>\n\t\tHome | Blog"), "{}", index_html);
+    assert!(index_html.contains(r#"<meta content="a blog" name="description">"#), "{}", index_html);
+    assert!(index_html.contains(r#"<link href="https://example.com/blog/" rel="canonical">"#), "{}", index_html);
+
+    let hello_html = fs::read_to_string(output.join("posts/hello/index.html")).unwrap();
+    assert!(hello_html.contains(r#"<html lang="ko">"#), "{}", hello_html);
+    assert!(hello_html.contains("Hello &quot;world&quot; | Blog"), "{}", hello_html);
+    assert!(hello_html.contains(r#"<meta content="first post" property="og:description">"#), "{}", hello_html);
+    assert!(hello_html.contains(r#"<meta content="https://example.com/blog/posts/hello/cover.png" property="og:image">"#), "{}", hello_html);
+    assert!(hello_html.contains(r#"<meta content="summary_large_image" name="twitter:card">"#), "{}", hello_html);
+    assert!(hello_html.contains(r#"<link href="https://example.com/blog/posts/hello/" rel="canonical">"#), "{}", hello_html);
+    Ok(())
+}
+
+/// Site와 frontmatter의 head_extra, body_end, extra_css, extra_js가 들어가고 파일이 복사되는지
+#[test]
+fn test_site_extras() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_extras");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(root.join("site.css"), "body { color: red; }").unwrap();
+    fs::write(input.join("index.md"), "---\ntitle: home\n---\n# Home\n").unwrap();
+    fs::write(input.join("analytics.html"), "<script>track();</script>").unwrap();
+    fs::write(input.join("post.js"), "console.log(1);").unwrap();
+    fs::write(input.join("banner.html"), "<div>BANNER</div>").unwrap();
+    fs::write(input.join("post.md"), "---\ntitle: post\nbody_end: analytics.html\nhead_extra: nothing.html\nextra_js: [post.js, https://example.com/a.js]\n---\n# Post\n").unwrap();
+
+    let report = Site::new(&input)
+        .output(&output)
+        .head_extra(r#"<link href="https://fonts.googleapis.com/css2?family=Inter" rel="stylesheet">"#)
+        .body_end("banner.html")
+        .body_end("missing.html")
+        .extra_css(root.join("site.css").to_string_lossy())
+        .build()?;
+
+    // builder의 파일 경로는 input 폴더 기준이고, 없는 파일은 이름 대신 경고가 된다.
+    let messages: Vec<String> = report.diagnostics.iter().map(|x| x.to_string()).collect();
+    assert_eq!(messages.len(), 2, "{:?}", messages);
+    assert!(messages.iter().any(|x| x.contains("[body_end]") && x.contains("missing.html")), "{:?}", messages);
+    assert!(messages.iter().any(|x| x.contains("post.md") && x.contains("[head_extra]") && x.contains("nothing.html")), "{:?}", messages);
+
+    assert!(output.join("assets/site.css").is_file());
+    assert!(output.join("assets/post.js").is_file());
+
+    let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(index_html.contains("fonts.googleapis.com"), "{}", index_html);
+    assert!(index_html.contains(r#"<link href="assets/site.css" rel="stylesheet">"#), "{}", index_html);
+    assert!(!index_html.contains("track();"));
+    assert!(index_html.contains("<div>BANNER</div>"), "{}", index_html);
+    assert!(!index_html.contains("missing.html"), "{}", index_html);
+
+    let post_html = fs::read_to_string(output.join("post/index.html")).unwrap();
+    assert!(post_html.contains(r#"<link href="../assets/site.css" rel="stylesheet">"#), "{}", post_html);
+    assert!(post_html.contains(r#"<script src="../assets/post.js">"#), "{}", post_html);
+    assert!(post_html.contains(r#"<script src="https://example.com/a.js">"#), "{}", post_html);
+    assert!(post_html.contains("<script>track();</script>"), "{}", post_html);
+    assert!(!post_html.contains("nothing.html"), "{}", post_html);
+    Ok(())
+}
+
+/// prema.toml의 값이 쓰이고, builder가 그 값을 덮어쓰고, 모르는 key는 경고가 되는지
+#[test]
+fn test_site_config() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_config");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(input.join("index.toml"), "[root]\nshape = \"text\"\ntext = \"home\"\n\n[banner]\nshape = \"text\"\ntext = \"BANNER\"\n").unwrap();
+    fs::write(input.join("post.md"), "---\ntitle: post\n---\n# Post\n").unwrap();
+    fs::write(input.join("prema.toml"), r#"[site]
+title = "Blog"
+lang = "en"
+colour = "red"
+
+[build]
+output = "../out"
+
+[defaults]
+dark = true
+header = "banner"
+"#).unwrap();
+
+    let report = Site::from_config(&input)?.lang("ko").build()?;
+    assert_eq!(report.diagnostics.iter().map(|x| x.key.as_str()).collect::<Vec<&str>>(), vec!["site.colour"]);
+
+    let output = root.join("out");
+    let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(index_html.contains(r#"<html lang="ko">"#), "{}", index_html);
+    assert!(index_html.contains("dark:"), "{}", index_html);
+    let post_html = fs::read_to_string(output.join("post/index.html")).unwrap();
+    assert!(post_html.contains("BANNER"), "{}", post_html);
+    assert!(post_html.contains("post | Blog"), "{}", post_html);
+
+    fs::write(input.join("prema.toml"), "[build]\ndrafts = \"yes\"\n").unwrap();
+    let error = Site::from_config(&input).unwrap_err();
+    assert!(error.to_string().contains("build.drafts"), "{}", error);
+    Ok(())
+}
+
+/// search shape가 있으면 모든 markdown 페이지의 검색 색인과 스크립트를 assets에 쓰는지
+#[test]
+fn test_site_search() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_search");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(input.join("docs")).unwrap();
+    fs::write(input.join("index.toml"), "[root]\nshape = \"column\"\n\n[root.search]\nshape = \"search\"\nplaceholder = \"Find docs\"\nlimit = 5\n").unwrap();
+    fs::write(input.join("docs/index.md"), "---\ntitle: Docs\n---\n# Guide\n").unwrap();
+    fs::write(input.join("docs/install.md"), "---\ntags: [setup, cli]\n---\n# Install\n\nRun `cargo install prema` first.\n\n## Upgrade\n\nRun it again.\n").unwrap();
+
+    Site::new(&input).output(&output).build()?;
+    let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(index_html.contains(r#"data-prema-search="assets/search.json""#), "{}", index_html);
+    assert!(index_html.contains(r#"src="assets/search.js""#), "{}", index_html);
+    assert!(index_html.contains(r#"placeholder="Find docs""#), "{}", index_html);
+    assert!(output.join("assets/search.js").exists());
+
+    let json = fs::read_to_string(output.join("assets/search.json")).unwrap();
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert_eq!(entries.len(), 2, "{}", json);
+    assert_eq!(entries[0]["title"], "Docs");
+    assert_eq!(entries[0]["url"], "docs/");
+    assert_eq!(entries[1]["title"], "Install");
+    assert_eq!(entries[1]["url"], "docs/install/");
+    assert_eq!(entries[1]["headings"], serde_json::json!(["Install", "Upgrade"]));
+    assert_eq!(entries[1]["tags"], serde_json::json!(["setup", "cli"]));
+    assert_eq!(entries[1]["text"], "Run cargo install prema first. Run it again.");
+    Ok(())
+}
+
+/// page_size로 나뉜 mdlist가 page/2/ 부터 쓰이고 pagination layout이 쪽 정보를 받는지
+#[test]
+fn test_site_pagination() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_pagination");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(input.join("index.toml"), r#"[root]
+shape = "column"
+
+[root.posts]
+shape = "mdlist_column"
+layout = "post"
+files = "*.md"
+order_by = "title"
+page_size = 2
+pagination = "pager"
+
+[post]
+shape = "text"
+text = "POST {title}"
+
+[pager]
+shape = "row"
+
+[pager.info]
+shape = "text"
+text = "{page} of {total_pages}"
+
+[pager.prev]
+shape = "text"
+text = "prev"
+path = "{prev_url}"
+
+[pager.next]
+shape = "text"
+text = "next"
+path = "{next_url}"
+"#).unwrap();
+    for title in ["a", "b", "c", "d", "e"] {
+        fs::write(input.join(format!("{}.md", title)), format!("---\ntitle: {}\n---\n# {}\n", title, title)).unwrap();
+    }
+
+    let report = Site::new(&input).output(&output).build()?;
+    assert!(report.pages.contains(&output.join("page/3/index.html")), "{:?}", report.pages);
+    assert!(!output.join("page/4").exists());
+
+    let first = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(first.contains("POST a") && first.contains("POST b") && !first.contains("POST c"), "{}", first);
+    assert!(first.contains("1 of 3"), "{}", first);
+    assert!(first.contains(r#"href="page/2/""#), "{}", first);
+
+    let second = fs::read_to_string(output.join("page/2/index.html")).unwrap();
+    assert!(second.contains("POST c") && second.contains("POST d") && !second.contains("POST a"), "{}", second);
+    assert!(second.contains("2 of 3"), "{}", second);
+    assert!(second.contains(r#"href="../../""#), "{}", second);
+    assert!(second.contains(r#"href="../3/""#), "{}", second);
+
+    let third = fs::read_to_string(output.join("page/3/index.html")).unwrap();
+    assert!(third.contains("POST e") && third.contains("3 of 3"), "{}", third);
+    assert!(![&first, &second, &third].iter().any(|html| html.contains("data-prema")), "{}", first);
+    Ok(())
+}
+
+/// 한 페이지의 나뉜 목록 둘이 쪽 번호를 따로 갖고, 두 번째 목록은 page/{key}/N/ 에 쓰이는지
+#[test]
+fn test_site_pagination_two_lists() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_pagination_two_lists");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(input.join("index.toml"), r#"[root]
+shape = "column"
+
+[root.posts]
+shape = "mdlist_column"
+layout = "post"
+files = "*.md"
+order_by = "title"
+page_size = 2
+pagination = "pager"
+
+[root.tags]
+shape = "list_column"
+layout = "tag"
+values = [{ name = "x" }, { name = "y" }, { name = "z" }]
+page_size = 2
+pagination = "pager"
+
+[post]
+shape = "text"
+text = "POST {title}"
+path = "{url}"
+
+[tag]
+shape = "text"
+text = "TAG {name}"
+
+[pager]
+shape = "row"
+
+[pager.next]
+shape = "text"
+text = "next {page} of {total_pages}"
+path = "{next_url}"
+
+[pager.prev]
+shape = "text"
+text = "prev {page} of {total_pages}"
+path = "{prev_url}"
+"#).unwrap();
+    for title in ["a", "b", "c", "d", "e"] {
+        fs::write(input.join(format!("{}.md", title)), format!("---\ntitle: {}\n---\n# {}\n", title, title)).unwrap();
+    }
+
+    let report = Site::new(&input).output(&output).build()?;
+    assert!(report.pages.contains(&output.join("page/3/index.html")), "{:?}", report.pages);
+    assert!(report.pages.contains(&output.join("page/tags/2/index.html")), "{:?}", report.pages);
+    assert!(!output.join("page/tags/3").exists());
+
+    let first = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(first.contains("POST a") && !first.contains("POST c"), "{}", first);
+    assert!(first.contains("TAG x") && !first.contains("TAG z"), "{}", first);
+    assert!(first.contains(r#"href="page/2/""#), "{}", first);
+    assert!(first.contains(r#"href="page/tags/2/""#), "{}", first);
+
+    // posts의 2쪽에서 tags는 1쪽이고, 주소는 page/2/ 폴더 기준이다.
+    let posts = fs::read_to_string(output.join("page/2/index.html")).unwrap();
+    assert!(posts.contains("POST c") && !posts.contains("POST a"), "{}", posts);
+    assert!(posts.contains("TAG x") && !posts.contains("TAG z"), "{}", posts);
+    assert!(posts.contains("next 2 of 3") && posts.contains("next 1 of 2"), "{}", posts);
+    assert!(posts.contains(r#"href="../../c/""#), "{}", posts);
+    assert!(posts.contains(r#"href="../../page/tags/2/""#), "{}", posts);
+
+    // tags의 2쪽에서 posts는 1쪽이다.
+    let tags = fs::read_to_string(output.join("page/tags/2/index.html")).unwrap();
+    assert!(tags.contains("TAG z") && !tags.contains("TAG x"), "{}", tags);
+    assert!(tags.contains("POST a") && !tags.contains("POST c"), "{}", tags);
+    assert!(tags.contains(r#"href="../../../a/""#), "{}", tags);
+    assert!(tags.contains(r#"href="../../../page/2/""#), "{}", tags);
+    assert!(tags.contains(r#"href="../../../""#), "{}", tags);
+    Ok(())
+}
+
+/// 다른 table 안의 같은 key 목록이 쪽 번호를 따로 갖고, markdown 페이지의 footer 목록도 나뉘는지
+#[test]
+fn test_site_pagination_nested_and_markdown() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_pagination_nested_and_markdown");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(input.join("index.toml"), r#"[root]
+shape = "column"
+
+[root.left]
+shape = "column"
+
+[root.left.items]
+shape = "list_column"
+layout = "item"
+values = [{ name = "a" }, { name = "b" }, { name = "c" }]
+page_size = 2
+
+[root.right]
+shape = "column"
+
+[root.right.items]
+shape = "list_column"
+layout = "item"
+values = [{ name = "v" }, { name = "w" }, { name = "x" }, { name = "y" }, { name = "z" }]
+page_size = 2
+
+[item]
+shape = "text"
+text = "ITEM {name}"
+
+[recent]
+shape = "list_column"
+layout = "item"
+values = [{ name = "m" }, { name = "n" }, { name = "o" }]
+page_size = 2
+"#).unwrap();
+    fs::write(input.join("post.md"), "---\ntitle: post\nfooter: recent\n---\n# post\n").unwrap();
+
+    let report = Site::new(&input).output(&output).build()?;
+    assert!(report.pages.contains(&output.join("page/2/index.html")), "{:?}", report.pages);
+    assert!(report.pages.contains(&output.join("page/right/items/3/index.html")), "{:?}", report.pages);
+    assert!(!output.join("page/3").exists());
+
+    let left = fs::read_to_string(output.join("page/2/index.html")).unwrap();
+    assert!(left.contains("ITEM c") && !left.contains("ITEM a"), "{}", left);
+    assert!(left.contains("ITEM v") && !left.contains("ITEM x"), "{}", left);
+
+    let right = fs::read_to_string(output.join("page/right/items/3/index.html")).unwrap();
+    assert!(right.contains("ITEM z") && !right.contains("ITEM v"), "{}", right);
+    assert!(right.contains("ITEM a") && !right.contains("ITEM c"), "{}", right);
+
+    let post = fs::read_to_string(output.join("post/index.html")).unwrap();
+    assert!(post.contains("ITEM m") && !post.contains("ITEM o"), "{}", post);
+    let post_second = fs::read_to_string(output.join("post/page/2/index.html")).unwrap();
+    assert!(post_second.contains("ITEM o") && !post_second.contains("ITEM m"), "{}", post_second);
+    assert!(!post.contains("data-prema") && !post_second.contains("data-prema"), "{}", post);
+    Ok(())
+}
+
+/// data 폴더의 toml, yaml, json, csv가 list의 data와 {data.*}로 쓰이고, data 폴더는 페이지가 되지 않는지
+#[test]
+fn test_site_data() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_data");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(input.join("data/shop")).unwrap();
+    fs::write(input.join("data/company.toml"), "name = \"Sharks\"\n\n[[members]]\nname = \"kim\"\nrole = \"dev\"\n").unwrap();
+    fs::write(input.join("data/team.yaml"), "- name: lee\n  rank: 2\n- name: park\n  rank: 1\n- name: choi\n  rank: 3\n").unwrap();
+    fs::write(input.join("data/shop/products.json"), r#"[{"name": "pen", "price": 3}, {"name": "ink", "price": 12, "note": null}]"#).unwrap();
+    fs::write(input.join("data/roster.csv"), "name,team\njung,web\nhan,app\nyoon,web\n").unwrap();
+    fs::write(input.join("index.toml"), r#"[root]
+shape = "column"
+
+[root.company]
+shape = "text"
+text = "COMPANY {data.company.name}"
+if = "data.company.name"
+
+[root.members]
+shape = "list_column"
+layout = "person"
+data = "company.members"
+
+[root.team]
+shape = "list_column"
+layout = "person"
+data = "team"
+order_by = "rank"
+limit = 2
+
+[root.products]
+shape = "list_column"
+layout = "product"
+data = "shop.products"
+filter = "price > 5"
+
+[root.roster]
+shape = "list_column"
+layout = "person"
+data = "roster"
+filter = 'team == "web"'
+order_by = "name_desc"
+
+[person]
+shape = "text"
+text = "PERSON {name}"
+
+[product]
+shape = "text"
+text = "PRODUCT {name} {price} {data.company.name | upper}"
+"#).unwrap();
+
+    Site::new(&input).output(&output).build()?;
+    let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+    let found: Vec<&str> = index_html
+        .lines()
+        .map(|x| x.trim())
+        .filter(|x| ["COMPANY", "PERSON", "PRODUCT"].iter().any(|k| x.starts_with(k)))
+        .collect();
+    assert_eq!(found, vec![
+        "COMPANY Sharks",
+        "PERSON kim",
+        "PERSON park",
+        "PERSON lee",
+        "PRODUCT ink 12 SHARKS",
+        "PERSON yoon",
+        "PERSON jung",
+    ]);
+    assert!(!input.join("data/index.md").exists());
+    assert!(!output.join("data").exists());
+    Ok(())
+}
+
+/// root가 아닌 곳의 data 폴더는 보통 페이지인지
+#[test]
+fn test_site_nested_data_page() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_nested_data_page");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(input.join("docs/data")).unwrap();
+    fs::write(input.join("index.md"), "# Home\n").unwrap();
+    fs::write(input.join("docs/index.md"), "# Docs\n").unwrap();
+    fs::write(input.join("docs/data/spec.md"), "# Spec\n\nSPEC BODY\n").unwrap();
+
+    Site::new(&input).output(&output).build()?;
+    let spec_html = fs::read_to_string(output.join("docs/data/spec/index.html")).unwrap();
+    assert!(spec_html.contains("SPEC BODY"), "{}", spec_html);
+    Ok(())
+}
+
+/// schema 에러는 경고로 알리고 빌드를 계속하고, strict면 빌드를 멈추는지
+#[test]
+fn test_site_strict() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_strict");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(input.join("index.toml"), r#"[root]
+shape = "text"
+text = "HOME"
+
+[helper]
+note = "not a layout"
+"#).unwrap();
+
+    let report = Site::new(&input).output(&output).build()?;
+    assert!(fs::read_to_string(output.join("index.html")).unwrap().contains("HOME"));
+    let missing_shape = report.diagnostics.iter().find(|x| x.message == "missing shape").unwrap();
+    assert!(!missing_shape.is_error());
+
+    let error = Site::new(&input).output(&output).strict(true).build().unwrap_err();
+    match error {
+        Error::Diagnostics(diagnostics) => assert!(diagnostics.iter().any(|x| x.is_error() && x.message == "missing shape")),
+        error => panic!("expected diagnostics, got {}", error),
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeSet;
+use std::fs;
+
+use crate::error::Result;
+use crate::site::Site;
+use crate::tailwind::{collect_classes, generate_css};
+
+fn css(classes: &[&str]) -> String {
+    generate_css(&classes.iter().map(|x| x.to_string()).collect()).0
+}
+
+/// 쓰인 class만, variant와 임의 값까지 css가 되는지
+#[test]
+fn test_generate_css() {
+    let css = css(&["p-4", "w-[200px]", "md:w-1/2", "dark:bg-slate-900", "hover:text-blue-700", "text-[#2563eb]", "bg-black/50", "not-a-class"]);
+    assert!(css.contains(".p-4{padding:1rem}"), "{}", css);
+    assert!(css.contains(r".w-\[200px\]{width:200px}"), "{}", css);
+    assert!(css.contains(r"@media (min-width: 768px){.md\:w-1\/2{width:50%}}"), "{}", css);
+    assert!(css.contains(r"@media (prefers-color-scheme: dark){.dark\:bg-slate-900{background-color:#0f172a}}"), "{}", css);
+    assert!(css.contains(r".hover\:text-blue-700:hover{color:#1d4ed8}"), "{}", css);
+    assert!(css.contains(r".text-\[\#2563eb\]{color:#2563eb}"), "{}", css);
+    assert!(css.contains(r".bg-black\/50{background-color:rgb(0 0 0 / 0.5)}"), "{}", css);
+    assert!(!css.contains("not-a-class"));
+    let (_, unsupported) = generate_css(&["p-4".to_string(), "not-a-class".to_string(), "language-rust".to_string()].into());
+    assert_eq!(unsupported, vec!["not-a-class"]);
+    assert!(!css.contains(".m-4"));
+
+    // breakpoint가 큰 쪽이 뒤에 와야 덮어쓴다.
+    let css = self::css(&["lg:p-8", "p-2", "sm:p-4", "px-1"]);
+    let at = |x: &str| css.find(x).unwrap();
+    assert!(at(".p-2{") < at(".px-1{"));
+    assert!(at(".px-1{") < at(r".sm\:p-4{"));
+    assert!(at(r".sm\:p-4{") < at(r".lg\:p-8{"));
+}
+
+/// html에서 class를 모으는지
+#[test]
+fn test_collect_classes() {
+    let mut classes = BTreeSet::new();
+    collect_classes(r#"<div class="flex  p-4"><p class="md:text-xl">a</p><a href="/">b</a></div>"#, &mut classes);
+    assert_eq!(classes.into_iter().collect::<Vec<String>>(), vec!["flex", "md:text-xl", "p-4"]);
+}
+
+/// --tailwind 로 빌드하면 CDN 대신 만든 css를 쓰는지
+#[test]
+fn test_site_tailwind() -> Result<()> {
+    let root = std::env::temp_dir().join("prema_test_site_tailwind");
+    let _ = fs::remove_dir_all(&root);
+    let input = root.join("md");
+    let output = root.join("html");
+    fs::create_dir_all(&input).unwrap();
+    fs::write(input.join("index.md"), "---\ntitle: home\n---\n# Home\n").unwrap();
+    fs::write(input.join("post.md"), "---\ntitle: post\n---\n# Post\n").unwrap();
+    fs::create_dir_all(input.join("about")).unwrap();
+    fs::write(input.join("about/index.toml"), "[root]\nshape = \"text\"\ntext = \"about\"\ncustom_class = \"p-4 fancy-card\"\n").unwrap();
+
+    let report = Site::new(&input).output(&output).tailwind(true).build()?;
+    // 모르는 class는 css에서 빠지고 경고가 된다.
+    let messages: Vec<String> = report.diagnostics.iter().map(|x| x.to_string()).collect();
+    assert_eq!(messages.len(), 1, "{:?}", messages);
+    assert!(messages[0].contains("tailwind.css") && messages[0].contains("\"fancy-card\""), "{:?}", messages);
+    let css = fs::read_to_string(output.join("assets/tailwind.css")).unwrap();
+    assert!(css.contains(".text-4xl{font-size:2.25rem;line-height:2.5rem}"), "{}", css);
+
+    let post_html = fs::read_to_string(output.join("post/index.html")).unwrap();
+    assert!(post_html.contains(r#"<link href="../assets/tailwind.css" rel="stylesheet">"#), "{}", post_html);
+    assert!(!post_html.contains("cdn.tailwindcss.com"));
+    Ok(())
+}
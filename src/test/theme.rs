@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::fs;
+
+use maplit::hashmap;
+use toml_edit::Table;
+
+use crate::layout::common::get_tomlview_for_key;
+use crate::layout::schema::validate_toml;
+use crate::layout::{layouts_from_toml, toml_to_html};
+use crate::markdown::markdown_to_htmlview;
+use crate::theme::Theme;
+
+const THEME: &str = r##"[theme]
+primary = "#2563eb"
+text = "#0f172a"
+text_dark = "#f8fafc"
+surface = "#fafaf9"
+font_heading = "Georgia, serif"
+radius = "8px"
+"##;
+
+fn theme_tables() -> HashMap<String, Table> {
+    let doc = THEME.parse::<toml_edit::DocumentMut>().unwrap();
+    hashmap! { "theme".to_string() => doc["theme"].as_table().unwrap().clone() }
+}
+
+fn theme() -> Theme {
+    Theme::from_layout_tables(&theme_tables())
+}
+
+/// token이 있는 색만 바뀌고, dark 쪽은 {token}_dark 가 있을 때만 바뀐다.
+#[test]
+fn test_color_class() {
+    let theme = theme();
+    assert_eq!(theme.color_class("text", "text", "text-slate-900 dark:text-white"), "text-[#0f172a] dark:text-[#f8fafc]");
+    assert_eq!(theme.color_class("primary", "text", "text-blue-700 dark:text-blue-500"), "text-[#2563eb] dark:text-blue-500");
+    assert_eq!(theme.color_class("muted", "text", "text-slate-500 dark:text-slate-400"), "text-slate-500 dark:text-slate-400");
+    assert_eq!(theme.font_class("font_heading").unwrap(), "[font-family:Georgia,serif]");
+    assert_eq!(theme.radius_class().unwrap(), "rounded-[8px]");
+}
+
+/// layout에서 {theme.primary} 를 쓸 수 있는지, body가 surface 색을 쓰는지
+#[test]
+fn test_theme_placeholder() {
+    let folder = std::env::temp_dir().join("prema_test_theme_placeholder");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, format!("[root]\nshape = \"text\"\ntext = \"hi\"\ncolor = \"{{theme.primary}}\"\n\n{}", THEME)).unwrap();
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+
+    let layouts = layouts_from_toml(&index_path).unwrap();
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let class = view.htmlview(None).attrs.get("class").unwrap().clone();
+    assert!(class.contains("text-[#2563eb]"), "{}", class);
+
+    let html = toml_to_html(&index_path, layouts).unwrap();
+    assert!(html.contains(r#"<body class="mx-auto w-screen h-screen bg-[#fafaf9]">"#), "{}", html);
+}
+
+/// markdown의 제목과 인용은 theme token을 쓴다.
+#[test]
+fn test_theme_markdown() {
+    let folder = std::env::temp_dir().join("prema_test_theme_markdown");
+    fs::create_dir_all(&folder).unwrap();
+    let md_path = folder.join("a.md");
+    fs::write(&md_path, "---\ntitle: a\n---\n# a\n\n> quote\n").unwrap();
+
+    let html = markdown_to_htmlview(&md_path, true, &theme_tables()).unwrap().html();
+    assert!(html.contains(r#"<h1 class="text-4xl font-bold mb-8 mt-5 text-[#0f172a] dark:text-[#f8fafc] [font-family:Georgia,serif]">"#), "{}", html);
+    assert!(html.contains("rounded-[8px]"), "{}", html);
+    let blockquote = html.split("<blockquote").nth(1).and_then(|x| x.split('>').next()).unwrap();
+    assert!(blockquote.contains("bg-[#fafaf9] dark:bg-gray-800"), "{}", blockquote);
+    assert!(!blockquote.contains("bg-gray-50"), "{}", blockquote);
+
+    let html = markdown_to_htmlview(&md_path, false, &hashmap! {}).unwrap().html();
+    assert!(html.contains(r#"<h1 class="text-4xl font-bold mb-8 mt-5 text-slate-900">"#), "{}", html);
+}
+
+/// 색 token과 radius의 틀린 값이 에러가 되는지
+#[test]
+fn test_theme_validation() {
+    let folder = std::env::temp_dir().join("prema_test_theme_validation");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, "[theme]\nprimary = \"#12\"\nradius = \"round\"\naccent = \"anything\"\n\n[root]\nshape = \"box\"\n").unwrap();
+    let found: Vec<String> = validate_toml(&index_path, &hashmap! {}).into_iter().map(|x| x.key).collect();
+    assert_eq!(found, vec!["theme.primary", "theme.radius"]);
+}
@@ -0,0 +1,738 @@
+use std::any::Any;
+use std::fs;
+use std::path::PathBuf;
+
+use maplit::hashmap;
+use toml_edit::InlineTable;
+
+use crate::error::{locate_key, Error, Location, Severity};
+use crate::html::HTMLView;
+use crate::layout::common::{get_tomlview_for_key, item_to_string};
+use crate::layout::registry::register_shape;
+use crate::layout::{layouts_from_toml, TOMLView};
+use crate::layout::schema::validate_toml;
+
+/// 알 수 없는 shape는 key path와 줄 번호를 가진 에러가 되는지
+#[test]
+fn test_unknown_shape_error() {
+    let folder = std::env::temp_dir().join("prema_test_unknown_shape");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, "# layout\n\n[root]\nshape = \"colum\"\n").unwrap();
+
+    match get_tomlview_for_key(&index_path, "root", None, None, &hashmap! {}) {
+        Err(Error::Layout { key, message, location, .. }) => {
+            assert_eq!(key, "root");
+            assert_eq!(message, "unknown shape \"colum\"");
+            assert_eq!(location, Some(Location { line: 3, column: 2 }));
+        }
+        _ => panic!("expected layout error"),
+    }
+}
+
+#[test]
+fn test_locate_key() {
+    let source = "[root]\nshape = \"column\"\n\n[root.title]\nshape = \"text\"\ntext = \"hi\"\n";
+    assert_eq!(locate_key(source, "root.title"), Some(Location { line: 4, column: 7 }));
+    assert_eq!(locate_key(source, "root.title.text"), Some(Location { line: 6, column: 1 }));
+    assert_eq!(locate_key(source, "root.nothing"), None);
+}
+
+/// 오타 key, 잘못된 값, 알 수 없는 shape를 위치와 함께 알려주는지
+#[test]
+fn test_validate_toml() {
+    let folder = std::env::temp_dir().join("prema_test_validate_toml");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, r##"[root]
+shape = "column"
+heigth = "100%"
+
+[root.image]
+shape = "image"
+width = "{width}"
+content_size = "stretch"
+
+[root.title]
+shape = "txt"
+
+[list_item]
+shape = "text"
+background = "#12"
+"##).unwrap();
+
+    let found: Vec<(Severity, String, Option<Location>, String)> = validate_toml(&index_path, &hashmap! {})
+        .into_iter()
+        .map(|x| (x.severity, x.key, x.location, x.message))
+        .collect();
+    assert_eq!(found, vec![
+        (Severity::Warning, "root.heigth".to_string(), Some(Location { line: 3, column: 1 }), "unknown key \"heigth\" for shape \"column\", did you mean \"height\"?".to_string()),
+        (Severity::Error, "root.image.content_size".to_string(), Some(Location { line: 8, column: 1 }), "invalid value \"stretch\", expected one of cover, contain, fill, none, scale-down".to_string()),
+        (Severity::Error, "root.title".to_string(), Some(Location { line: 10, column: 7 }), "unknown shape \"txt\"".to_string()),
+        (Severity::Error, "list_item.background".to_string(), Some(Location { line: 15, column: 1 }), "invalid color \"#12\", expected a color like \"#fff\"".to_string()),
+    ]);
+}
+
+fn write_index(name: &str, source: &str) -> std::path::PathBuf {
+    let folder = std::env::temp_dir().join(name);
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, source).unwrap();
+    index_path
+}
+
+/// params가 있는 layout을 shape처럼 쓰고, 인자 타입이 틀리면 에러가 나는지
+#[test]
+fn test_component() {
+    let index_path = write_index("prema_test_component", r##"[root]
+shape = "column"
+
+[root.first]
+shape = "card"
+title = "Hello"
+count = 3
+width = "300px"
+
+[root.second]
+shape = "card"
+title = "World"
+count = "many"
+
+[card]
+shape = "column"
+width = "100px"
+params = { title = "string", count = { type = "integer", default = 1 } }
+
+[card.title]
+shape = "text"
+text = "{title} x{count}"
+"##);
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let diagnostics = validate_toml(&index_path, &hashmap! {});
+    let found: Vec<(String, String)> = diagnostics.into_iter().map(|x| (x.key, x.message)).collect();
+    assert_eq!(found, vec![(
+        "root.second.count".to_string(),
+        "param \"count\" expects integer, found \"many\" for component \"card\"".to_string(),
+    )]);
+
+    let first = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = first.htmlview(None).html();
+    assert!(html.contains("Hello x3"));
+    assert!(html.contains("w-[300px]"));
+    assert!(!html.contains("World"));
+}
+
+struct BadgeView {
+    key: String,
+    text: String,
+    views: Vec<Box<dyn TOMLView>>,
+}
+
+impl TOMLView for BadgeView {
+    fn as_any(&self) -> &dyn Any { self }
+    fn index_path(&self) -> PathBuf { PathBuf::new() }
+    fn shape(&self) -> String { "badge".to_string() }
+    fn key(&self) -> String { self.key.clone() }
+    fn width(&self) -> String { "wrap".to_string() }
+    fn height(&self) -> String { "wrap".to_string() }
+    fn background(&self) -> String { "transparent".to_string() }
+    fn path(&self) -> String { String::new() }
+    fn value(&self) -> Option<InlineTable> { None }
+    fn dark(&self) -> bool { false }
+    fn views(&self) -> &Vec<Box<dyn TOMLView>> { &self.views }
+    fn htmlview(&self, _super_view: Option<&dyn TOMLView>) -> HTMLView {
+        HTMLView {
+            tag: "span".to_string(),
+            attrs: hashmap! { "class".to_string() => "badge".to_string() },
+            value: self.text.clone(),
+            views: vec![],
+        }
+    }
+}
+
+/// 등록한 shape가 index.toml에서 기본 shape처럼 만들어지는지
+#[test]
+fn test_register_shape() {
+    register_shape("badge", |_index_path, key, table, value, _super_view, _layout_tables| {
+        Ok(Box::new(BadgeView {
+            key: key.to_string(),
+            text: item_to_string(table, "text", "", value),
+            views: vec![],
+        }))
+    });
+    let index_path = write_index("prema_test_register_shape", r##"[root]
+shape = "column"
+
+[root.new]
+shape = "badge"
+text = "NEW"
+"##);
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &hashmap! {}).unwrap();
+    let html = view.htmlview(None).html();
+    assert!(html.contains("<span class=\"badge\">"));
+    assert!(html.contains("NEW"));
+}
+
+/// breakpoint별 열 개수, gap, 하위 table과 values, span이 grid class로 나오는지
+#[test]
+fn test_grid() {
+    let index_path = write_index("prema_test_grid", r##"[root]
+shape = "grid"
+columns = { base = 1, md = 2, lg = 4 }
+gap = "16px"
+row_gap = "8px"
+layout = "cell"
+values = [{ name = "a" }, { name = "b", span = 2 }]
+
+[root.hero]
+shape = "text"
+text = "hero"
+span = { md = 2, lg = 4 }
+
+[cell]
+shape = "text"
+text = "{name}"
+"##);
+    let layouts = layouts_from_toml(&index_path).unwrap();
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    assert_eq!(view.views().len(), 3);
+    let html_view = view.htmlview(None);
+    let class = html_view.attrs.get("class").unwrap();
+    assert!(class.contains("grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-[16px] gap-y-[8px]"), "{}", class);
+
+    // base가 없으면 md보다 작은 화면에는 span class가 없다.
+    let spans: Vec<&String> = html_view.views.iter().map(|x| x.attrs.get("class").unwrap()).collect();
+    assert!(spans[0].ends_with(" md:col-span-2 lg:col-span-4"), "{}", spans[0]);
+    assert!(!spans[0].contains(" col-span-2"), "{}", spans[0]);
+    assert!(!spans[1].contains("col-span"));
+    assert!(spans[2].ends_with("col-span-2"));
+}
+
+/// mdgrid가 markdown 파일마다 layout을 만들고 row_count도 열 개수로 받는지
+#[test]
+fn test_mdgrid() {
+    let index_path = write_index("prema_test_mdgrid", r##"[root]
+shape = "mdgrid"
+row_count = 2
+files = "*.md"
+layout = "cell"
+order_by = "title"
+
+[cell]
+shape = "text"
+text = "{title}"
+"##);
+    let folder = index_path.parent().unwrap();
+    fs::write(folder.join("b.md"), "---\ntitle: second\n---\n").unwrap();
+    fs::write(folder.join("a.md"), "---\ntitle: first\n---\n").unwrap();
+    fs::write(folder.join("c.md"), "---\ntitle: draft\npublish: false\n---\n").unwrap();
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    assert_eq!(view.shape(), "mdgrid");
+    let html = view.htmlview(None).html();
+    assert!(html.contains("grid grid-cols-2"));
+    let first = html.find("first").unwrap();
+    let second = html.find("second").unwrap();
+    assert!(first < second);
+    assert!(!html.contains("draft"));
+}
+
+/// mdlist_row도 mdlist_column과 같은 목록을 써서 order_by, filter, limit을 따르는지
+#[test]
+fn test_mdlist_row_query() {
+    let index_path = write_index("prema_test_mdlist_row_query", r##"[root]
+shape = "mdlist_row"
+files = "*.md"
+layout = "cell"
+order_by = "rank_desc"
+filter = "rank >= 2"
+limit = 2
+
+[cell]
+shape = "text"
+text = "POST {title}"
+"##);
+    let folder = index_path.parent().unwrap();
+    for (title, rank) in [("a", 1), ("b", 3), ("c", 5), ("d", 2)] {
+        fs::write(folder.join(format!("{}.md", title)), format!("---\ntitle: {}\nrank: {}\n---\n", title, rank)).unwrap();
+    }
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    assert_eq!(view.shape(), "mdlist_row");
+    let html = view.htmlview(None).html();
+    let found: Vec<&str> = html.lines().map(|x| x.trim()).filter(|x| x.starts_with("POST")).collect();
+    assert_eq!(found, vec!["POST c", "POST b"]);
+}
+
+/// 여러 order_by key, filter, offset, limit, group_by가 mdlist에 쓰이는지
+#[test]
+fn test_mdlist_query() {
+    let index_path = write_index("prema_test_mdlist_query", r##"[root]
+shape = "mdlist_column"
+files = "*.md"
+layout = "cell"
+order_by = ["created_desc", "title"]
+filter = ["tags contains \"rust\"", "rank >= 2"]
+offset = 1
+limit = 3
+group_by = "created:year"
+group_layout = "year"
+
+[cell]
+shape = "text"
+text = "POST {title} {group}"
+
+[year]
+shape = "text"
+text = "YEAR {group} ({count})"
+"##);
+    let folder = index_path.parent().unwrap();
+    let posts = [
+        ("a", "2024-03-01", "[rust]", 10),
+        ("b", "2024-03-01", "[rust, web]", 9),
+        ("c", "2023-12-31", "[rust]", 2),
+        ("d", "2023-01-05", "rust", 3),
+        ("e", "2025-01-01", "[rust]", 5),
+        ("f", "2024-05-01", "[go]", 5),
+        ("g", "2022-05-01", "[rust]", 1),
+    ];
+    for (title, created, tags, rank) in posts {
+        fs::write(folder.join(format!("{}.md", title)), format!("---
+title: {}
+created: {}
+tags: {}
+rank: {}
+---
+", title, created, tags, rank)).unwrap();
+    }
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    let found: Vec<&str> = html
+        .lines()
+        .map(|x| x.trim())
+        .filter(|x| x.starts_with("POST") || x.starts_with("YEAR"))
+        .collect();
+    // e는 offset으로 빠지고, f는 tags, g는 rank 때문에 빠진다.
+    assert_eq!(found, vec![
+        "YEAR 2024 (2)",
+        "POST a 2024",
+        "POST b 2024",
+        "YEAR 2023 (1)",
+        "POST c 2023",
+    ]);
+}
+
+/// order_by가 group_by와 다른 key여도 묶음의 머리가 한 번씩만 나오는지
+#[test]
+fn test_mdlist_group_unsorted() {
+    let index_path = write_index("prema_test_mdlist_group_unsorted", r##"[root]
+shape = "mdlist_column"
+files = "*.md"
+layout = "cell"
+order_by = "title"
+group_by = "category"
+group_layout = "category"
+limit = 3
+
+[cell]
+shape = "text"
+text = "POST {title}"
+
+[category]
+shape = "text"
+text = "CATEGORY {group} ({count})"
+"##);
+    let folder = index_path.parent().unwrap();
+    for (title, category) in [("a", "rust"), ("b", "go"), ("c", "rust"), ("d", "go")] {
+        fs::write(folder.join(format!("{}.md", title)), format!("---\ntitle: {}\ncategory: {}\n---\n", title, category)).unwrap();
+    }
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    let found: Vec<&str> = html
+        .lines()
+        .map(|x| x.trim())
+        .filter(|x| x.starts_with("POST") || x.starts_with("CATEGORY"))
+        .collect();
+    // 묶음은 처음 나온 a의 rust부터, limit은 묶은 뒤에 자른다.
+    assert_eq!(found, vec![
+        "CATEGORY rust (2)",
+        "POST a",
+        "POST c",
+        "CATEGORY go (1)",
+        "POST b",
+    ]);
+}
+
+/// 잘못된 filter는 validate_toml의 에러가 되는지
+#[test]
+fn test_mdlist_filter_error() {
+    let index_path = write_index("prema_test_mdlist_filter_error", r##"[root]
+shape = "mdlist_column"
+files = "*.md"
+layout = "cell"
+filter = "tags has rust"
+order_by = 3
+"##);
+    let found: Vec<(String, String)> = validate_toml(&index_path, &hashmap! {})
+        .into_iter()
+        .map(|x| (x.key, x.message))
+        .collect();
+    assert_eq!(found, vec![
+        ("root.filter".to_string(), "invalid filter \"tags has rust\", expected \"key operator value\" like tags contains \"rust\"".to_string()),
+        ("root.order_by".to_string(), "invalid value 3, expected a string or an array of strings".to_string()),
+    ]);
+}
+
+/// 여러 files 패턴, ** 하위 폴더, ! 로 빼기, 하위 폴더 index.md의 {url}
+#[test]
+fn test_mdlist_files() {
+    let index_path = write_index("prema_test_mdlist_files", r##"[root]
+shape = "mdlist_column"
+files = ["posts/*.md", "notes/**/*.md", "!notes/drafts/**"]
+layout = "cell"
+order_by = "title"
+
+[cell]
+shape = "text"
+text = "POST {title} {url}"
+"##);
+    let folder = index_path.parent().unwrap();
+    let files = [
+        ("index.md", "self"),
+        ("posts/a.md", "a"),
+        ("posts/b/index.md", "b"),
+        ("posts/b/deep.md", "deep"),
+        ("notes/c.md", "c"),
+        ("notes/x/index.md", "x"),
+        ("notes/x/y.md", "y"),
+        ("notes/drafts/z.md", "z"),
+    ];
+    for (path, title) in files {
+        let path = folder.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("---\ntitle: {}\n---\n", title)).unwrap();
+    }
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    let found: Vec<&str> = html.lines().map(|x| x.trim()).filter(|x| x.starts_with("POST")).collect();
+    // posts/*.md는 한 단계만, notes/**는 하위 폴더까지 찾는다.
+    assert_eq!(found, vec![
+        "POST a posts/a/",
+        "POST c notes/c/",
+        "POST x notes/x/",
+        "POST y notes/x/y/",
+    ]);
+}
+
+/// mdlist 항목에 url, word_count, reading_time, excerpt, modified, first_image가 채워지는지
+#[test]
+fn test_mdlist_computed_metas() {
+    let index_path = write_index("prema_test_mdlist_computed_metas", r##"[root]
+shape = "mdlist_column"
+files = "posts/**/*.md"
+layout = "cell"
+order_by = "title"
+
+[cell]
+shape = "text"
+text = "POST {title}|{url}|{word_count}|{reading_time}|{excerpt}|{first_image}"
+"##);
+    let folder = index_path.parent().unwrap();
+    fs::create_dir_all(folder.join("posts/b")).unwrap();
+    fs::write(folder.join("posts/a.md"), r##"---
+title: a
+---
+# Hello
+
+First para words here.
+
+Second *para*.
+
+<!-- more -->
+
+Third.
+
+![cover](img/cover.png)
+"##).unwrap();
+    fs::write(folder.join("posts/b/index.md"), r##"---
+title: b
+excerpt: written by hand
+---
+Only the first paragraph.
+
+![remote](https://example.com/x.png)
+
+Not this one.
+"##).unwrap();
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    let found: Vec<&str> = html.lines().map(|x| x.trim()).filter(|x| x.starts_with("POST")).collect();
+    // frontmatter에 있는 excerpt가 계산한 것보다 앞선다.
+    assert_eq!(found, vec![
+        "POST a|posts/a/|8|1|First para words here. Second para.|posts/a/img/cover.png",
+        "POST b|posts/b/|7|1|written by hand|https://example.com/x.png",
+    ]);
+
+    let md_path = folder.join("posts/a.md");
+    let (_, node) = crate::markdown::read_markdown(&md_path).unwrap();
+    let metas = crate::markdown::computed_metas(&md_path, &node);
+    let modified = metas.get("modified").and_then(|x| x.as_str()).unwrap();
+    assert!(regex::Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap().is_match(modified), "{}", modified);
+}
+
+/// 잘못된 glob 패턴은 panic 대신 validate_toml의 에러가 되는지
+#[test]
+fn test_mdlist_files_error() {
+    let index_path = write_index("prema_test_mdlist_files_error", r##"[root]
+shape = "mdlist_column"
+files = ["posts/*.md", "!posts/[a"]
+layout = "cell"
+"##);
+    let found: Vec<(String, String)> = validate_toml(&index_path, &hashmap! {})
+        .into_iter()
+        .map(|x| (x.key, x.message))
+        .collect();
+    assert_eq!(found, vec![
+        ("root.files".to_string(), "invalid glob pattern \"!posts/[a\", Pattern syntax error near position 6: invalid range pattern".to_string()),
+    ]);
+    let layouts = layouts_from_toml(&index_path).unwrap();
+    assert!(get_tomlview_for_key(&index_path, "root", None, None, &layouts).is_ok());
+}
+
+/// {key | 필터} 가 date, upper, lower, truncate, default로 바뀌는지
+#[test]
+fn test_placeholder_filters() {
+    let table: toml_edit::DocumentMut = r##"
+date = "{created | date:\"%Y년 %m월 %d일\"}"
+short_year = "{short | date:\"%Y.%m.%d\"}"
+plain_date = "{created | date}"
+bad_date = "{title | date:\"%Y\"}"
+upper = "{title | upper}"
+lower = "{title|lower}"
+truncate = "{summary | truncate:10}"
+short = "{title | truncate:120}"
+chain = "{summary | upper | truncate:5}"
+default = "{missing | default:\"n/a\"}"
+empty = "{blank | default:'n/a'}"
+missing = "{missing | upper}"
+pipe = "{missing | default:\"a | b\"}"
+"##.parse().unwrap();
+    let mut value = InlineTable::new();
+    value.insert("created", "2025-10-01".into());
+    value.insert("short", "25-10-01".into());
+    value.insert("title", "Hello Prema".into());
+    value.insert("summary", "A static site generator".into());
+    value.insert("blank", "".into());
+
+    let found = |key: &str| item_to_string(table.as_table(), key, "", Some(&value));
+    assert_eq!(found("date"), "2025년 10월 01일");
+    assert_eq!(found("short_year"), "2025.10.01");
+    assert_eq!(found("plain_date"), "2025-10-01");
+    assert_eq!(found("bad_date"), "Hello Prema");
+    assert_eq!(found("upper"), "HELLO PREMA");
+    assert_eq!(found("lower"), "hello prema");
+    assert_eq!(found("truncate"), "A static s…");
+    assert_eq!(found("short"), "Hello Prema");
+    assert_eq!(found("chain"), "A STA…");
+    assert_eq!(found("default"), "n/a");
+    assert_eq!(found("empty"), "n/a");
+    assert_eq!(found("missing"), "");
+    assert_eq!(found("pipe"), "a | b");
+}
+
+/// [theme]이 있어도 key로 읽을 수 없는 {} 는 그대로 두고, {{ }} 는 글자 그대로의 { } 가 되는지
+#[test]
+fn test_placeholder_literal_braces() {
+    let index_path = write_index("prema_test_placeholder_literal_braces", r##"[theme]
+primary = "#ff0000"
+
+[root]
+shape = "text"
+text = 'JSON {"a": 1} {{title}} {missing} {a b} {theme.primary} {{"b": {{"c": 2}}}}'
+"##);
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    assert!(html.contains(r#"JSON {"a": 1} {title}  {a b} #ff0000 {"b": {"c": 2}}"#), "{}", html);
+}
+
+/// placeholder가 없는 문자열의 {{, }} 는 그대로 남는지
+#[test]
+fn test_literal_braces_without_placeholders() {
+    let index_path = write_index("prema_test_literal_braces_without_placeholders", r##"[root]
+shape = "text"
+text = 'if (a) { b({"c": {"d": 1}}) }}'
+"##);
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    assert!(html.contains(r#"if (a) { b({"c": {"d": 1}}) }}"#), "{}", html);
+}
+
+/// 모르는 필터는 validate_toml의 에러가 되는지
+#[test]
+fn test_placeholder_filter_error() {
+    let index_path = write_index("prema_test_placeholder_filter_error", r##"[root]
+shape = "text"
+text = "{title | shout}"
+"##);
+    let found: Vec<(String, String)> = validate_toml(&index_path, &hashmap! {})
+        .into_iter()
+        .map(|x| (x.key, x.message))
+        .collect();
+    assert_eq!(found, vec![
+        ("root.text".to_string(), "unknown filter \"shout\" in {title | shout}, expected one of date, upper, lower, truncate, default".to_string()),
+    ]);
+}
+
+/// if, unless로 하위 layout을 빼고, 없는 값의 {key}는 빈 문자열이 되는지
+#[test]
+fn test_if_unless() {
+    let index_path = write_index("prema_test_if_unless", r##"[root]
+shape = "list_column"
+layout = "cell"
+values = [
+    { title = "a", subtitle = "first", rank = 3 },
+    { title = "b", subtitle = "", draft = true, rank = 1 },
+]
+
+[cell]
+shape = "column"
+
+[cell.title]
+shape = "text"
+text = "TITLE {title} [{subtitle}] [{missing}]"
+
+[cell.subtitle]
+shape = "text"
+if = "subtitle"
+text = "SUBTITLE {subtitle}"
+
+[cell.published]
+shape = "text"
+unless = "draft"
+text = "PUBLISHED {title}"
+
+[cell.top]
+shape = "text"
+if = "rank >= 2"
+text = "TOP {title}"
+
+[cell.own]
+shape = "text"
+value = { badge = "new" }
+if = "badge"
+text = "BADGE {badge}"
+"##);
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let layouts = layouts_from_toml(&index_path).unwrap();
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    let found: Vec<&str> = html
+        .lines()
+        .map(|x| x.trim())
+        .filter(|x| ["TITLE", "SUBTITLE", "PUBLISHED", "TOP", "BADGE"].iter().any(|k| x.starts_with(k)))
+        .collect();
+    assert_eq!(found, vec![
+        "TITLE a [first] []",
+        "SUBTITLE first",
+        "PUBLISHED a",
+        "TOP a",
+        "BADGE new",
+        "TITLE b [] []",
+        "BADGE new",
+    ]);
+
+    let index_path = write_index("prema_test_if_unless_error", r##"[root]
+shape = "text"
+if = "rank is big"
+text = "hi"
+"##);
+    let found: Vec<String> = validate_toml(&index_path, &hashmap! {}).into_iter().map(|x| x.key).collect();
+    assert_eq!(found, vec!["root.if".to_string()]);
+}
+
+/// width, height, direction의 breakpoint table과 hidden이 class로 나오는지
+#[test]
+fn test_responsive() {
+    let index_path = write_index("prema_test_responsive", r##"[root]
+shape = "column"
+width = { base = "100%", md = "50%", lg = "w-1/3" }
+direction = { base = "column", md = "row" }
+
+[root.side]
+shape = "text"
+text = "side"
+height = { base = "wrap", md = "200px", xl = "wrap" }
+hidden = ["base", "sm"]
+
+[root.ad]
+shape = "image"
+image_path = "ad.png"
+width = { lg = "100px", xxl = "1px" }
+hidden = ["2xl"]
+"##);
+    let found: Vec<String> = validate_toml(&index_path, &hashmap! {}).into_iter().map(|x| format!("{}: {}", x.key, x.message)).collect();
+    assert_eq!(found, vec!["root.ad.width: unknown breakpoint \"xxl\", expected one of base, sm, md, lg, xl, 2xl"]);
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &hashmap! {}).unwrap();
+    let html_view = view.htmlview(None);
+    let class = html_view.attrs.get("class").unwrap();
+    assert!(class.starts_with("flex flex-col md:flex-row "));
+    assert!(class.contains("w-[100%] md:w-[50%] lg:w-1/3"));
+
+    let side = html_view.views[0].attrs.get("class").unwrap();
+    assert!(side.contains("md:h-[200px] xl:h-auto max-sm:hidden sm:max-md:hidden"));
+    let ad = html_view.views[1].attrs.get("class").unwrap();
+    assert!(ad.contains("lg:w-[100px] 2xl:hidden"));
+}
+
+/// Site 없이 layout table들 옆에 data table을 넘겨도 {data.*} 와 list의 data를 쓰는지
+#[test]
+fn test_data_table() {
+    let index_path = write_index("prema_test_data_table", r#"[root]
+shape = "column"
+
+[root.name]
+shape = "text"
+text = "NAME {data.company.name}"
+
+[root.members]
+shape = "list_column"
+layout = "member"
+data = "company.members"
+
+[member]
+shape = "text"
+text = "MEMBER {name} {data.company.name}"
+"#);
+    let data = r#"company = { name = "Sharks", members = [{ name = "kim" }] }"#.parse::<toml_edit::DocumentMut>().unwrap();
+    let mut layouts = layouts_from_toml(&index_path).unwrap();
+    layouts.insert("data".to_string(), data.as_table().clone());
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    assert!(html.contains("NAME Sharks"), "{}", html);
+    assert!(html.contains("MEMBER kim Sharks"), "{}", html);
+}
@@ -0,0 +1,188 @@
+use std::fs;
+
+use maplit::hashmap;
+
+use crate::layout::common::get_tomlview_for_key;
+use crate::layout::layouts_from_toml;
+use crate::layout::schema::validate_toml;
+
+/// 모든 shape가 같이 받는 key들
+const COMMON: &str = r##"id = "main"
+margin = 4
+padding = { base = "8px", md = "16px" }
+border = "1px"
+shadow = "md"
+opacity = 80
+z_index = 10
+aria-label = "main area"
+custom_class = "custom"
+"##;
+
+/// COMMON이 만드는 class, shape마다 다른 class 뒤에 붙는다.
+const COMMON_CLASS: &str = "m-4 p-[8px] md:p-[16px] border-[1px] shadow-md opacity-[80%] z-[10] custom";
+
+/// shape 하나를 그리고 가장 바깥 태그를 돌려준다.
+fn snapshot(name: &str, root: &str, rest: &str) -> String {
+    let folder = std::env::temp_dir().join(format!("prema_test_view_{}", name));
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join("a.md"), "---\ntitle: a\n---\n# a\n").unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, format!("[root]\n{}{}\n{}", root, COMMON, rest)).unwrap();
+
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+    let layouts = layouts_from_toml(&index_path).unwrap();
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html = view.htmlview(None).html();
+    html.lines().next().unwrap().to_string()
+}
+
+/// column은 세로 flex
+#[test]
+fn test_column_snapshot() {
+    let html = snapshot("column", "shape = \"column\"\n", "");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="flex flex-col overflow-hidden {}" id="main" style="background:transparent; gap:0px;"></div>"#, COMMON_CLASS));
+}
+
+/// scroll_row는 가로 스크롤, 문자열 width는 공통 class 앞에 온다.
+#[test]
+fn test_scroll_row_snapshot() {
+    let html = snapshot("scroll_row", "shape = \"scroll_row\"\nwidth = \"100px\"\n", "");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="flex flex-row overflow-x-auto w-[100px] {}" id="main" style="background:transparent; gap:0px;"></div>"#, COMMON_CLASS));
+}
+
+/// box는 relative
+#[test]
+fn test_box_snapshot() {
+    let html = snapshot("box", "shape = \"box\"\n", "");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="relative {}" id="main" style="background:transparent;"></div>"#, COMMON_CLASS));
+}
+
+/// text는 span, background가 class로 들어간다.
+#[test]
+fn test_text_snapshot() {
+    let html = snapshot("text", "shape = \"text\"\ntext = \"hi\"\n", "");
+    assert_eq!(html, format!(r#"<span aria-label="main area" class="bg-[transparent] text-slate-950 justify-left items-top flex {}" id="main">"#, COMMON_CLASS));
+}
+
+/// image는 src를 가진 img
+#[test]
+fn test_image_snapshot() {
+    let html = snapshot("image", "shape = \"image\"\nimage_path = \"a.png\"\n", "");
+    assert_eq!(html, format!(r#"<img aria-label="main area" class="flex {}" id="main" src="a.png" style="background:transparent;"></img>"#, COMMON_CLASS));
+}
+
+/// nav는 style 없이 class만
+#[test]
+fn test_nav_snapshot() {
+    let html = snapshot("nav", "shape = \"nav\"\ntitle = \"t\"\n", "");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="{}" id="main">"#, COMMON_CLASS));
+}
+
+/// list_column은 values마다 layout
+#[test]
+fn test_list_column_snapshot() {
+    let html = snapshot("list_column", "shape = \"list_column\"\nlayout = \"cell\"\nvalues = [{ name = \"a\" }]\n", "[cell]\nshape = \"text\"\ntext = \"{name}\"\n");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="flex flex-col overflow-y-auto {}" id="main" style="background:transparent; gap:0px;">"#, COMMON_CLASS));
+}
+
+/// mdlist_row는 markdown마다 layout
+#[test]
+fn test_mdlist_row_snapshot() {
+    let html = snapshot("mdlist_row", "shape = \"mdlist_row\"\nlayout = \"cell\"\nfiles = \"*.md\"\n", "[cell]\nshape = \"text\"\ntext = \"{title}\"\n");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="flex flex-row overflow-x-auto {}" id="main" style="background:transparent; gap:0px;">"#, COMMON_CLASS));
+}
+
+/// markdown은 세로 flex 안에 markdown
+#[test]
+fn test_markdown_snapshot() {
+    let html = snapshot("markdown", "shape = \"markdown\"\nmarkdown_path = \"a.md\"\n", "");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="flex flex-col overflow-y-auto {}" id="main" style="background:transparent; gap:0px;">"#, COMMON_CLASS));
+}
+
+/// grid는 열 개수와 gap
+#[test]
+fn test_grid_snapshot() {
+    let html = snapshot("grid", "shape = \"grid\"\ncolumns = 2\n", "");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="grid grid-cols-2 gap-[0px] overflow-x-auto {}" id="main" style="background:transparent;"></div>"#, COMMON_CLASS));
+}
+
+/// embed는 wrap일 때 embed한 layout의 크기를 따른다.
+#[test]
+fn test_embed_snapshot() {
+    let html = snapshot("embed", "shape = \"embed\"\nlayout = \"cell\"\n", "[cell]\nshape = \"text\"\ntext = \"x\"\nwidth = \"w-10\"\n");
+    assert_eq!(html, format!(r#"<div aria-label="main area" class="w-10 {}" id="main" style="background:transparent; gap:0px;">"#, COMMON_CLASS));
+}
+
+/// id가 없으면 key가 id, embed는 embed_ 를 붙인다.
+#[test]
+fn test_default_id() {
+    let folder = std::env::temp_dir().join("prema_test_view_default_id");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, "[root]\nshape = \"column\"\n\n[root.menu]\nshape = \"embed\"\nlayout = \"cell\"\n\n[cell]\nshape = \"text\"\n").unwrap();
+    let layouts = layouts_from_toml(&index_path).unwrap();
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &layouts).unwrap();
+    let html_view = view.htmlview(None);
+    assert_eq!(html_view.attrs.get("id").unwrap(), "root");
+    assert_eq!(html_view.views[0].attrs.get("id").unwrap(), "embed_menu");
+}
+
+/// 공통 key의 틀린 값이 에러가 되는지
+#[test]
+fn test_common_keys_validation() {
+    let folder = std::env::temp_dir().join("prema_test_view_validation");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, "[root]\nshape = \"box\"\nopacity = 150\nshadow = \"huge\"\nmargin = \"wide\"\nz_index = \"top\"\naria-hidden = true\n").unwrap();
+    let found: Vec<String> = validate_toml(&index_path, &hashmap! {}).into_iter().map(|x| x.key).collect();
+    assert_eq!(found, vec!["root.opacity", "root.shadow", "root.margin", "root.z_index"]);
+}
+
+/// 방향별 padding, margin, border table, 글자 간격 key가 class로 나오는지
+#[test]
+fn test_style_keys() {
+    let folder = std::env::temp_dir().join("prema_test_view_style");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, r##"[root]
+shape = "text"
+text = "title"
+margin = { x = "auto", top = { base = 2, md = 4 } }
+padding = { y = "8px", x = { md = "16px" } }
+border = { width = "2px", color = "#e5e7eb", radius = "full" }
+line_height = 1.5
+letter_spacing = "0.05em"
+max_width = { base = "prose", lg = "960px" }
+text_transform = "uppercase"
+"##).unwrap();
+    assert!(validate_toml(&index_path, &hashmap! {}).is_empty());
+
+    let view = get_tomlview_for_key(&index_path, "root", None, None, &hashmap! {}).unwrap();
+    let html_view = view.htmlview(None);
+    let class = html_view.attrs.get("class").unwrap();
+    assert_eq!(class, "bg-[transparent] text-slate-950 justify-left items-top flex mx-auto mt-2 md:mt-4 md:px-[16px] py-[8px] border-[2px] border-[#e5e7eb] rounded-full leading-[1.5] tracking-[0.05em] max-w-prose lg:max-w-[960px] uppercase");
+}
+
+/// style key의 틀린 값과 방향, border key가 에러가 되는지
+#[test]
+fn test_style_keys_validation() {
+    let folder = std::env::temp_dir().join("prema_test_view_style_validation");
+    fs::create_dir_all(&folder).unwrap();
+    let index_path = folder.join("index.toml");
+    fs::write(&index_path, r##"[root]
+shape = "box"
+padding = { x = "8px", middle = 2 }
+border = { width = "thick" }
+line_height = "tall"
+max_width = "huge"
+text_transform = "upper"
+"##).unwrap();
+    let found: Vec<String> = validate_toml(&index_path, &hashmap! {}).into_iter().map(|x| format!("{}: {}", x.key, x.message)).collect();
+    assert_eq!(found, vec![
+        "root.padding: unknown side \"middle\", expected one of x, y, top, bottom, left, right",
+        "root.border: width: invalid length \"thick\", expected a length like \"10px\"",
+        "root.line_height: invalid line height \"tall\", expected a number like 1.5 or a length like \"24px\"",
+        "root.max_width: invalid max width \"huge\", expected a length or one of none, xs, sm, md, lg, xl, 2xl, 3xl, 4xl, 5xl, 6xl, 7xl, full, prose, screen",
+        "root.text_transform: invalid value \"upper\", expected one of uppercase, lowercase, capitalize, none",
+    ]);
+}
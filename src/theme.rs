@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use toml_edit::{InlineTable, Table, Value};
+
+/// 색, 글꼴, 모서리 token을 담는 최상위 table 이름
+pub const THEME_TABLE: &str = "theme";
+
+/// 색 token, {token}_dark 로 dark 모드 색을 따로 줄 수 있다.
+pub const COLOR_TOKENS: [&str; 5] = ["primary", "surface", "text", "muted", "border"];
+
+/// 글꼴 token
+pub const FONT_TOKENS: [&str; 3] = ["font_body", "font_heading", "font_mono"];
+
+/// primary, primary_dark 처럼 색 token인지
+pub fn is_color_token(key: &str) -> bool {
+    let token = key.strip_suffix("_dark").unwrap_or(key);
+    COLOR_TOKENS.contains(&token)
+}
+
+/// ### theme
+/// index.toml의 [theme] table
+///
+/// ```toml
+/// [theme]
+/// primary = "#2563eb"
+/// text = "#0f172a"
+/// text_dark = "#f8fafc"
+/// font_body = "Inter, sans-serif"
+/// radius = "8px"
+/// ```
+///
+/// layout에서는 {theme.primary} 처럼 쓰고, markdown과 nav는 기본 색 대신 token을 쓴다.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    tokens: HashMap<String, String>,
+}
+
+impl Theme {
+    pub fn from_table(table: &Table) -> Theme {
+        let tokens = table
+            .iter()
+            .filter_map(|(k, item)| match item.as_value()? {
+                Value::String(s) => Some((k.to_string(), s.value().trim().to_string())),
+                Value::Integer(n) => Some((k.to_string(), n.value().to_string())),
+                Value::Float(x) => Some((k.to_string(), x.value().to_string())),
+                _ => None,
+            })
+            .collect();
+        Theme { tokens }
+    }
+
+    /// 물려받은 layout table들 중 [theme]
+    pub fn from_layout_tables(layout_tables: &HashMap<String, Table>) -> Theme {
+        layout_tables
+            .get(THEME_TABLE)
+            .map(Theme::from_table)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, token: &str) -> Option<&str> {
+        self.tokens.get(token).map(|s| s.as_str()).filter(|s| !s.is_empty())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// {theme.primary} 를 채우는 데 쓰는 값
+    pub fn to_inline_table(&self) -> InlineTable {
+        let mut table = InlineTable::new();
+        for (k, v) in &self.tokens {
+            table.insert(k, Value::from(v.as_str()));
+        }
+        table
+    }
+
+    /// ### color class
+    /// token이 있으면 기본 class의 색을 token 색으로 바꾼다.
+    ///
+    /// ("text", "text", "text-slate-900 dark:text-white") -> text = "#222" 이면 "text-[#222] dark:text-white"
+    ///
+    /// dark 쪽은 {token}_dark 가 있을 때만 바뀐다.
+    pub fn color_class(&self, token: &str, prefix: &str, default: &str) -> String {
+        let (dark, light): (Vec<&str>, Vec<&str>) = default
+            .split_whitespace()
+            .partition(|class| class.starts_with("dark:") || class.contains(":dark:"));
+        let light = match self.get(token) {
+            Some(color) => vec![format!("{}-[{}]", prefix, arbitrary(color))],
+            None => light.iter().map(|x| x.to_string()).collect(),
+        };
+        let dark = match self.get(&format!("{}_dark", token)) {
+            Some(color) => vec![format!("dark:{}-[{}]", prefix, arbitrary(color))],
+            None => dark.iter().map(|x| x.to_string()).collect(),
+        };
+        light.into_iter().chain(dark).collect::<Vec<String>>().join(" ")
+    }
+
+    /// font_heading = "Inter, sans-serif" -> [font-family:Inter,sans-serif]
+    pub fn font_class(&self, token: &str) -> Option<String> {
+        self.get(token).map(|font| format!("[font-family:{}]", arbitrary(&font.replace(", ", ","))))
+    }
+
+    /// radius = "8px" -> rounded-[8px]
+    pub fn radius_class(&self) -> Option<String> {
+        self.get("radius").map(|radius| format!("rounded-[{}]", arbitrary(radius)))
+    }
+}
+
+/// tailwind 임의 값 안에서는 공백 대신 _
+fn arbitrary(value: &str) -> String {
+    value.trim().replace(' ', "_")
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use serde_yaml::Value as YamlValue;
-use toml_edit::{value, InlineTable, Value as TomlEditValue};
+use toml_edit::{Array, InlineTable, Value as TomlEditValue};
 
 pub fn yaml_hashmap_to_inline_table(map: &HashMap<String, YamlValue>) -> InlineTable {
     let mut table = InlineTable::new();
@@ -14,21 +14,21 @@ pub fn yaml_hashmap_to_inline_table(map: &HashMap<String, YamlValue>) -> InlineT
     table
 }
 
-fn yaml_to_toml_edit_value(yaml: &YamlValue) -> Option<TomlEditValue> {
+pub fn yaml_to_toml_edit_value(yaml: &YamlValue) -> Option<TomlEditValue> {
     match yaml {
-        // YamlValue::Mapping(m) => {
-        //     let mut inline = InlineTable::new();
-        //     for (k, v) in m {
-        //         if let YamlValue::String(key) = k {
-        //             inline.insert(key.clone(), yaml_to_toml_edit_value(v));
-        //         }
-        //     }
-        //     TomlEditValue::InlineTable(inline)
-        // },
-        // YamlValue::Sequence(seq) => {
-        //     let arr: Vec<TomlEditValue> = seq.iter().map(yaml_to_toml_edit_value).collect();
-        //     TomlEditValue::Array(arr.into())
-        // },
+        YamlValue::Mapping(m) => {
+            let mut inline = InlineTable::new();
+            for (k, v) in m {
+                if let (YamlValue::String(key), Some(value)) = (k, yaml_to_toml_edit_value(v)) {
+                    inline.insert(key, value);
+                }
+            }
+            Some(TomlEditValue::InlineTable(inline))
+        },
+        YamlValue::Sequence(seq) => {
+            let arr: Array = seq.iter().filter_map(yaml_to_toml_edit_value).collect();
+            Some(TomlEditValue::Array(arr))
+        },
         YamlValue::String(s) => Some(TomlEditValue::from(s)),
         YamlValue::Number(n) => {
             if let Some(i) = n.as_i64() {